@@ -3,66 +3,1143 @@
 
 //! Command line arguments handling functions.
 
-use crate::{config::Config, emulator::Mode};
-use std::{env, process};
+mod completions;
 
-/// Handle command line arguments.
+use crate::config::Config;
+use chip8::emulator::{
+    EmulatorResult, Engine, Mode, Settings, Timing, capture, config_file,
+    headless, quirks::Quirks, random::Algorithm, rom, terminal, vip,
+};
+use std::path::Path;
+
+pub use completions::{Shell, completions};
+
+/// Default number of frames run by `test` command.
+const DEFAULT_FRAMES: u64 = 60;
+
+/// Command line option identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Opt {
+    Help,
+    Config,
+    Seed,
+    Rnd,
+    Engine,
+    Timing,
+    Quirks,
+    Speed,
+    Romdb,
+    NoRomdb,
+    LoadAddr,
+    Keymap,
+    Braille,
+    Wav,
+    Frequency,
+    Volume,
+    SampleRate,
+    Screenshot,
+    ShotAt,
+    Gif,
+    Scale,
+    Palette,
+    Record,
+    Play,
+    Output,
+    Format,
+    Trace,
+    Profile,
+    Coverage,
+    Compare,
+    TraceDiff,
+    Vip,
+    Monitor,
+    Frames,
+    Keys,
+    Expect,
+    Snapshot,
+    Bench,
+}
+
+/// Command line option description.
+struct OptSpec {
+    /// Option identifier.
+    id: Opt,
+    /// Long option name without dashes.
+    long: &'static str,
+    /// Short option letter.
+    short: Option<char>,
+    /// Value placeholder (option is a flag if not set).
+    value: Option<&'static str>,
+    /// Help text lines.
+    help: &'static str,
+}
+
+/// Command description.
+struct CommandSpec {
+    /// Command name.
+    name: &'static str,
+    /// Operand placeholders.
+    operands: &'static [&'static str],
+    /// Short command description.
+    about: &'static str,
+    /// Accepted options.
+    options: &'static [Opt],
+}
+
+/// Descriptions of all command line options.
+const OPTIONS: [OptSpec; 38] = [
+    OptSpec {
+        id: Opt::Help,
+        long: "help",
+        short: Some('h'),
+        value: None,
+        help: "display command options",
+    },
+    OptSpec {
+        id: Opt::Config,
+        long: "config",
+        short: None,
+        value: Some("<file>"),
+        help: "configuration file to use instead of\nthe default one",
+    },
+    OptSpec {
+        id: Opt::Seed,
+        long: "seed",
+        short: Some('s'),
+        value: Some("<number>"),
+        help: "seed random number generator",
+    },
+    OptSpec {
+        id: Opt::Rnd,
+        long: "rnd",
+        short: None,
+        value: Some("<splitmix|vip>"),
+        help: "random number generation algorithm\n\
+               (vip requires --vip machine)",
+    },
+    OptSpec {
+        id: Opt::Engine,
+        long: "engine",
+        short: None,
+        value: Some("<reference|interpreter|recompiler>"),
+        help: "instruction execution engine\n(default: interpreter)",
+    },
+    OptSpec {
+        id: Opt::Timing,
+        long: "timing",
+        short: None,
+        value: Some("<instructions|vip>"),
+        help: "fixed instructions per frame or\n\
+               COSMAC VIP machine cycles timing\n\
+               (default: instructions)",
+    },
+    OptSpec {
+        id: Opt::Quirks,
+        long: "quirks",
+        short: None,
+        value: Some("<chip8|schip|xochip>"),
+        help: "interpreter quirks preset\n\
+               (default: from ROM database or chip8)",
+    },
+    OptSpec {
+        id: Opt::Speed,
+        long: "speed",
+        short: None,
+        value: Some("<number>"),
+        help: "instructions per frame\n(default: from ROM database or 10)",
+    },
+    OptSpec {
+        id: Opt::Romdb,
+        long: "romdb",
+        short: None,
+        value: Some("<file>"),
+        help: "ROM database in community CHIP-8\n\
+               database programs.json format\n\
+               (required to look up ROM settings)",
+    },
+    OptSpec {
+        id: Opt::NoRomdb,
+        long: "no-romdb",
+        short: None,
+        value: None,
+        help: "do not look up ROM settings by SHA-1",
+    },
+    OptSpec {
+        id: Opt::LoadAddr,
+        long: "load-addr",
+        short: None,
+        value: Some("<address>"),
+        help: "program load and entry address\n\
+               (default: 0x200, ETI-660: 0x600)",
+    },
+    OptSpec {
+        id: Opt::Keymap,
+        long: "keymap",
+        short: None,
+        value: Some("<keys>"),
+        help: "16 keyboard keys for keypad 0-F\n(default: x123qweasdzc4rfv)",
+    },
+    OptSpec {
+        id: Opt::Braille,
+        long: "braille",
+        short: None,
+        value: None,
+        help: "render display with braille patterns",
+    },
+    OptSpec {
+        id: Opt::Wav,
+        long: "wav",
+        short: None,
+        value: Some("<file>"),
+        help: "write session audio to WAV file",
+    },
+    OptSpec {
+        id: Opt::Frequency,
+        long: "frequency",
+        short: None,
+        value: Some("<hz>"),
+        help: "beeper tone frequency (default: 440)",
+    },
+    OptSpec {
+        id: Opt::Volume,
+        long: "volume",
+        short: None,
+        value: Some("<percent>"),
+        help: "beeper volume 0-100 (default: 25)",
+    },
+    OptSpec {
+        id: Opt::SampleRate,
+        long: "sample-rate",
+        short: None,
+        value: Some("<hz>"),
+        help: "audio sample rate (default: 44100)",
+    },
+    OptSpec {
+        id: Opt::Screenshot,
+        long: "screenshot",
+        short: None,
+        value: Some("<file>"),
+        help: "save .ppm or .png screenshot at exit,\n\
+               at '--shot-at' frames or on [Tab]",
+    },
+    OptSpec {
+        id: Opt::ShotAt,
+        long: "shot-at",
+        short: None,
+        value: Some("<frame,...>"),
+        help: "frames to take screenshots after",
+    },
+    OptSpec {
+        id: Opt::Gif,
+        long: "gif",
+        short: None,
+        value: Some("<file>"),
+        help: "record session to animated GIF",
+    },
+    OptSpec {
+        id: Opt::Scale,
+        long: "scale",
+        short: None,
+        value: Some("<number>"),
+        help: "image pixels per display pixel\n(default: 8)",
+    },
+    OptSpec {
+        id: Opt::Palette,
+        long: "palette",
+        short: None,
+        value: Some("<RRGGBB,RRGGBB>"),
+        help: "lit and unlit pixel colors",
+    },
+    OptSpec {
+        id: Opt::Record,
+        long: "record",
+        short: None,
+        value: Some("<file>"),
+        help: "record keypad input movie",
+    },
+    OptSpec {
+        id: Opt::Play,
+        long: "play",
+        short: None,
+        value: Some("<file>"),
+        help: "replay keypad input movie",
+    },
+    OptSpec {
+        id: Opt::Output,
+        long: "output",
+        short: Some('o'),
+        value: Some("<file>"),
+        help: "output file",
+    },
+    OptSpec {
+        id: Opt::Format,
+        long: "format",
+        short: Some('f'),
+        value: Some("<binary|hex|ihex>"),
+        help: "output program format: raw bytes,\n\
+               hexadecimal text or Intel HEX\n(default: binary)",
+    },
+    OptSpec {
+        id: Opt::Trace,
+        long: "trace",
+        short: Some('t'),
+        value: None,
+        help: "print execution trace",
+    },
+    OptSpec {
+        id: Opt::Profile,
+        long: "profile",
+        short: Some('p'),
+        value: None,
+        help: "print instruction hotspots report",
+    },
+    OptSpec {
+        id: Opt::Coverage,
+        long: "coverage",
+        short: None,
+        value: None,
+        help: "print code and branch coverage report",
+    },
+    OptSpec {
+        id: Opt::Compare,
+        long: "compare",
+        short: Some('c'),
+        value: Some("<quirks,quirks>"),
+        help: "run program under two quirks presets\n\
+               and report first divergence",
+    },
+    OptSpec {
+        id: Opt::TraceDiff,
+        long: "trace-diff",
+        short: None,
+        value: Some("<trace>"),
+        help: "report first divergence of execution\n\
+               trace file operand and given trace",
+    },
+    OptSpec {
+        id: Opt::Vip,
+        long: "vip",
+        short: None,
+        value: Some("<interpreter>"),
+        help: "run program on emulated COSMAC VIP\n\
+               with original interpreter image and\n\
+               report first divergence",
+    },
+    OptSpec {
+        id: Opt::Monitor,
+        long: "monitor",
+        short: None,
+        value: Some("<file>"),
+        help: "COSMAC VIP monitor ROM image",
+    },
+    OptSpec {
+        id: Opt::Frames,
+        long: "frames",
+        short: None,
+        value: Some("<number>"),
+        help: "number of frames to run (default: 60)",
+    },
+    OptSpec {
+        id: Opt::Keys,
+        long: "keys",
+        short: None,
+        value: Some("<file>"),
+        help: "key script ('<frame>:+<key>' presses\n\
+               and '<frame>:-<key>' releases)",
+    },
+    OptSpec {
+        id: Opt::Expect,
+        long: "expect",
+        short: None,
+        value: Some("<file>"),
+        help: "expected ASCII-art or hash screen\nsnapshot",
+    },
+    OptSpec {
+        id: Opt::Snapshot,
+        long: "snapshot",
+        short: None,
+        value: Some("<file>"),
+        help: "write ASCII-art screen snapshot",
+    },
+    OptSpec {
+        id: Opt::Bench,
+        long: "bench",
+        short: None,
+        value: None,
+        help: "compare throughput of execution engines",
+    },
+];
+
+/// Descriptions of all commands.
+const COMMANDS: [CommandSpec; 10] = [
+    CommandSpec {
+        name: "run",
+        operands: &["<file>"],
+        about: "run program in terminal",
+        options: &[
+            Opt::Help,
+            Opt::Config,
+            Opt::Seed,
+            Opt::Rnd,
+            Opt::Engine,
+            Opt::Timing,
+            Opt::Quirks,
+            Opt::Speed,
+            Opt::Romdb,
+            Opt::NoRomdb,
+            Opt::LoadAddr,
+            Opt::Keymap,
+            Opt::Braille,
+            Opt::Wav,
+            Opt::Frequency,
+            Opt::Volume,
+            Opt::SampleRate,
+            Opt::Screenshot,
+            Opt::ShotAt,
+            Opt::Gif,
+            Opt::Scale,
+            Opt::Palette,
+            Opt::Record,
+            Opt::Play,
+        ],
+    },
+    CommandSpec {
+        name: "disasm",
+        operands: &["<file>"],
+        about: "print program assembly listing",
+        options: &[Opt::Help, Opt::Config, Opt::LoadAddr, Opt::Output],
+    },
+    CommandSpec {
+        name: "asm",
+        operands: &["<source>"],
+        about: "assemble Octo source to program file\n\
+                (default output: <source>.ch8)",
+        options: &[Opt::Help, Opt::Output, Opt::Format],
+    },
+    CommandSpec {
+        name: "debug",
+        operands: &["<file>"],
+        about: "step program frame by frame with console\n\
+                commands or run analysis selected by option",
+        options: &[
+            Opt::Help,
+            Opt::Config,
+            Opt::Seed,
+            Opt::Rnd,
+            Opt::Engine,
+            Opt::Timing,
+            Opt::Quirks,
+            Opt::Speed,
+            Opt::Romdb,
+            Opt::NoRomdb,
+            Opt::LoadAddr,
+            Opt::Trace,
+            Opt::Profile,
+            Opt::Coverage,
+            Opt::Compare,
+            Opt::TraceDiff,
+            Opt::Vip,
+            Opt::Monitor,
+        ],
+    },
+    CommandSpec {
+        name: "info",
+        operands: &["<file>"],
+        about: "print program ROM information",
+        options: &[
+            Opt::Help,
+            Opt::Config,
+            Opt::Quirks,
+            Opt::Speed,
+            Opt::Romdb,
+            Opt::NoRomdb,
+            Opt::LoadAddr,
+        ],
+    },
+    CommandSpec {
+        name: "test",
+        operands: &["<file>"],
+        about: "run program without display for number\n\
+                of frames and print or check the screen",
+        options: &[
+            Opt::Help,
+            Opt::Config,
+            Opt::Seed,
+            Opt::Rnd,
+            Opt::Engine,
+            Opt::Timing,
+            Opt::Quirks,
+            Opt::Speed,
+            Opt::Romdb,
+            Opt::NoRomdb,
+            Opt::LoadAddr,
+            Opt::Frames,
+            Opt::Keys,
+            Opt::Expect,
+            Opt::Snapshot,
+            Opt::Bench,
+            Opt::Screenshot,
+            Opt::ShotAt,
+            Opt::Gif,
+            Opt::Scale,
+            Opt::Palette,
+            Opt::Record,
+            Opt::Play,
+        ],
+    },
+    CommandSpec {
+        name: "lint",
+        operands: &["<file>"],
+        about: "check program for call stack overflow,\n\
+                unreachable code and suspicious jumps\n\
+                and stores",
+        options: &[
+            Opt::Help,
+            Opt::Config,
+            Opt::Romdb,
+            Opt::NoRomdb,
+            Opt::LoadAddr,
+        ],
+    },
+    CommandSpec {
+        name: "config",
+        operands: &[],
+        about: "print effective configuration of\n\
+                configuration file and options",
+        options: &[
+            Opt::Help,
+            Opt::Config,
+            Opt::Quirks,
+            Opt::Speed,
+            Opt::Romdb,
+            Opt::Keymap,
+            Opt::Braille,
+            Opt::Frequency,
+            Opt::Volume,
+            Opt::SampleRate,
+            Opt::Scale,
+            Opt::Palette,
+            Opt::Monitor,
+        ],
+    },
+    CommandSpec {
+        name: "completions",
+        operands: &["<bash|zsh|fish>"],
+        about: "print shell completion script",
+        options: &[Opt::Help],
+    },
+    CommandSpec {
+        name: "help",
+        operands: &["[command]"],
+        about: "display commands or command options",
+        options: &[],
+    },
+];
+
+/// Parsed command.
+#[derive(Debug)]
+pub enum Command {
+    /// Print usage of all commands or options of single command.
+    Help(Option<&'static str>),
+    /// Print software version.
+    Version,
+    /// Print shell completion script.
+    Completions(Shell),
+    /// Print effective configuration.
+    Config,
+    /// Run emulator operation mode on target file.
+    Emulate(Mode, String),
+}
+
+/// Parsed command line.
+#[derive(Debug)]
+pub struct Options {
+    /// Command to execute.
+    pub command: Command,
+    /// Emulator runtime settings.
+    pub settings: Settings,
+    /// Default file paths.
+    pub paths: config_file::Paths,
+}
+
+impl Options {
+    /// Construct new `Options` object with default settings.
+    ///
+    /// # Parameters
+    /// - `command` - given command to execute.
+    ///
+    /// # Returns
+    /// - New `Options` object.
+    fn new(command: Command) -> Self {
+        Self {
+            command,
+            settings: Settings::default(),
+            paths: config_file::Paths::default(),
+        }
+    }
+}
+
+/// Command specific option values.
+#[derive(Debug, Default)]
+struct Values {
+    /// Headless run options.
+    headless: headless::Options,
+    /// Number of frames to run.
+    frames: Option<u64>,
+    /// Output file name.
+    output: Option<String>,
+    /// Output program format.
+    format: Option<rom::Format>,
+    /// Operation mode selected by option with the option.
+    mode: Option<(Opt, Mode)>,
+}
+
+/// Parse command line arguments.
 ///
-/// # Returns
+/// Configuration file from `--config` or default location is applied
+/// first, so command line options take precedence over file values.
 ///
-/// Tuple of:
-/// - Emulator mode.
-/// - Target filename.
-pub fn handle_args() -> (Mode, String) {
-    let args: Vec<String> = env::args().collect();
-    let argc = args.len();
-    let name = Config::name();
+/// # Parameters
+/// - `args`   - given command line arguments without program name.
+/// - `config` - given default configuration file location.
+///
+/// # Returns
+/// - Parsed command line - in case of success.
+/// - `Err`               - otherwise.
+pub fn parse(
+    args: &[String],
+    config: Option<&Path>,
+) -> EmulatorResult<Options> {
+    let Some(name) = args.first() else {
+        return Err("no command given".to_string());
+    };
+
+    let command = match name.as_str() {
+        "-h" | "--help" | "help" => {
+            let command = match args.get(1) {
+                Some(name) => Some(find_command(name)?.name),
+                None => None,
+            };
+
+            return Ok(Options::new(Command::Help(command)));
+        }
+        "-v" | "--version" | "version" => {
+            return Ok(Options::new(Command::Version));
+        }
+        name => find_command(name)?,
+    };
+
+    let mut options = Vec::new();
+    let mut operands = Vec::new();
+    let mut rest = args[1..].iter();
+    let mut only_operands = false;
+
+    while let Some(arg) = rest.next() {
+        if only_operands || !arg.starts_with('-') || arg == "-" {
+            operands.push(arg.clone());
+            continue;
+        }
+
+        if arg == "--" {
+            only_operands = true;
+            continue;
+        }
+
+        let (key, inline) = match arg.split_once('=') {
+            Some((key, value)) if arg.starts_with("--") => {
+                (key, Some(value.to_string()))
+            }
+            _ => (arg.as_str(), None),
+        };
+
+        let spec = find_option(command, key)?;
+        let value = match (spec.value, inline) {
+            (None, None) => None,
+            (None, Some(_)) => {
+                return Err(format!("option '--{}' takes no value", spec.long));
+            }
+            (Some(_), Some(value)) => Some(value),
+            (Some(_), None) => match rest.next() {
+                Some(value) => Some(value.clone()),
+                None => {
+                    return Err(format!(
+                        "option '--{}' requires a value",
+                        spec.long
+                    ));
+                }
+            },
+        };
+
+        options.push((spec.id, value.unwrap_or_default()));
+    }
+
+    if options.iter().any(|(id, _)| *id == Opt::Help) {
+        return Ok(Options::new(Command::Help(Some(command.name))));
+    }
+
+    if operands.len() != command.operands.len() {
+        return Err(format!(
+            "usage: {} {} [options] {}",
+            Config::name(),
+            command.name,
+            command.operands.join(" ")
+        ));
+    }
+
+    let mut result = Options::new(Command::Config);
+    let file = options.iter().rev().find(|(id, _)| *id == Opt::Config);
+
+    match (file, config) {
+        (Some((_, filename)), _) => config_file::load(
+            Path::new(filename),
+            &mut result.settings,
+            &mut result.paths,
+        )?,
+        (None, Some(path)) if path.exists() => {
+            config_file::load(path, &mut result.settings, &mut result.paths)?
+        }
+        _ => {}
+    }
 
-    if argc < 2 {
-        println!("{name}: Use '-h' or '--help' for usage.");
-        process::exit(1);
+    let mut values = Values::default();
+
+    for (id, value) in &options {
+        apply(*id, value, &mut result, &mut values)?;
     }
 
-    let mut mode = Mode::Emulator;
-    let mut filename: String = Default::default();
-
-    for (i, arg) in args[1..argc].iter().enumerate() {
-        match arg.as_str() {
-            "-h" | "--help" => help(),
-            "-v" | "--version" => version(),
-            "-d" | "--disasm" => {
-                mode = Mode::Disassembler;
-                filename = get_filename(&args, i + 2);
-                break;
+    let mut operands = operands.into_iter();
+
+    result.command = match command.name {
+        "config" => Command::Config,
+        "completions" => {
+            let shell = operands.next().unwrap_or_default();
+            Command::Completions(Shell::from_name(&shell)?)
+        }
+        name => {
+            let filename = operands.next().unwrap_or_default();
+            let mode = select_mode(name, &filename, values, &result.paths)?;
+
+            // High-level CPU has no interpreter page to read the table from.
+            if result.settings.random == Algorithm::Vip
+                && !matches!(mode, Mode::Vip(_))
+            {
+                return Err(
+                    "random algorithm 'vip' requires the --vip machine"
+                        .to_string(),
+                );
             }
-            "-e" | "--emulator" => {
-                mode = Mode::Emulator;
-                filename = get_filename(&args, i + 2);
-                break;
+            let filename = match mode {
+                Mode::Assemble(..) | Mode::TraceDiff(_) => filename,
+                _ => find_program(filename, &result.paths),
+            };
+
+            Command::Emulate(mode, filename)
+        }
+    };
+
+    Ok(result)
+}
+
+/// Apply option value.
+///
+/// # Parameters
+/// - `id`      - given option identifier.
+/// - `value`   - given option value (empty for flags).
+/// - `options` - given parsed command line to update.
+/// - `values`  - given command specific option values to update.
+///
+/// # Returns
+/// - `Ok`  - in case of success.
+/// - `Err` - if value is invalid.
+fn apply(
+    id: Opt,
+    value: &str,
+    options: &mut Options,
+    values: &mut Values,
+) -> EmulatorResult<()> {
+    let settings = &mut options.settings;
+    let value = value.to_string();
+
+    match id {
+        Opt::Help | Opt::Config => {}
+        Opt::Seed => settings.seed = Some(get_number(&value)?),
+        Opt::Rnd => settings.random = Algorithm::from_name(&value)?,
+        Opt::Engine => settings.engine = Engine::from_name(&value)?,
+        Opt::Timing => settings.timing = Timing::from_name(&value)?,
+        Opt::Quirks => settings.quirks = Some(Quirks::from_preset(&value)?),
+        Opt::Speed => {
+            let speed = get_number(&value)?;
+
+            if speed == 0 {
+                return Err("speed must be positive".to_string());
             }
-            _ => {
-                println!("{name}: unknown option '{arg}'");
-                process::exit(1);
+
+            settings.speed = Some(speed as usize);
+        }
+        Opt::Romdb => settings.database = Some(value),
+        Opt::NoRomdb => settings.lookup = false,
+        Opt::LoadAddr => settings.load_addr = get_number(&value)? as usize,
+        Opt::Keymap => {
+            terminal::parse_keymap(&value)?;
+            settings.terminal.keymap = Some(value);
+        }
+        Opt::Braille => settings.terminal.glyphs = terminal::Glyphs::Braille,
+        Opt::Wav => settings.sound.wav = Some(value),
+        Opt::Frequency => {
+            let frequency = get_number(&value)?;
+
+            if !(20..=20_000).contains(&frequency) {
+                return Err("frequency must be 20-20000 Hz".to_string());
             }
+
+            settings.sound.frequency = frequency as f64;
         }
+        Opt::Volume => {
+            let volume = get_number(&value)?;
+
+            if volume > 100 {
+                return Err("volume must be 0-100".to_string());
+            }
+
+            settings.sound.volume = volume as u8;
+        }
+        Opt::SampleRate => {
+            let rate = get_number(&value)?;
+
+            if !(1_000..=192_000).contains(&rate) {
+                return Err("sample rate must be 1000-192000 Hz".to_string());
+            }
+
+            settings.sound.sample_rate = rate as u32;
+        }
+        Opt::Screenshot => settings.capture.screenshot = Some(value),
+        Opt::ShotAt => {
+            settings.capture.frames = value
+                .split(',')
+                .map(get_number)
+                .collect::<EmulatorResult<_>>()?;
+        }
+        Opt::Gif => settings.capture.gif = Some(value),
+        Opt::Scale => {
+            let scale = get_number(&value)?;
+
+            if !(1..=capture::MAX_SCALE as u64).contains(&scale) {
+                return Err(format!("scale must be 1-{}", capture::MAX_SCALE));
+            }
+
+            settings.capture.scale = scale as u32;
+        }
+        Opt::Palette => {
+            let palette = capture::parse_palette(&value)?;
+            config_file::set_palette(settings, palette);
+        }
+        Opt::Record => settings.movie.record = Some(value),
+        Opt::Play => settings.movie.play = Some(value),
+        Opt::Output => values.output = Some(value),
+        Opt::Format => {
+            values.format = Some(match value.as_str() {
+                "binary" => rom::Format::Binary,
+                "hex" => rom::Format::HexText,
+                "ihex" => rom::Format::IntelHex,
+                _ => {
+                    return Err(format!(
+                        "unknown format '{value}' (expected binary, hex or \
+                         ihex)"
+                    ));
+                }
+            });
+        }
+        Opt::Trace => select(values, id, Mode::Trace)?,
+        Opt::Profile => select(values, id, Mode::Profile)?,
+        Opt::Coverage => select(values, id, Mode::Coverage)?,
+        Opt::Compare => {
+            let Some((first, second)) = value.split_once(',') else {
+                return Err(format!(
+                    "malformed quirks pair '{value}' (expected quirks,quirks)"
+                ));
+            };
+
+            let first = Quirks::from_preset(first)?;
+            let second = Quirks::from_preset(second)?;
+
+            select(values, id, Mode::Compare(first, second))?;
+        }
+        Opt::TraceDiff => select(values, id, Mode::TraceDiff(value))?,
+        Opt::Vip => {
+            let options = vip::Options {
+                interpreter: value,
+                monitor: None,
+            };
+
+            select(values, id, Mode::Vip(options))?;
+        }
+        Opt::Monitor => options.paths.monitor = Some(value),
+        Opt::Frames => values.frames = Some(get_number(&value)?),
+        Opt::Keys => values.headless.keys = Some(value),
+        Opt::Expect => values.headless.expect = Some(value),
+        Opt::Snapshot => values.headless.snapshot = Some(value),
+        Opt::Bench => select(values, id, Mode::Bench)?,
     }
 
-    if filename.is_empty() {
-        println!("{name}: filename is empty");
-        process::exit(1);
+    Ok(())
+}
+
+/// Select operation mode by option.
+///
+/// # Parameters
+/// - `values` - given command specific option values to update.
+/// - `id`     - given option identifier.
+/// - `mode`   - given selected operation mode.
+///
+/// # Returns
+/// - `Ok`  - in case of success.
+/// - `Err` - if other mode option was already given.
+fn select(values: &mut Values, id: Opt, mode: Mode) -> EmulatorResult<()> {
+    if let Some((other, _)) = values.mode {
+        return Err(format!(
+            "options '--{}' and '--{}' can not be combined",
+            option_spec(other).long,
+            option_spec(id).long
+        ));
     }
 
-    (mode, filename)
+    values.mode = Some((id, mode));
+    Ok(())
 }
 
-/// Print list of available commands
-fn help() {
-    println!("{}\n{}", Config::title(), Config::help());
-    process::exit(0);
+/// Get operation mode of command.
+///
+/// # Parameters
+/// - `command`  - given command name.
+/// - `filename` - given command operand.
+/// - `values`   - given command specific option values.
+/// - `paths`    - given default file paths.
+///
+/// # Returns
+/// - Operation mode - in case of success.
+/// - `Err`          - otherwise.
+fn select_mode(
+    command: &str,
+    filename: &str,
+    values: Values,
+    paths: &config_file::Paths,
+) -> EmulatorResult<Mode> {
+    let selected = values.mode.map(|(_, mode)| mode);
+
+    let mode = match command {
+        "run" => Mode::Emulator,
+        "disasm" => Mode::Disassembler(values.output),
+        "asm" => {
+            let format = values.format.unwrap_or(rom::Format::Binary);
+            let output = values.output.unwrap_or_else(|| {
+                let extension = match format {
+                    rom::Format::HexText => "hex",
+                    rom::Format::IntelHex => "ihx",
+                    _ => "ch8",
+                };
+
+                Path::new(filename)
+                    .with_extension(extension)
+                    .to_string_lossy()
+                    .into_owned()
+            });
+
+            if output == filename {
+                return Err(format!("output would overwrite '{filename}'"));
+            }
+
+            Mode::Assemble(output, format)
+        }
+        "debug" => match selected {
+            Some(Mode::Vip(options)) => Mode::Vip(vip::Options {
+                monitor: paths.monitor.clone(),
+                ..options
+            }),
+            Some(mode) => mode,
+            None => Mode::Tas,
+        },
+        "info" => Mode::Info,
+        "lint" => Mode::Lint,
+        "test" => match selected {
+            Some(mode) => mode,
+            None => {
+                let frames = values.frames.unwrap_or(DEFAULT_FRAMES);
+                Mode::Headless(frames, values.headless)
+            }
+        },
+        _ => unreachable!("command '{command}' has no operation mode"),
+    };
+
+    Ok(mode)
 }
 
-/// Print current version of the program
-fn version() {
+/// Find program file, looking it up in ROMs directory if it does not exist.
+///
+/// # Parameters
+/// - `filename` - given program file name.
+/// - `paths`    - given default file paths.
+///
+/// # Returns
+/// - Program file name.
+fn find_program(filename: String, paths: &config_file::Paths) -> String {
+    if let Some(roms) = &paths.roms
+        && !Path::new(&filename).exists()
+    {
+        let path = Path::new(roms).join(&filename);
+
+        if path.exists() {
+            return path.to_string_lossy().into_owned();
+        }
+    }
+
+    filename
+}
+
+/// Find command by name.
+///
+/// # Parameters
+/// - `name` - given command name.
+///
+/// # Returns
+/// - Command description - in case of success.
+/// - `Err`                - otherwise.
+fn find_command(name: &str) -> EmulatorResult<&'static CommandSpec> {
+    COMMANDS
+        .iter()
+        .find(|command| command.name == name)
+        .ok_or_else(|| format!("unknown command '{name}'"))
+}
+
+/// Find option accepted by command.
+///
+/// # Parameters
+/// - `command` - given command description.
+/// - `key`     - given option as written (`--name` or `-x`).
+///
+/// # Returns
+/// - Option description - in case of success.
+/// - `Err`               - otherwise.
+fn find_option(
+    command: &CommandSpec,
+    key: &str,
+) -> EmulatorResult<&'static OptSpec> {
+    let spec = command
+        .options
+        .iter()
+        .map(|&id| option_spec(id))
+        .find(|spec| match key.strip_prefix("--") {
+            Some(long) => spec.long == long,
+            None => {
+                let mut chars = key.chars().skip(1);
+                let short = (chars.next(), chars.next());
+                short.0.is_some() && short.1.is_none() && spec.short == short.0
+            }
+        });
+
+    spec.ok_or_else(|| {
+        format!("unknown option '{key}' for command '{}'", command.name)
+    })
+}
+
+/// Get option description.
+///
+/// # Parameters
+/// - `id` - given option identifier.
+///
+/// # Returns
+/// - Option description.
+fn option_spec(id: Opt) -> &'static OptSpec {
+    OPTIONS
+        .iter()
+        .find(|spec| spec.id == id)
+        .expect("every option should be described")
+}
+
+/// Get usage text of all commands or options of single command.
+///
+/// # Parameters
+/// - `command` - given command name (all commands if not set).
+///
+/// # Returns
+/// - Help text.
+pub fn help(command: Option<&str>) -> String {
+    let name = Config::name();
+    let mut text = format!("{}\nUSAGE\n", Config::title());
+
+    let Some(command) = command.and_then(|name| find_command(name).ok()) else {
+        text += &format!("       {name} <command> [options] <file>\n\n\n");
+        text += &format!("DESCRIPTION\n{}\nCOMMANDS\n\n", Config::about());
+
+        for command in &COMMANDS {
+            let operands = command.operands.join(" ");
+            let mut lines = command.about.lines();
+
+            text += &format!(
+                "       {:<12}{operands:<17}{}\n",
+                command.name,
+                lines.next().unwrap_or_default()
+            );
+
+            for line in lines {
+                text += &format!("{:36}{line}\n", "");
+            }
+        }
+
+        text += &format!("\n       Use '{name} help <command>' for options.\n");
+        return text;
+    };
+
+    let operands = command.operands.join(" ");
+    text += &format!("       {name} {} [options] {operands}\n\n", command.name);
+    text += &format!(
+        "       {}\n\nOPTIONS\n\n",
+        command.about.replace('\n', "\n       ")
+    );
+
+    for &id in command.options {
+        text += &option_help(option_spec(id));
+    }
+
+    text
+}
+
+/// Format option help entry.
+///
+/// # Parameters
+/// - `spec` - given option description.
+///
+/// # Returns
+/// - Option help lines.
+fn option_help(spec: &OptSpec) -> String {
+    let short = spec.short.map(|c| format!("-{c},")).unwrap_or_default();
+    let long = format!("--{}", spec.long);
+    let mut lines = spec.help.lines();
+
+    let first = match spec.value {
+        Some(value) => value,
+        None => lines.next().unwrap_or_default(),
+    };
+
+    // Long option column fits the longest option name.
+    let width = OPTIONS
+        .iter()
+        .map(|spec| spec.long.len() + 2)
+        .max()
+        .unwrap_or_default();
+    let mut text = format!("        {short:<7}{long:<width$} {first}\n");
+
+    for line in lines {
+        text += &format!("{:indent$}{line}\n", "", indent = width + 16);
+    }
+
+    text
+}
+
+/// Get software version information.
+///
+/// # Returns
+/// - Version text.
+pub fn version() -> String {
     let title = Config::title();
     let name = Config::name();
     let version = Config::version();
@@ -71,29 +1148,132 @@ fn version() {
     let repository = Config::repository();
     let authors = Config::authors();
 
-    println!("{title}\n\n{name} ({version}) - {description}");
-    println!("Repository: {repository}\nCreated by {authors}");
-    println!("Running under {license} license.");
-
-    process::exit(0);
+    format!(
+        "{title}\n\n{name} ({version}) - {description}\n\
+         Repository: {repository}\nCreated by {authors}\n\
+         Running under {license} license.\n"
+    )
 }
 
-/// Extract filename from command line arguments.
+/// Parse decimal or hexadecimal (0x prefixed) number.
 ///
 /// # Parameters
-/// - `args` - given command line arguments.
-/// - `pos`  - given position of filename in arguments list.
+/// - `value` - given number string representation.
 ///
 /// # Returns
-/// - Target filename.
-fn get_filename(args: &[String], pos: usize) -> String {
-    let arg = match args.get(pos) {
-        Some(arg) => arg,
-        None => {
-            println!("{}: target path is not set", Config::name());
-            process::exit(1);
-        }
+/// - Parsed number - in case of success.
+/// - `Err`         - otherwise.
+fn get_number(value: &str) -> EmulatorResult<u64> {
+    let result = match value.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse(),
     };
 
-    String::from(arg)
+    result.map_err(|_| format!("invalid number '{value}'"))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// Parse command line given as single string without configuration
+    /// file.
+    fn parse_line(line: &str) -> EmulatorResult<Options> {
+        let args: Vec<String> =
+            line.split_whitespace().map(String::from).collect();
+        parse(&args, None)
+    }
+
+    #[test]
+    fn test_parse() {
+        let options =
+            parse_line("run --speed 20 -s 0x10 rom.ch8 --quirks=schip")
+                .unwrap();
+
+        assert!(matches!(
+            &options.command,
+            Command::Emulate(Mode::Emulator, filename) if filename == "rom.ch8"
+        ));
+        assert_eq!(Some(20), options.settings.speed);
+        assert_eq!(Some(16), options.settings.seed);
+        assert_eq!(Some(Quirks::schip()), options.settings.quirks);
+
+        let options =
+            parse_line("debug --compare chip8,xochip rom.ch8").unwrap();
+        assert!(matches!(
+            options.command,
+            Command::Emulate(Mode::Compare(..), _)
+        ));
+
+        let options = parse_line("asm game.8o -f ihex").unwrap();
+        assert!(matches!(
+            &options.command,
+            Command::Emulate(Mode::Assemble(output, rom::Format::IntelHex), _)
+                if output == "game.ihx"
+        ));
+
+        assert!(matches!(
+            parse_line("test rom.ch8").unwrap().command,
+            Command::Emulate(Mode::Headless(DEFAULT_FRAMES, _), _)
+        ));
+        assert!(matches!(
+            parse_line("debug --vip chip8.bin --rnd vip rom.ch8")
+                .unwrap()
+                .command,
+            Command::Emulate(Mode::Vip(_), _)
+        ));
+        assert!(matches!(
+            parse_line("lint rom.ch8").unwrap().command,
+            Command::Emulate(Mode::Lint, _)
+        ));
+        assert!(matches!(
+            parse_line("help run").unwrap().command,
+            Command::Help(Some("run"))
+        ));
+        assert!(matches!(
+            parse_line("info -h").unwrap().command,
+            Command::Help(Some("info"))
+        ));
+        assert!(matches!(
+            parse_line("completions fish").unwrap().command,
+            Command::Completions(Shell::Fish)
+        ));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for line in [
+            "",
+            "play rom.ch8",
+            "run",
+            "run a.ch8 b.ch8",
+            "run --speed 0 rom.ch8",
+            "run --speed",
+            "run --braille=yes rom.ch8",
+            "disasm --speed 10 rom.ch8",
+            "debug --trace --profile rom.ch8",
+            "asm game.8o -f gif",
+            "completions tcsh",
+            "run --rnd vip rom.ch8",
+        ] {
+            assert!(parse_line(line).is_err(), "{line}");
+        }
+    }
+
+    #[test]
+    fn test_help() {
+        let text = help(None);
+
+        for command in &COMMANDS {
+            assert!(text.contains(command.name));
+        }
+
+        let text = help(Some("run"));
+        assert!(text.contains("        -s,    --seed        <number>\n"));
+        assert!(text.contains("               --braille     render display"));
+        assert!(!text.contains("--trace"));
+
+        let text = help(Some("config"));
+        assert!(text.contains("               --sample-rate <hz>\n"));
+    }
 }
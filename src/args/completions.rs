@@ -0,0 +1,206 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Shell completion scripts generated from command descriptions.
+
+use crate::{
+    args::{COMMANDS, CommandSpec, option_spec},
+    config::Config,
+};
+use chip8::emulator::EmulatorResult;
+
+/// Shell with supported completion script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl Shell {
+    /// Get shell by name.
+    ///
+    /// # Parameters
+    /// - `name` - given shell name.
+    ///
+    /// # Returns
+    /// - Shell - in case of success.
+    /// - `Err` - otherwise.
+    pub fn from_name(name: &str) -> EmulatorResult<Self> {
+        match name {
+            "bash" => Ok(Self::Bash),
+            "zsh" => Ok(Self::Zsh),
+            "fish" => Ok(Self::Fish),
+            _ => Err(format!(
+                "unknown shell '{name}' (expected bash, zsh or fish)"
+            )),
+        }
+    }
+}
+
+/// Get shell completion script.
+///
+/// # Parameters
+/// - `shell` - given target shell.
+///
+/// # Returns
+/// - Completion script text.
+pub fn completions(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash(),
+        // Zsh runs bash completion through its compatibility layer.
+        Shell::Zsh => format!(
+            "#compdef {}\n\nautoload -U +X bashcompinit && bashcompinit\n\n{}",
+            Config::name(),
+            bash()
+        ),
+        Shell::Fish => fish(),
+    }
+}
+
+/// Get command options as written on the command line.
+///
+/// # Parameters
+/// - `command` - given command description.
+///
+/// # Returns
+/// - Long and short option names.
+fn option_names(command: &CommandSpec) -> Vec<String> {
+    let mut names = Vec::new();
+
+    for &id in command.options {
+        let spec = option_spec(id);
+        names.push(format!("--{}", spec.long));
+
+        if let Some(short) = spec.short {
+            names.push(format!("-{short}"));
+        }
+    }
+
+    names
+}
+
+/// Get bash completion script.
+///
+/// # Returns
+/// - Script text.
+fn bash() -> String {
+    let name = Config::name();
+    let commands: Vec<&str> =
+        COMMANDS.iter().map(|command| command.name).collect();
+    let commands = commands.join(" ");
+
+    let mut cases = String::new();
+
+    for command in &COMMANDS {
+        let words = match command.name {
+            "help" => commands.clone(),
+            "completions" => "bash zsh fish".to_string(),
+            _ => option_names(command).join(" "),
+        };
+
+        cases += &format!("        {}) words=\"{words}\" ;;\n", command.name);
+    }
+
+    format!(
+        r#"_{name}() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local words=""
+
+    if [ "$COMP_CWORD" -eq 1 ]; then
+        COMPREPLY=($(compgen -W "{commands}" -- "$cur"))
+        return
+    fi
+
+    case "${{COMP_WORDS[1]}}" in
+{cases}    esac
+
+    if [[ "$cur" == -* || "${{COMP_WORDS[1]}}" =~ ^(help|completions)$ ]]; then
+        COMPREPLY=($(compgen -W "$words" -- "$cur"))
+    else
+        COMPREPLY=($(compgen -f -- "$cur"))
+    fi
+}}
+
+complete -o filenames -F _{name} {name}
+"#
+    )
+}
+
+/// Get fish completion script.
+///
+/// # Returns
+/// - Script text.
+fn fish() -> String {
+    let name = Config::name();
+    let describe = |text: &str| {
+        let line = text.lines().next().unwrap_or_default();
+        format!("'{}'", line.replace('\'', "\\'"))
+    };
+
+    let mut script =
+        format!("complete -c {name} -f -n __fish_use_subcommand\n");
+
+    for command in &COMMANDS {
+        script += &format!(
+            "complete -c {name} -f -n __fish_use_subcommand -a {} -d {}\n",
+            command.name,
+            describe(command.about)
+        );
+    }
+
+    for command in &COMMANDS {
+        let condition =
+            format!("-n '__fish_seen_subcommand_from {}'", command.name);
+
+        for &id in command.options {
+            let spec = option_spec(id);
+            let short =
+                spec.short.map(|c| format!(" -s {c}")).unwrap_or_default();
+            let value = if spec.value.is_some() { " -r" } else { "" };
+
+            script += &format!(
+                "complete -c {name} {condition}{short} -l {}{value} -d {}\n",
+                spec.long,
+                describe(spec.help)
+            );
+        }
+    }
+
+    script += &format!(
+        "complete -c {name} -f -n '__fish_seen_subcommand_from help' -a '{}'\n",
+        COMMANDS
+            .iter()
+            .map(|command| command.name)
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+    script += &format!(
+        "complete -c {name} -f -n '__fish_seen_subcommand_from completions' \
+         -a 'bash zsh fish'\n"
+    );
+
+    script
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completions() {
+        let script = completions(Shell::Bash);
+
+        assert!(script.contains("complete -o filenames -F _chip8 chip8"));
+        assert!(script.contains("run disasm asm debug info test"));
+        assert!(script.contains("--speed"));
+
+        assert!(completions(Shell::Zsh).starts_with("#compdef chip8"));
+
+        let script = completions(Shell::Fish);
+        assert!(script.contains(
+            "complete -c chip8 -n '__fish_seen_subcommand_from run' -s s \
+             -l seed -r -d 'seed random number generator'"
+        ));
+    }
+}
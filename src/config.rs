@@ -77,26 +77,25 @@ impl Config {
         "#
     }
 
-    /// Get software list of available commands.
+    /// Get software detailed description.
     ///
     /// # Returns
-    /// - List of available commands.
-    pub const fn help() -> &'static str {
+    /// - Description of supported programs and configuration.
+    pub const fn about() -> &'static str {
         r#"
-USAGE
-       chip8 [options] <file>
-
-
-DESCRIPTION
-
        chip8 - CHIP-8 interpreted programming language emulator
 
-OPTIONS
+       Programs are loaded from binary ROMs, Octo cartridge GIF
+       images, hexadecimal text dumps or Intel HEX files.
 
-        -d,    --disasm     run in disassembler mode
-        -e,    --emulator   run in emulator mode
-        -h,    --help       display options list
-        -v,    --version    display version of hexd
-        "#
+       Default settings are read from TOML configuration file
+       $XDG_CONFIG_HOME/chip8/config.toml or
+       ~/.config/chip8/config.toml: speed, quirks, keymap,
+       braille, scale and palette keys, [sound] sample_rate,
+       frequency and volume and [paths] roms, romdb and
+       monitor. Command line options take precedence, speed,
+       quirks, keymap and palette from ROM database or
+       cartridge override configuration file values.
+"#
     }
 }
@@ -0,0 +1,851 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Static program analysis.
+//!
+//! Instructions are found by following control flow from program entry
+//! through jumps, calls and both outcomes of conditional skips, so sprites
+//! and other data bytes are never decoded as code. Index register value is
+//! tracked along each path to resolve memory stores targets.
+
+use crate::emulator::{cpu::STACK_SIZE, opcode::OpCode, rom::Variant};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fmt,
+};
+
+/// Memory store with statically known target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Store {
+    /// Store instruction address.
+    pub addr: usize,
+    /// First written memory address.
+    pub first: usize,
+    /// Last written memory address.
+    pub last: usize,
+}
+
+/// Static analysis warning.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    /// Nested calls need more return addresses than call stack holds.
+    StackOverflow { depth: usize },
+    /// Subroutines call each other in cycle.
+    Recursion { chain: Vec<usize> },
+    /// Instructions are never reached from program entry.
+    Unreachable { first: usize, last: usize },
+    /// Jump or call leaves program.
+    TargetOutside { addr: usize, target: usize },
+    /// Jump or call goes to odd address.
+    TargetOdd { addr: usize, target: usize },
+    /// Jump or call goes into program data.
+    TargetData { addr: usize, target: usize },
+    /// Jump or call goes into middle of instruction.
+    TargetMisaligned { addr: usize, target: usize },
+    /// Execution reaches undefined instruction.
+    Undefined { addr: usize, raw: u16 },
+    /// Memory store writes outside of program area.
+    StoreOutside(Store),
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StackOverflow { depth } => write!(
+                f,
+                "call depth {depth} overflows {STACK_SIZE}-entry stack"
+            ),
+            Self::Recursion { chain } => {
+                let chain: Vec<String> =
+                    chain.iter().map(|addr| format!("{addr:#05X}")).collect();
+                write!(f, "recursive calls {}", chain.join(" -> "))
+            }
+            Self::Unreachable { first, last } => {
+                write!(f, "<{first:#05X}> unreachable code up to {last:#05X}")
+            }
+            Self::TargetOutside { addr, target } => {
+                write!(f, "<{addr:#05X}> jump outside program to {target:#05X}")
+            }
+            Self::TargetOdd { addr, target } => {
+                write!(f, "<{addr:#05X}> jump to odd address {target:#05X}")
+            }
+            Self::TargetData { addr, target } => {
+                write!(f, "<{addr:#05X}> jump into data at {target:#05X}")
+            }
+            Self::TargetMisaligned { addr, target } => write!(
+                f,
+                "<{addr:#05X}> jump into middle of instruction at {target:#05X}"
+            ),
+            Self::Undefined { addr, raw } => {
+                write!(f, "<{addr:#05X}> undefined instruction {raw:04X}")
+            }
+            Self::StoreOutside(store) => write!(
+                f,
+                "<{:#05X}> store outside program to {:#05X}-{:#05X}",
+                store.addr, store.first, store.last
+            ),
+        }
+    }
+}
+
+/// Exploration state as (subroutine, instruction address, index register).
+type State = (usize, usize, Option<usize>);
+
+/// Static program analysis results struct.
+pub struct Analysis {
+    /// Program data bytes.
+    data: Vec<u8>,
+    /// Program load address.
+    load_addr: usize,
+    /// Reachable instructions by address.
+    instructions: BTreeMap<usize, OpCode>,
+    /// Called subroutines per subroutine address including program entry.
+    calls: BTreeMap<usize, BTreeSet<usize>>,
+    /// Memory stores with known target.
+    stores: BTreeSet<Store>,
+    /// Addresses of jumps with offset from V0.
+    indirect_jumps: BTreeSet<usize>,
+    /// Jump and call targets per instruction address.
+    jumps: BTreeMap<usize, usize>,
+    /// Memory addresses loaded into index register.
+    references: BTreeSet<usize>,
+}
+
+impl Analysis {
+    /// Analyze program.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    /// - `load_addr`    - given program load address.
+    ///
+    /// # Returns
+    /// - New `Analysis` object.
+    pub fn new(program_data: &[u8], load_addr: usize) -> Self {
+        let mut analysis = Self {
+            data: program_data.to_vec(),
+            load_addr,
+            instructions: BTreeMap::new(),
+            calls: BTreeMap::from([(load_addr, BTreeSet::new())]),
+            stores: BTreeSet::new(),
+            indirect_jumps: BTreeSet::new(),
+            jumps: BTreeMap::new(),
+            references: BTreeSet::new(),
+        };
+
+        let mut visited: HashSet<State> = HashSet::new();
+        let mut pending: Vec<State> = vec![(load_addr, load_addr, None)];
+
+        while let Some(state) = pending.pop() {
+            if visited.insert(state) {
+                analysis.explore(state, &mut pending);
+            }
+        }
+
+        analysis
+    }
+
+    /// Record instruction and queue its successors.
+    ///
+    /// # Parameters
+    /// - `state`   - given exploration state.
+    /// - `pending` - given queue of states to explore.
+    fn explore(&mut self, state: State, pending: &mut Vec<State>) {
+        let (routine, addr, index) = state;
+
+        let Some(opcode) = self.fetch(addr) else {
+            return;
+        };
+
+        self.instructions.insert(addr, opcode);
+
+        let next = addr + size(&opcode);
+        let target = opcode.addr as usize;
+        let (x, y) = (opcode.reg_x as usize, opcode.reg_y as usize);
+
+        if let Some(index) = index {
+            let last = match pattern(&opcode) {
+                "Fx55" => Some(index + x),
+                "Fx33" => Some(index + 2),
+                "5xy2" => Some(index + x.abs_diff(y)),
+                _ => None,
+            };
+
+            if let Some(last) = last {
+                self.stores.insert(Store {
+                    addr,
+                    first: index,
+                    last,
+                });
+            }
+        }
+
+        match pattern(&opcode) {
+            "00EE" | "00FD" => {}
+            "1nnn" => {
+                self.jumps.insert(addr, target);
+                pending.push((routine, target, index));
+            }
+            "2nnn" => {
+                self.jumps.insert(addr, target);
+
+                self.calls.entry(routine).or_default().insert(target);
+                self.calls.entry(target).or_default();
+
+                pending.push((target, target, index));
+                // Subroutine may change index register.
+                pending.push((routine, next, None));
+            }
+            "3xkk" | "4xkk" | "5xy0" | "9xy0" | "Ex9E" | "ExA1" => {
+                // Skip passes over whole long instruction.
+                let skipped = self
+                    .fetch(next)
+                    .map_or(next + 2, |skipped| next + size(&skipped));
+
+                pending.push((routine, next, index));
+                pending.push((routine, skipped, index));
+            }
+            "Bnnn" => {
+                self.indirect_jumps.insert(addr);
+                self.jumps.insert(addr, target);
+                pending.push((routine, target, index));
+
+                // Offset usually selects entry of jump table at base.
+                let mut entry = target + 2;
+
+                while self
+                    .fetch(entry)
+                    .is_some_and(|entry| pattern(&entry) == "1nnn")
+                {
+                    pending.push((routine, entry, index));
+                    entry += 2;
+                }
+            }
+            "Annn" => {
+                self.references.insert(target);
+                pending.push((routine, next, Some(target)));
+            }
+            "F000" => {
+                let long = self.word(addr + 2).map(usize::from);
+                self.references.extend(long);
+                pending.push((routine, next, long));
+            }
+            // Index register is advanced by offset, font or memory quirk.
+            "Fx1E" | "Fx29" | "Fx30" | "Fx55" | "Fx65" => {
+                pending.push((routine, next, None))
+            }
+            _ => pending.push((routine, next, index)),
+        }
+    }
+
+    /// Read program word.
+    ///
+    /// # Parameters
+    /// - `addr` - given memory address.
+    ///
+    /// # Returns
+    /// - Word - if both bytes are inside program.
+    /// - `None` - otherwise.
+    fn word(&self, addr: usize) -> Option<u16> {
+        let offset = addr.checked_sub(self.load_addr)?;
+        let bytes = self.data.get(offset..offset + 2)?;
+
+        Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    /// Decode program instruction.
+    ///
+    /// # Parameters
+    /// - `addr` - given instruction address.
+    ///
+    /// # Returns
+    /// - Opcode - if whole instruction is inside program.
+    /// - `None` - otherwise.
+    fn fetch(&self, addr: usize) -> Option<OpCode> {
+        let opcode = OpCode::new(self.word(addr)?);
+
+        if size(&opcode) > 2 {
+            self.word(addr + 2)?;
+        }
+
+        Some(opcode)
+    }
+
+    /// Get reachable instructions.
+    ///
+    /// # Returns
+    /// - Reachable instructions by address.
+    pub fn instructions(&self) -> &BTreeMap<usize, OpCode> {
+        &self.instructions
+    }
+
+    /// Get platform required by reachable instructions.
+    ///
+    /// # Returns
+    /// - Required platform and extension instruction patterns.
+    pub fn platform(&self) -> (Variant, Vec<&'static str>) {
+        let patterns: BTreeSet<&'static str> =
+            self.instructions.values().map(pattern).collect();
+        let extensions: Vec<&'static str> = patterns
+            .into_iter()
+            .filter(|pattern| platform(pattern) != Variant::Chip8)
+            .collect();
+        let required = extensions
+            .iter()
+            .map(|pattern| platform(pattern))
+            .max()
+            .unwrap_or(Variant::Chip8);
+
+        (required, extensions)
+    }
+
+    /// Get number of reachable instructions per instruction pattern.
+    ///
+    /// # Returns
+    /// - Pattern counts sorted by descending count.
+    pub fn histogram(&self) -> Vec<(&'static str, usize)> {
+        let mut counts: BTreeMap<&'static str, usize> = BTreeMap::new();
+
+        for opcode in self.instructions.values() {
+            *counts.entry(pattern(opcode)).or_default() += 1;
+        }
+
+        let mut histogram: Vec<(&'static str, usize)> =
+            counts.into_iter().collect();
+        histogram.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        histogram
+    }
+
+    /// Get called subroutines.
+    ///
+    /// # Returns
+    /// - Subroutine addresses.
+    pub fn subroutines(&self) -> BTreeSet<usize> {
+        self.calls.values().flatten().copied().collect()
+    }
+
+    /// Get maximum number of nested calls.
+    ///
+    /// # Returns
+    /// - Maximum call depth - if program has no recursion.
+    /// - `None`             - otherwise.
+    pub fn call_depth(&self) -> Option<usize> {
+        self.max_depth().ok()
+    }
+
+    /// Get recursive call chain.
+    ///
+    /// # Returns
+    /// - Subroutine addresses from first to repeated one - if program has
+    ///   recursion.
+    /// - `None` - otherwise.
+    pub fn recursion(&self) -> Option<Vec<usize>> {
+        self.max_depth().err()
+    }
+
+    /// Get maximum number of nested calls from program entry.
+    ///
+    /// # Returns
+    /// - Maximum call depth - if program has no recursion.
+    /// - `Err`              - recursive call chain otherwise.
+    fn max_depth(&self) -> Result<usize, Vec<usize>> {
+        self.depth(self.load_addr, &mut Vec::new(), &mut HashMap::new())
+    }
+
+    /// Get maximum number of nested calls from subroutine.
+    ///
+    /// # Parameters
+    /// - `routine` - given subroutine address.
+    /// - `path`    - given active call chain.
+    /// - `depths`  - given depths of already visited subroutines.
+    ///
+    /// # Returns
+    /// - Maximum call depth - if subroutine has no recursion.
+    /// - `Err`              - recursive call chain otherwise.
+    fn depth(
+        &self,
+        routine: usize,
+        path: &mut Vec<usize>,
+        depths: &mut HashMap<usize, usize>,
+    ) -> Result<usize, Vec<usize>> {
+        if let Some(&depth) = depths.get(&routine) {
+            return Ok(depth);
+        }
+
+        if let Some(start) = path.iter().position(|&addr| addr == routine) {
+            let mut chain = path[start..].to_vec();
+            chain.push(routine);
+
+            return Err(chain);
+        }
+
+        path.push(routine);
+
+        let mut depth = 0;
+
+        for &callee in self.calls.get(&routine).into_iter().flatten() {
+            depth = depth.max(self.depth(callee, path, depths)? + 1);
+        }
+
+        path.pop();
+        depths.insert(routine, depth);
+
+        Ok(depth)
+    }
+
+    /// Get platform features used by reachable instructions.
+    ///
+    /// # Returns
+    /// - Feature names.
+    pub fn features(&self) -> Vec<&'static str> {
+        let uses = |patterns: &[&str]| {
+            self.instructions
+                .values()
+                .any(|opcode| patterns.contains(&pattern(opcode)))
+        };
+
+        [
+            ("keypad", uses(&["Ex9E", "ExA1", "Fx0A"])),
+            ("sound", uses(&["Fx18", "F002", "Fx3A"])),
+            ("random", uses(&["Cxkk"])),
+        ]
+        .into_iter()
+        .filter_map(|(name, used)| used.then_some(name))
+        .collect()
+    }
+
+    /// Get memory stores overwriting reachable instructions.
+    ///
+    /// # Returns
+    /// - Suspected self-modifying stores.
+    pub fn self_modifying(&self) -> Vec<Store> {
+        self.stores
+            .iter()
+            .filter(|store| {
+                self.instructions
+                    .range(..=store.last)
+                    .any(|(addr, opcode)| addr + size(opcode) > store.first)
+            })
+            .copied()
+            .collect()
+    }
+
+    /// Get static analysis warnings.
+    ///
+    /// # Returns
+    /// - Warnings about call stack, jumps, instructions and stores.
+    pub fn warnings(&self) -> Vec<Warning> {
+        let mut warnings = Vec::new();
+
+        match self.max_depth() {
+            Ok(depth) if depth > STACK_SIZE => {
+                warnings.push(Warning::StackOverflow { depth })
+            }
+            Ok(_) => {}
+            Err(chain) => warnings.push(Warning::Recursion { chain }),
+        }
+
+        for (&addr, &target) in &self.jumps {
+            warnings.extend(self.check_target(addr, target));
+        }
+
+        for (&addr, opcode) in &self.instructions {
+            if pattern(opcode) == "unknown" {
+                warnings.push(Warning::Undefined {
+                    addr,
+                    raw: opcode.raw,
+                });
+            }
+        }
+
+        warnings.extend(self.unreachable());
+
+        let end = self.load_addr + self.data.len();
+
+        for &store in &self.stores {
+            if store.first < self.load_addr || store.last >= end {
+                warnings.push(Warning::StoreOutside(store));
+            }
+        }
+
+        warnings
+    }
+
+    /// Check jump or call target.
+    ///
+    /// # Parameters
+    /// - `addr`   - given jump instruction address.
+    /// - `target` - given jump target address.
+    ///
+    /// # Returns
+    /// - Warning - if target is suspicious.
+    /// - `None`  - otherwise.
+    fn check_target(&self, addr: usize, target: usize) -> Option<Warning> {
+        let end = self.load_addr + self.data.len();
+        let previous = self.instructions.range(..target).next_back();
+
+        if target < self.load_addr || target >= end {
+            Some(Warning::TargetOutside { addr, target })
+        } else if !target.is_multiple_of(2) {
+            Some(Warning::TargetOdd { addr, target })
+        } else if self.references.contains(&target)
+            || self
+                .fetch(target)
+                .is_none_or(|opcode| pattern(&opcode) == "unknown")
+        {
+            Some(Warning::TargetData { addr, target })
+        } else if previous
+            .is_some_and(|(start, opcode)| start + size(opcode) > target)
+        {
+            Some(Warning::TargetMisaligned { addr, target })
+        } else {
+            None
+        }
+    }
+
+    /// Get ranges of unreached program bytes which look like code.
+    ///
+    /// # Returns
+    /// - Unreachable code warnings.
+    fn unreachable(&self) -> Vec<Warning> {
+        let mut reached = vec![false; self.data.len()];
+
+        for (&addr, opcode) in &self.instructions {
+            let offset = addr - self.load_addr;
+            reached[offset..offset + size(opcode)].fill(true);
+        }
+
+        let mut warnings = Vec::new();
+        let mut offset = 0;
+
+        while offset < reached.len() {
+            if reached[offset] {
+                offset += 1;
+                continue;
+            }
+
+            let start = offset;
+
+            while offset < reached.len() && !reached[offset] {
+                offset += 1;
+            }
+
+            let first = self.load_addr + start;
+            let last = self.load_addr + offset - 1;
+
+            if self.is_code(first, last) {
+                warnings.push(Warning::Unreachable { first, last });
+            }
+        }
+
+        warnings
+    }
+
+    /// Check whether unreached program bytes look like code.
+    ///
+    /// Data is loaded through index register and rarely decodes to defined
+    /// instructions only, while zero bytes are usually padding.
+    ///
+    /// # Parameters
+    /// - `first` - given first byte address.
+    /// - `last`  - given last byte address.
+    ///
+    /// # Returns
+    /// - `true` - if bytes look like code.
+    fn is_code(&self, first: usize, last: usize) -> bool {
+        let offset = first - self.load_addr;
+        let bytes = &self.data[offset..=last - self.load_addr];
+        let words = bytes.chunks_exact(2);
+
+        self.references.range(first..=last).next().is_none()
+            && words.remainder().is_empty()
+            && bytes.iter().any(|&byte| byte != 0)
+            && words.clone().all(|word| {
+                let opcode =
+                    OpCode::new(u16::from_be_bytes([word[0], word[1]]));
+                pattern(&opcode) != "unknown"
+            })
+    }
+
+    /// Display analysis report.
+    pub fn report(&self) {
+        let (platform, extensions) = self.platform();
+        let code_size: usize = self.instructions.values().map(size).sum();
+        let features = self.features();
+
+        if extensions.is_empty() {
+            println!("Platform:  {}", platform.name());
+        } else {
+            println!(
+                "Platform:  {} (uses {})",
+                platform.name(),
+                extensions.join(", ")
+            );
+        }
+
+        println!(
+            "Code:      {} instructions ({code_size} of {} bytes)",
+            self.instructions.len(),
+            self.data.len()
+        );
+
+        let depth = match self.call_depth() {
+            Some(depth) => depth.to_string(),
+            None => "unbounded (recursion)".to_string(),
+        };
+
+        println!(
+            "Calls:     {} subroutines, depth {depth}",
+            self.subroutines().len()
+        );
+
+        if features.is_empty() {
+            println!("Uses:      none");
+        } else {
+            println!("Uses:      {}", features.join(", "));
+        }
+
+        let stores = self.self_modifying();
+
+        if !stores.is_empty() {
+            println!("\nSelf-modifying stores:");
+
+            for store in stores {
+                println!(
+                    "  <{:#05X}>  writes {:#05X}-{:#05X}",
+                    store.addr, store.first, store.last
+                );
+            }
+        }
+
+        println!("\nInstructions:");
+
+        for (pattern, count) in self.histogram() {
+            println!("  {pattern:<7}  {count:>6}");
+        }
+    }
+}
+
+/// Get instruction size.
+///
+/// # Parameters
+/// - `opcode` - given instruction opcode.
+///
+/// # Returns
+/// - Instruction size in bytes.
+fn size(opcode: &OpCode) -> usize {
+    // XO-CHIP long index load carries address in next word.
+    if opcode.raw == 0xF000 { 4 } else { 2 }
+}
+
+/// Get instruction pattern.
+///
+/// # Parameters
+/// - `opcode` - given instruction opcode.
+///
+/// # Returns
+/// - Opcode pattern with operand placeholders.
+pub fn pattern(opcode: &OpCode) -> &'static str {
+    match (opcode.class, opcode.raw) {
+        (0x0, 0x00E0) => "00E0",
+        (0x0, 0x00EE) => "00EE",
+        (0x0, 0x00FB) => "00FB",
+        (0x0, 0x00FC) => "00FC",
+        (0x0, 0x00FD) => "00FD",
+        (0x0, 0x00FE) => "00FE",
+        (0x0, 0x00FF) => "00FF",
+        (0x0, raw) if raw & 0xFFF0 == 0x00C0 => "00Cn",
+        (0x0, raw) if raw & 0xFFF0 == 0x00D0 => "00Dn",
+        (0x0, _) => "0nnn",
+        (0x1, _) => "1nnn",
+        (0x2, _) => "2nnn",
+        (0x3, _) => "3xkk",
+        (0x4, _) => "4xkk",
+        (0x5, _) => match opcode.nibble {
+            0x0 => "5xy0",
+            0x2 => "5xy2",
+            0x3 => "5xy3",
+            _ => "unknown",
+        },
+        (0x6, _) => "6xkk",
+        (0x7, _) => "7xkk",
+        (0x8, _) => match opcode.nibble {
+            0x0 => "8xy0",
+            0x1 => "8xy1",
+            0x2 => "8xy2",
+            0x3 => "8xy3",
+            0x4 => "8xy4",
+            0x5 => "8xy5",
+            0x6 => "8xy6",
+            0x7 => "8xy7",
+            0xE => "8xyE",
+            _ => "unknown",
+        },
+        (0x9, _) if opcode.nibble == 0 => "9xy0",
+        (0xA, _) => "Annn",
+        (0xB, _) => "Bnnn",
+        (0xC, _) => "Cxkk",
+        (0xD, _) if opcode.nibble == 0 => "Dxy0",
+        (0xD, _) => "Dxyn",
+        (0xE, _) if opcode.byte == 0x9E => "Ex9E",
+        (0xE, _) if opcode.byte == 0xA1 => "ExA1",
+        (0xF, 0xF000) => "F000",
+        (0xF, 0xF002) => "F002",
+        (0xF, _) => match opcode.byte {
+            0x01 => "Fn01",
+            0x07 => "Fx07",
+            0x0A => "Fx0A",
+            0x15 => "Fx15",
+            0x18 => "Fx18",
+            0x1E => "Fx1E",
+            0x29 => "Fx29",
+            0x30 => "Fx30",
+            0x33 => "Fx33",
+            0x3A => "Fx3A",
+            0x55 => "Fx55",
+            0x65 => "Fx65",
+            0x75 => "Fx75",
+            0x85 => "Fx85",
+            _ => "unknown",
+        },
+        _ => "unknown",
+    }
+}
+
+/// Get platform introducing instruction pattern.
+///
+/// # Parameters
+/// - `pattern` - given instruction pattern.
+///
+/// # Returns
+/// - Machine variant introducing instruction.
+fn platform(pattern: &str) -> Variant {
+    match pattern {
+        "00Cn" | "00FB" | "00FC" | "00FD" | "00FE" | "00FF" | "Dxy0"
+        | "Fx30" | "Fx75" | "Fx85" => Variant::Schip,
+        "00Dn" | "5xy2" | "5xy3" | "F000" | "Fn01" | "F002" | "Fx3A" => {
+            Variant::XoChip
+        }
+        _ => Variant::Chip8,
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::emulator::cpu::tests::program_bytes;
+
+    #[test]
+    fn test_analysis() {
+        // 0x200: call 0x208, skip on key, RND, loop; 0x208: call 0x20C;
+        // 0x20C: return; 0x20E: sprite data.
+        let program = [
+            0x2208, 0xE09E, 0xC10F, 0x1206, 0x220C, 0x00EE, 0x00EE, 0xF0F0,
+        ];
+        let analysis = Analysis::new(&program_bytes(&program), 0x200);
+
+        assert_eq!(7, analysis.instructions().len());
+        assert!(!analysis.instructions().contains_key(&0x20E));
+        assert_eq!(BTreeSet::from([0x208, 0x20C]), analysis.subroutines());
+        assert_eq!(Some(2), analysis.call_depth());
+        assert_eq!(vec!["keypad", "random"], analysis.features());
+        assert_eq!((Variant::Chip8, Vec::new()), analysis.platform());
+        assert!(analysis.histogram().contains(&("2nnn", 2)));
+        assert!(analysis.self_modifying().is_empty());
+
+        // Recursion, SUPER-CHIP exit and overwriting own code.
+        let program = [0xA200, 0xF055, 0x3000, 0x2200, 0x00FD];
+        let analysis = Analysis::new(&program_bytes(&program), 0x200);
+
+        assert_eq!(None, analysis.call_depth());
+        assert_eq!((Variant::Schip, vec!["00FD"]), analysis.platform());
+        assert_eq!(
+            vec![Store {
+                addr: 0x202,
+                first: 0x200,
+                last: 0x200
+            }],
+            analysis.self_modifying()
+        );
+
+        assert_eq!(Some(vec![0x200, 0x200]), analysis.recursion());
+
+        // Skip over XO-CHIP long index load.
+        let program = [0x3000, 0xF000, 0x0300, 0xF233, 0x1208];
+        let analysis = Analysis::new(&program_bytes(&program), 0x200);
+
+        assert!(analysis.instructions().contains_key(&0x206));
+        assert!(!analysis.instructions().contains_key(&0x204));
+        assert_eq!(Variant::XoChip, analysis.platform().0);
+        assert!(analysis.self_modifying().is_empty());
+    }
+
+    #[test]
+    fn test_warnings() {
+        // Jump table at 0x208 with entry into sprite at 0x210, unreachable
+        // code at 0x202 and 0x20C.
+        let program = [
+            0xB208, 0xA210, 0x6001, 0x00E0, 0x1208, 0x1210, 0x6001, 0x7001,
+            0xF0F0,
+        ];
+        let analysis = Analysis::new(&program_bytes(&program), 0x200);
+
+        assert_eq!(
+            vec![
+                Warning::TargetData {
+                    addr: 0x20A,
+                    target: 0x210
+                },
+                Warning::Undefined {
+                    addr: 0x210,
+                    raw: 0xF0F0
+                },
+                Warning::Unreachable {
+                    first: 0x202,
+                    last: 0x207
+                },
+                Warning::Unreachable {
+                    first: 0x20C,
+                    last: 0x20F
+                },
+            ],
+            analysis.warnings()
+        );
+
+        // Store past program end and loop at odd address.
+        let program = [0xA300, 0xF255, 0x1207, 0x0012, 0x0700];
+        let analysis = Analysis::new(&program_bytes(&program), 0x200);
+
+        assert_eq!(
+            vec![
+                Warning::TargetOdd {
+                    addr: 0x204,
+                    target: 0x207
+                },
+                Warning::TargetOdd {
+                    addr: 0x207,
+                    target: 0x207
+                },
+                Warning::StoreOutside(Store {
+                    addr: 0x202,
+                    first: 0x300,
+                    last: 0x302
+                }),
+            ],
+            analysis.warnings()
+        );
+
+        // Chain of 17 nested calls.
+        let mut program: Vec<u16> = (1..=17).map(|n| 0x2200 + n * 2).collect();
+        program.push(0x00EE);
+        let analysis = Analysis::new(&program_bytes(&program), 0x200);
+
+        assert_eq!(
+            vec![Warning::StackOverflow { depth: 17 }],
+            analysis.warnings()
+        );
+        assert_eq!(
+            "call depth 17 overflows 16-entry stack",
+            analysis.warnings()[0].to_string()
+        );
+    }
+}
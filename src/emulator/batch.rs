@@ -0,0 +1,293 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Batch emulation of many independent machines across worker threads.
+
+use crate::emulator::{
+    EmulatorResult,
+    cpu::{Cpu, DISPLAY_HEIGHT, DISPLAY_WIDTH, KEY_COUNT},
+    quirks::Quirks,
+    random::{Algorithm, Random},
+    tas::Keys,
+};
+use std::{num::NonZeroUsize, thread};
+
+/// Number of display pixels of single machine.
+pub const PIXELS: usize = DISPLAY_WIDTH * DISPLAY_HEIGHT;
+
+/// Batch of machines running the same program with different inputs.
+///
+/// Framebuffers of all machines are kept in one contiguous buffer with one
+/// byte (0 or 1) per pixel, machine after machine.
+/// Each machine holds 4 KiB of RAM and shares the pre-decoded instruction
+/// table with the others until it writes to memory and gets own 16 KiB copy.
+pub struct Batch {
+    /// Machine states.
+    machines: Vec<Cpu>,
+    /// Execution errors of faulted machines.
+    faults: Vec<Option<String>>,
+    /// Framebuffers of all machines.
+    framebuffers: Vec<u8>,
+    /// Number of instructions executed per frame.
+    speed: usize,
+    /// Number of worker threads.
+    threads: usize,
+}
+
+impl Batch {
+    /// Construct new `Batch` object.
+    ///
+    /// # Parameters
+    /// - `count`  - given number of machines.
+    /// - `quirks` - given interpreter behavior quirks.
+    /// - `speed`  - given number of instructions per frame.
+    ///
+    /// # Returns
+    /// - New `Batch` object.
+    pub fn new(count: usize, quirks: Quirks, speed: usize) -> Self {
+        let threads = thread::available_parallelism()
+            .map(NonZeroUsize::get)
+            .unwrap_or(1);
+
+        Self {
+            machines: vec![Cpu::with_quirks(quirks); count],
+            faults: vec![None; count],
+            framebuffers: vec![0; count * PIXELS],
+            speed,
+            threads,
+        }
+    }
+
+    /// Set number of worker threads.
+    ///
+    /// # Parameters
+    /// - `threads` - given number of worker threads (at least one).
+    pub fn set_threads(&mut self, threads: usize) {
+        self.threads = threads.max(1);
+    }
+
+    /// Get number of machines.
+    ///
+    /// # Returns
+    /// - Batch size.
+    pub fn len(&self) -> usize {
+        self.machines.len()
+    }
+
+    /// Check whether batch has no machines.
+    ///
+    /// # Returns
+    /// - `true` - if batch is empty.
+    pub fn is_empty(&self) -> bool {
+        self.machines.is_empty()
+    }
+
+    /// Load program to every machine, resetting its state.
+    ///
+    /// Machine `n` uses random number generator seed `seed + n`.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    /// - `algorithm`    - given random number generator algorithm.
+    /// - `seed`         - given random number generator seed of first machine.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - if program does not fit in memory.
+    pub fn reset(
+        &mut self,
+        program_data: &[u8],
+        algorithm: Algorithm,
+        seed: u64,
+    ) -> EmulatorResult<()> {
+        let mut machine = Cpu::with_quirks(*self.machines[0].quirks());
+        machine.load_program(program_data)?;
+
+        for (index, cpu) in self.machines.iter_mut().enumerate() {
+            *cpu = machine.clone();
+            cpu.set_random(Random::new(
+                algorithm,
+                seed.wrapping_add(index as u64),
+            ));
+        }
+
+        self.faults.fill(None);
+        self.framebuffers.fill(0);
+        Ok(())
+    }
+
+    /// Execute frames on every machine holding specified keys.
+    ///
+    /// Faulted machines are skipped, their errors are available via
+    /// `faults`.
+    ///
+    /// # Parameters
+    /// - `actions` - given keys held by each machine.
+    /// - `frames`  - given number of frames to execute.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - if number of actions does not match batch size.
+    pub fn step(
+        &mut self,
+        actions: &[Keys],
+        frames: u64,
+    ) -> EmulatorResult<()> {
+        if actions.len() != self.machines.len() {
+            return Err(format!(
+                "Expected {} actions, got {}",
+                self.machines.len(),
+                actions.len()
+            ));
+        }
+
+        if self.machines.is_empty() {
+            return Ok(());
+        }
+
+        let chunk = self.machines.len().div_ceil(self.threads);
+        let speed = self.speed;
+
+        thread::scope(|scope| {
+            let work = self
+                .machines
+                .chunks_mut(chunk)
+                .zip(self.faults.chunks_mut(chunk))
+                .zip(self.framebuffers.chunks_mut(chunk * PIXELS))
+                .zip(actions.chunks(chunk));
+
+            for (((machines, faults), framebuffers), actions) in work {
+                scope.spawn(move || {
+                    let machines = machines.iter_mut().zip(faults.iter_mut());
+                    let outputs = framebuffers.chunks_mut(PIXELS).zip(actions);
+
+                    for ((cpu, fault), (framebuffer, &keys)) in
+                        machines.zip(outputs)
+                    {
+                        if fault.is_none() {
+                            *fault = run(cpu, keys, frames, speed).err();
+                        }
+
+                        for (pixel, &on) in
+                            framebuffer.iter_mut().zip(cpu.display())
+                        {
+                            *pixel = on as u8;
+                        }
+                    }
+                });
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Get framebuffers of all machines.
+    ///
+    /// # Returns
+    /// - Pixels of machine `n` at `n * PIXELS..(n + 1) * PIXELS`.
+    pub fn framebuffers(&self) -> &[u8] {
+        &self.framebuffers
+    }
+
+    /// Get framebuffer of single machine.
+    ///
+    /// # Parameters
+    /// - `index` - given machine index.
+    ///
+    /// # Returns
+    /// - Pixels in row-major order.
+    pub fn framebuffer(&self, index: usize) -> &[u8] {
+        &self.framebuffers[index * PIXELS..(index + 1) * PIXELS]
+    }
+
+    /// Get machine states.
+    ///
+    /// # Returns
+    /// - Machines in batch order.
+    pub fn machines(&self) -> &[Cpu] {
+        &self.machines
+    }
+
+    /// Get execution errors.
+    ///
+    /// # Returns
+    /// - Error of each faulted machine, `None` for running ones.
+    pub fn faults(&self) -> &[Option<String>] {
+        &self.faults
+    }
+}
+
+/// Execute frames on single machine.
+///
+/// # Parameters
+/// - `cpu`    - given machine.
+/// - `keys`   - given held keys.
+/// - `frames` - given number of frames to execute.
+/// - `speed`  - given number of instructions per frame.
+///
+/// # Returns
+/// - `Ok`  - in case of success.
+/// - `Err` - in case of execution error.
+fn run(
+    cpu: &mut Cpu,
+    keys: Keys,
+    frames: u64,
+    speed: usize,
+) -> EmulatorResult<()> {
+    for key in 0..KEY_COUNT {
+        cpu.set_key(key as u8, keys & (1 << key) != 0);
+    }
+
+    for _ in 0..frames {
+        cpu.frame(speed)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::emulator::env::Environment;
+
+    /// Draw random sprite at random position every frame.
+    const PROGRAM: [u8; 12] = [
+        0x00, 0xE0, 0xC0, 0x3F, 0xC1, 0x1F, 0xA0, 0x50, 0xD0, 0x15, 0x12, 0x00,
+    ];
+
+    #[test]
+    fn test_batch_matches_environment() {
+        let mut batch = Batch::new(5, Quirks::default(), 20);
+        batch.set_threads(2);
+        batch.reset(&PROGRAM, Algorithm::SplitMix, 10).unwrap();
+
+        let actions = [0, 1, 2, 3, 4];
+        batch.step(&actions, 3).unwrap();
+
+        assert_eq!(5 * PIXELS, batch.framebuffers().len());
+
+        for (index, &keys) in actions.iter().enumerate() {
+            let mut env =
+                Environment::new(Quirks::default(), Algorithm::SplitMix, 20);
+            env.reset(&PROGRAM, 10 + index as u64).unwrap();
+
+            let step = env.step(keys, 3).unwrap();
+            let expected: Vec<u8> =
+                step.observation.iter().map(|&on| on as u8).collect();
+
+            assert_eq!(expected, batch.framebuffer(index));
+        }
+
+        assert!(batch.faults().iter().all(Option::is_none));
+        assert!(batch.step(&[0], 1).is_err());
+    }
+
+    #[test]
+    fn test_batch_faults() {
+        let mut batch = Batch::new(3, Quirks::default(), 10);
+        batch.reset(&[0x00, 0xEE], Algorithm::SplitMix, 0).unwrap();
+        batch.step(&[0; 3], 1).unwrap();
+
+        assert!(batch.faults().iter().all(Option::is_some));
+    }
+}
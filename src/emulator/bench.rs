@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Execution engines throughput benchmark.
+
+use crate::emulator::{
+    EmulatorResult,
+    cpu::{Cpu, Engine},
+};
+use std::{
+    fmt::{self, Display, Formatter},
+    time::{Duration, Instant},
+};
+
+/// Default number of frames executed by each engine.
+pub const DEFAULT_FRAMES: u64 = 1_000_000;
+
+/// Benchmark results of execution engines.
+#[derive(Debug, Clone)]
+pub struct Report {
+    /// Number of instructions executed by each engine.
+    pub cycles: u64,
+    /// Run time of each engine.
+    pub times: Vec<(Engine, Duration)>,
+}
+
+impl Report {
+    /// Get instructions per second.
+    ///
+    /// # Parameters
+    /// - `elapsed` - given run time.
+    ///
+    /// # Returns
+    /// - Executed instructions per second.
+    fn ips(&self, elapsed: Duration) -> u64 {
+        (self.cycles as f64 / elapsed.as_secs_f64().max(f64::EPSILON)) as u64
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "instructions  {}", self.cycles)?;
+
+        let Some(&(_, reference)) = self.times.first() else {
+            return Ok(());
+        };
+
+        for &(engine, elapsed) in &self.times {
+            let speedup = reference.as_secs_f64()
+                / elapsed.as_secs_f64().max(f64::EPSILON);
+
+            write!(
+                f,
+                "\n{:<13} {} IPS ({speedup:.2}x)",
+                engine.name(),
+                self.ips(elapsed)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Run program under every execution engine.
+///
+/// All runs start from the same machine state and must finish in the same
+/// state as the reference engine.
+///
+/// # Parameters
+/// - `cpu`    - given machine with loaded program.
+/// - `frames` - given number of frames to execute.
+/// - `speed`  - given number of instructions per frame.
+///
+/// # Returns
+/// - Benchmark report - in case of success.
+/// - `Err`            - in case of execution error or state divergence.
+pub fn measure(cpu: &Cpu, frames: u64, speed: usize) -> EmulatorResult<Report> {
+    let mut reference = None;
+    let mut times = Vec::new();
+    let mut cycles = 0;
+
+    for engine in Engine::ALL {
+        let mut machine = cpu.clone();
+        machine.set_engine(engine);
+
+        let start = Instant::now();
+
+        for _ in 0..frames {
+            machine.frame(speed)?;
+        }
+
+        times.push((engine, start.elapsed()));
+
+        // Frames end early on display wait and VIP timing ignores speed.
+        cycles = machine.cycles() - cpu.cycles();
+
+        let state = machine.save_state();
+
+        match &reference {
+            None => reference = Some(state),
+            Some(expected) if *expected != state => {
+                return Err(format!(
+                    "{} engine diverged from reference",
+                    engine.name()
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+
+    Ok(Report { cycles, times })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::emulator::{cpu::tests::cpu_with_program, quirks::Quirks};
+
+    #[test]
+    fn test_measure() {
+        // Self-modifying loop: store BCD of V0 over the ADD operand.
+        let program = [0x7001, 0xA203, 0xF033, 0x1200];
+        let cpu = cpu_with_program(&program, Quirks::default());
+
+        let report = measure(&cpu, 100, 10).unwrap();
+        assert_eq!(1000, report.cycles);
+        assert_eq!(Engine::ALL.len(), report.times.len());
+        assert!(report.to_string().contains("recompiler"));
+
+        // Drawing ends first frame after two instructions and later ones
+        // after three.
+        let program = [0x6000, 0xD001, 0x1200];
+        let cpu = cpu_with_program(&program, Quirks::chip8());
+
+        assert_eq!(299, measure(&cpu, 100, 10).unwrap().cycles);
+
+        let failing = cpu_with_program(&[0x00EE], Quirks::default());
+        assert!(measure(&failing, 10, 10).is_err());
+    }
+}
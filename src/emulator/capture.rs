@@ -0,0 +1,542 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Display screenshots (PPM/PNG) and session recording (GIF).
+
+use crate::emulator::{
+    EmulatorResult,
+    cpu::{DISPLAY_HEIGHT, DISPLAY_WIDTH, KEY_COUNT},
+    frontend::{Frame, Frontend},
+    sound,
+    terminal::{Color, DEFAULT_PALETTE},
+};
+use std::{borrow::Cow, fs::File, io::BufWriter, path::Path};
+
+/// Default pixel scale of captured images.
+pub const DEFAULT_SCALE: u32 = 8;
+
+/// Maximum pixel scale of captured images.
+pub const MAX_SCALE: u32 = 64;
+
+/// Screenshot file name used when none is set.
+const DEFAULT_SCREENSHOT: &str = "chip8.png";
+
+/// Display capture options.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// Number of image pixels per display pixel.
+    pub scale: u32,
+    /// Lit and unlit pixel colors (looked up in ROM database if not set).
+    pub palette: Option<(Color, Color)>,
+    /// Screenshot file name (`.ppm` or `.png`).
+    pub screenshot: Option<String>,
+    /// Frames after which screenshots are taken.
+    pub frames: Vec<u64>,
+    /// Output GIF file name for session recording.
+    pub gif: Option<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            scale: DEFAULT_SCALE,
+            palette: None,
+            screenshot: None,
+            frames: Vec::new(),
+            gif: None,
+        }
+    }
+}
+
+/// Parse palette of two `RRGGBB` colors separated by comma.
+///
+/// # Parameters
+/// - `text` - given palette string, e.g. `ffffff,000000`.
+///
+/// # Returns
+/// - Lit and unlit pixel colors - in case of success.
+/// - `Err`                      - otherwise.
+pub fn parse_palette(text: &str) -> EmulatorResult<(Color, Color)> {
+    let error =
+        || format!("malformed palette '{text}' (expected RRGGBB,RRGGBB)");
+    let parse = |color: &str| {
+        let color = color.trim_start_matches('#');
+        let value = u32::from_str_radix(color, 16).map_err(|_| error())?;
+
+        if color.len() != 6 {
+            return Err(error());
+        }
+
+        let [_, r, g, b] = value.to_be_bytes();
+        Ok((r, g, b))
+    };
+
+    let (foreground, background) = text.split_once(',').ok_or_else(error)?;
+
+    Ok((parse(foreground)?, parse(background)?))
+}
+
+/// Convert display to scaled RGB pixels.
+///
+/// # Parameters
+/// - `display` - given display pixels.
+/// - `scale`   - given number of image pixels per display pixel.
+/// - `palette` - given lit and unlit pixel colors.
+///
+/// # Returns
+/// - RGB bytes in row-major order.
+pub fn rgb(display: &[bool], scale: u32, palette: (Color, Color)) -> Vec<u8> {
+    indexed(display, scale)
+        .iter()
+        .flat_map(|&index| {
+            let (r, g, b) = if index == 1 { palette.0 } else { palette.1 };
+            [r, g, b]
+        })
+        .collect()
+}
+
+/// Convert display to scaled palette indices (0 - unlit, 1 - lit).
+///
+/// # Parameters
+/// - `display` - given display pixels.
+/// - `scale`   - given number of image pixels per display pixel.
+///
+/// # Returns
+/// - Palette indices in row-major order.
+fn indexed(display: &[bool], scale: u32) -> Vec<u8> {
+    let scale = scale as usize;
+    let mut result = Vec::with_capacity(display.len() * scale * scale);
+
+    for row in display.chunks(DISPLAY_WIDTH) {
+        for _ in 0..scale {
+            for &pixel in row {
+                result.extend(std::iter::repeat_n(pixel as u8, scale));
+            }
+        }
+    }
+
+    result
+}
+
+/// Encode display as binary PPM image.
+///
+/// # Parameters
+/// - `display` - given display pixels.
+/// - `scale`   - given number of image pixels per display pixel.
+/// - `palette` - given lit and unlit pixel colors.
+///
+/// # Returns
+/// - PPM file bytes.
+pub fn ppm(display: &[bool], scale: u32, palette: (Color, Color)) -> Vec<u8> {
+    let (width, height) = image_size(scale);
+    let mut bytes = format!("P6\n{width} {height}\n255\n").into_bytes();
+
+    bytes.extend(rgb(display, scale, palette));
+    bytes
+}
+
+/// Encode display as PNG image.
+///
+/// # Parameters
+/// - `display` - given display pixels.
+/// - `scale`   - given number of image pixels per display pixel.
+/// - `palette` - given lit and unlit pixel colors.
+///
+/// # Returns
+/// - PNG file bytes - in case of success.
+/// - `Err`          - otherwise.
+pub fn png(
+    display: &[bool],
+    scale: u32,
+    palette: (Color, Color),
+) -> EmulatorResult<Vec<u8>> {
+    let (width, height) = image_size(scale);
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, width, height);
+
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let error =
+        |error: png::EncodingError| format!("Error encode PNG: {error}");
+    let mut writer = encoder.write_header().map_err(error)?;
+
+    writer
+        .write_image_data(&rgb(display, scale, palette))
+        .map_err(error)?;
+    writer.finish().map_err(error)?;
+
+    Ok(bytes)
+}
+
+/// Save display screenshot, format is chosen by file extension.
+///
+/// # Parameters
+/// - `filename` - given output file name (`.ppm` or `.png`).
+/// - `display`  - given display pixels.
+/// - `options`  - given capture options.
+///
+/// # Returns
+/// - `Ok`  - in case of success.
+/// - `Err` - otherwise.
+pub fn save_screenshot(
+    filename: &str,
+    display: &[bool],
+    options: &Options,
+) -> EmulatorResult<()> {
+    let extension = Path::new(filename)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_ascii_lowercase());
+
+    let palette = options.palette.unwrap_or(DEFAULT_PALETTE);
+    let bytes = match extension.as_deref() {
+        Some("ppm") => ppm(display, options.scale, palette),
+        Some("png") => png(display, options.scale, palette)?,
+        _ => {
+            return Err(format!(
+                "unsupported screenshot format '{filename}' (expected .ppm \
+                 or .png)"
+            ));
+        }
+    };
+
+    std::fs::write(filename, bytes)
+        .map_err(|error| format!("Error write '{filename}': {error}"))
+}
+
+/// Get screenshot file name for specific frame.
+///
+/// # Parameters
+/// - `filename` - given screenshot file name.
+/// - `frame`    - given frame number.
+///
+/// # Returns
+/// - File name with frame number inserted before extension.
+pub fn frame_filename(filename: &str, frame: u64) -> String {
+    match filename.rsplit_once('.') {
+        Some((stem, extension)) => format!("{stem}-{frame}.{extension}"),
+        None => format!("{filename}-{frame}"),
+    }
+}
+
+/// Get image size for specified scale.
+///
+/// # Parameters
+/// - `scale` - given number of image pixels per display pixel.
+///
+/// # Returns
+/// - Image width and height.
+fn image_size(scale: u32) -> (u32, u32) {
+    (DISPLAY_WIDTH as u32 * scale, DISPLAY_HEIGHT as u32 * scale)
+}
+
+/// Animated GIF session recorder.
+///
+/// Identical consecutive frames are merged into single GIF frame with
+/// longer delay.
+pub struct GifRecorder<W: std::io::Write> {
+    /// GIF encoder.
+    encoder: gif::Encoder<W>,
+    /// Number of image pixels per display pixel.
+    scale: u32,
+    /// Display waiting to be written and number of frames it was shown.
+    pending: Option<(Vec<u8>, u64)>,
+    /// Number of recorded 60 Hz frames.
+    frames: u64,
+    /// Elapsed time of written GIF frames in centiseconds.
+    written: u64,
+}
+
+impl<W: std::io::Write> GifRecorder<W> {
+    /// Construct new `GifRecorder` object.
+    ///
+    /// # Parameters
+    /// - `output`  - given GIF output.
+    /// - `scale`   - given number of image pixels per display pixel.
+    /// - `palette` - given lit and unlit pixel colors.
+    ///
+    /// # Returns
+    /// - New `GifRecorder` object - in case of success.
+    /// - `Err`                    - otherwise.
+    pub fn new(
+        output: W,
+        scale: u32,
+        palette: (Color, Color),
+    ) -> EmulatorResult<Self> {
+        let (width, height) = image_size(scale);
+        let ((fr, fg, fb), (br, bg, bb)) = palette;
+        let colors = [br, bg, bb, fr, fg, fb];
+        let mut encoder =
+            gif::Encoder::new(output, width as u16, height as u16, &colors)
+                .map_err(gif_error)?;
+
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(gif_error)?;
+
+        Ok(Self {
+            encoder,
+            scale,
+            pending: None,
+            frames: 0,
+            written: 0,
+        })
+    }
+
+    /// Record single 60 Hz frame.
+    ///
+    /// # Parameters
+    /// - `display` - given display pixels.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    pub fn record(&mut self, display: &[bool]) -> EmulatorResult<()> {
+        let image = indexed(display, self.scale);
+
+        match &mut self.pending {
+            Some((pending, count)) if *pending == image => *count += 1,
+            _ => {
+                self.flush()?;
+                self.pending = Some((image, 1));
+            }
+        }
+
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Write pending frame and finish GIF stream.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    pub fn finish(mut self) -> EmulatorResult<()> {
+        self.flush()?;
+        self.encoder
+            .into_inner()
+            .map_err(gif_error)?
+            .flush()
+            .map_err(|error| format!("Error write GIF: {error}"))
+    }
+
+    /// Write pending frame with delay rounded to GIF centiseconds.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn flush(&mut self) -> EmulatorResult<()> {
+        let Some((image, _)) = self.pending.take() else {
+            return Ok(());
+        };
+
+        let (width, height) = image_size(self.scale);
+        let elapsed = self.frames * 100 / 60;
+        let delay = (elapsed - self.written).min(u16::MAX as u64) as u16;
+        let frame = gif::Frame {
+            width: width as u16,
+            height: height as u16,
+            delay,
+            buffer: Cow::Owned(image),
+            ..gif::Frame::default()
+        };
+
+        self.written = elapsed;
+        self.encoder.write_frame(&frame).map_err(gif_error)
+    }
+}
+
+/// Convert GIF encoding error to emulator error.
+///
+/// # Parameters
+/// - `error` - given GIF encoding error.
+///
+/// # Returns
+/// - Error message.
+fn gif_error(error: impl std::fmt::Display) -> String {
+    format!("Error encode GIF: {error}")
+}
+
+/// Frontend wrapper capturing screenshots and GIF recording.
+///
+/// Screenshots are taken after configured frames and whenever wrapped
+/// frontend requests one.
+pub struct Capture<'a> {
+    /// Wrapped frontend.
+    inner: &'a mut dyn Frontend,
+    /// Capture options.
+    options: Options,
+    /// Session GIF recorder.
+    gif: Option<GifRecorder<BufWriter<File>>>,
+}
+
+impl<'a> Capture<'a> {
+    /// Construct new `Capture` object.
+    ///
+    /// # Parameters
+    /// - `inner`   - given frontend to wrap.
+    /// - `options` - given capture options.
+    ///
+    /// # Returns
+    /// - New `Capture` object - in case of success.
+    /// - `Err`                - otherwise.
+    pub fn new(
+        inner: &'a mut dyn Frontend,
+        options: &Options,
+    ) -> EmulatorResult<Self> {
+        let gif = match &options.gif {
+            Some(filename) => {
+                let file = File::create(filename).map_err(|error| {
+                    format!("Error create '{filename}': {error}")
+                })?;
+
+                Some(GifRecorder::new(
+                    BufWriter::new(file),
+                    options.scale,
+                    options.palette.unwrap_or(DEFAULT_PALETTE),
+                )?)
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            inner,
+            options: options.clone(),
+            gif,
+        })
+    }
+
+    /// Finish GIF recording.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    pub fn finish(self) -> EmulatorResult<()> {
+        match self.gif {
+            Some(gif) => gif.finish(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Frontend for Capture<'_> {
+    fn poll_keys(
+        &mut self,
+        keypad: &mut [bool; KEY_COUNT],
+    ) -> EmulatorResult<()> {
+        self.inner.poll_keys(keypad)
+    }
+
+    fn quit_requested(&self) -> bool {
+        self.inner.quit_requested()
+    }
+
+    fn present(&mut self, frame: &Frame) -> EmulatorResult<()> {
+        // Frame numbers given by user count finished frames.
+        let finished = frame.number + 1;
+        let filename = self
+            .options
+            .screenshot
+            .as_deref()
+            .unwrap_or(DEFAULT_SCREENSHOT);
+
+        if self.options.frames.contains(&finished) {
+            let filename = frame_filename(filename, finished);
+            save_screenshot(&filename, frame.display, &self.options)?;
+        }
+
+        if self.inner.screenshot_requested() {
+            let filename = frame_filename(filename, finished);
+            save_screenshot(&filename, frame.display, &self.options)?;
+        }
+
+        if let Some(gif) = &mut self.gif {
+            gif.record(frame.display)?;
+        }
+
+        self.inner.present(frame)
+    }
+
+    fn screenshot_requested(&mut self) -> bool {
+        self.inner.screenshot_requested()
+    }
+
+    fn set_tone(&mut self, playing: bool) -> EmulatorResult<()> {
+        self.inner.set_tone(playing)
+    }
+
+    fn push_samples(&mut self, samples: &[i16]) -> EmulatorResult<()> {
+        self.inner.push_samples(samples)
+    }
+
+    fn audio(&self) -> Option<&sound::Options> {
+        self.inner.audio()
+    }
+
+    fn realtime(&self) -> bool {
+        self.inner.realtime()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// Test palette of white lit and black unlit pixels.
+    const PALETTE: (Color, Color) = ((0xFF, 0xFF, 0xFF), (0x00, 0x00, 0x00));
+
+    #[test]
+    fn test_parse_palette() {
+        assert_eq!(PALETTE, parse_palette("ffffff,#000000").unwrap());
+        assert!(parse_palette("ffffff").is_err());
+        assert!(parse_palette("fff,000").is_err());
+        assert!(parse_palette("gggggg,000000").is_err());
+    }
+
+    #[test]
+    fn test_images() {
+        let mut display = [false; DISPLAY_WIDTH * DISPLAY_HEIGHT];
+        display[1] = true;
+
+        let image = ppm(&display, 2, PALETTE);
+        let header = b"P6\n128 64\n255\n";
+
+        assert_eq!(header.len() + 128 * 64 * 3, image.len());
+        assert_eq!([0, 0, 0, 0, 0, 0, 255, 255, 255], image[14..23]);
+        assert_eq!([255, 255, 255], image[14 + 128 * 3 + 6..][..3]);
+
+        let image = png(&display, 1, PALETTE).unwrap();
+        assert_eq!(b"\x89PNG", &image[..4]);
+    }
+
+    #[test]
+    fn test_gif() {
+        let mut display = [false; DISPLAY_WIDTH * DISPLAY_HEIGHT];
+        let mut bytes = Vec::new();
+        let mut recorder = GifRecorder::new(&mut bytes, 1, PALETTE).unwrap();
+
+        for _ in 0..30 {
+            recorder.record(&display).unwrap();
+        }
+
+        display[0] = true;
+        recorder.record(&display).unwrap();
+        recorder.finish().unwrap();
+
+        let mut decoder = gif::DecodeOptions::new()
+            .read_info(bytes.as_slice())
+            .unwrap();
+        let first = decoder.read_next_frame().unwrap().unwrap().delay;
+        let second = decoder.read_next_frame().unwrap().unwrap().delay;
+
+        assert_eq!((50, 1), (first, second));
+        assert!(decoder.read_next_frame().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_frame_filename() {
+        assert_eq!("shot-60.png", frame_filename("shot.png", 60));
+        assert_eq!("shot-1", frame_filename("shot", 1));
+    }
+}
@@ -0,0 +1,602 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! User configuration file with default runtime settings.
+//!
+//! Configuration is TOML file with emulation settings at the top level and
+//! `[sound]` and `[paths]` tables, e.g.:
+//!
+//! ```toml
+//! speed = 15
+//! quirks = "schip"
+//! keymap = "x123qweasdzc4rfv"
+//! palette = "FFFFFF,000000"
+//! scale = 4
+//!
+//! [sound]
+//! frequency = 330
+//! volume = 50
+//!
+//! [paths]
+//! roms = "/home/user/roms"
+//! ```
+//!
+//! Command line options take precedence over file values. Settings also
+//! found in ROM database or cartridge (speed, quirks, keymap and palette)
+//! are kept as [`Defaults`] and only fill values left unset by them.
+
+use crate::emulator::{
+    EmulatorResult, Settings, capture,
+    quirks::{self, Quirks},
+    sound, terminal,
+    terminal::{Color, Glyphs},
+};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+use toml::{Table, Value};
+
+/// Configuration file location inside user configuration directory.
+const FILE_NAME: &str = "chip8/config.toml";
+
+/// Default file paths.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Paths {
+    /// Directory searched for programs not found by given file name.
+    pub roms: Option<String>,
+    /// COSMAC VIP monitor ROM image.
+    pub monitor: Option<String>,
+}
+
+/// Configuration file values applied after per-ROM settings.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Defaults {
+    /// Interpreter behavior quirks.
+    pub quirks: Option<Quirks>,
+    /// Instructions per frame.
+    pub speed: Option<usize>,
+    /// Keyboard characters in keypad keys order (0-F).
+    pub keymap: Option<String>,
+    /// Lit and unlit pixel colors.
+    pub palette: Option<(Color, Color)>,
+}
+
+impl Defaults {
+    /// Apply defaults to runtime settings not set on the command line, in
+    /// ROM database or cartridge.
+    ///
+    /// # Parameters
+    /// - `settings` - given runtime settings.
+    pub fn apply(&self, settings: &mut Settings) {
+        settings.quirks = settings.quirks.or(self.quirks);
+        settings.speed = settings.speed.or(self.speed);
+
+        let keymap = settings.terminal.keymap.take();
+        settings.terminal.keymap = keymap.or_else(|| self.keymap.clone());
+
+        if let Some(palette) = settings.capture.palette.or(self.palette) {
+            set_palette(settings, palette);
+        }
+    }
+}
+
+/// Get default configuration file location.
+///
+/// # Returns
+/// - `$XDG_CONFIG_HOME/chip8/config.toml` or
+///   `$HOME/.config/chip8/config.toml` - if either variable is set.
+/// - `None`                             - otherwise.
+pub fn default_path() -> Option<PathBuf> {
+    let directory = env::var_os("XDG_CONFIG_HOME")
+        .filter(|directory| !directory.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            env::var_os("HOME").map(|home| PathBuf::from(home).join(".config"))
+        })?;
+
+    Some(directory.join(FILE_NAME))
+}
+
+/// Load configuration file.
+///
+/// # Parameters
+/// - `path`     - given configuration file path.
+/// - `settings` - given runtime settings to update.
+/// - `paths`    - given default file paths to update.
+///
+/// # Returns
+/// - `Ok`  - in case of success.
+/// - `Err` - otherwise.
+pub fn load(
+    path: &Path,
+    settings: &mut Settings,
+    paths: &mut Paths,
+) -> EmulatorResult<()> {
+    let filename = path.display();
+    let text = fs::read_to_string(path)
+        .map_err(|error| format!("Error read '{filename}': {error}"))?;
+
+    parse(&text, settings, paths)
+        .map_err(|error| format!("{filename}: {error}"))
+}
+
+/// Parse configuration text.
+///
+/// # Parameters
+/// - `text`     - given configuration TOML text.
+/// - `settings` - given runtime settings to update.
+/// - `paths`    - given default file paths to update.
+///
+/// # Returns
+/// - `Ok`  - in case of success.
+/// - `Err` - otherwise.
+pub fn parse(
+    text: &str,
+    settings: &mut Settings,
+    paths: &mut Paths,
+) -> EmulatorResult<()> {
+    let table: Table = text
+        .parse()
+        .map_err(|error: toml::de::Error| error.message().to_string())?;
+
+    for (key, value) in &table {
+        match key.as_str() {
+            "speed" => {
+                let speed = integer(key, value)?;
+
+                if speed <= 0 {
+                    return Err("speed must be positive".to_string());
+                }
+
+                settings.defaults.speed = Some(speed as usize);
+            }
+            "quirks" => settings.defaults.quirks = Some(parse_quirks(value)?),
+            "keymap" => {
+                let keymap = string(key, value)?;
+
+                terminal::parse_keymap(keymap)?;
+                settings.defaults.keymap = Some(keymap.to_string());
+            }
+            "braille" => {
+                settings.terminal.glyphs = match boolean(key, value)? {
+                    true => Glyphs::Braille,
+                    false => Glyphs::HalfBlock,
+                };
+            }
+            "scale" => {
+                let scale = integer(key, value)?;
+
+                if !(1..=capture::MAX_SCALE as i64).contains(&scale) {
+                    return Err(format!(
+                        "scale must be 1-{}",
+                        capture::MAX_SCALE
+                    ));
+                }
+
+                settings.capture.scale = scale as u32;
+            }
+            "palette" => {
+                let palette = capture::parse_palette(string(key, value)?)?;
+                settings.defaults.palette = Some(palette);
+            }
+            "sound" => parse_sound(table_of(key, value)?, &mut settings.sound)?,
+            "paths" => {
+                for (key, value) in table_of(key, value)? {
+                    let path = Some(string(key, value)?.to_string());
+
+                    match key.as_str() {
+                        "roms" => paths.roms = path,
+                        "romdb" => settings.database = path,
+                        "monitor" => paths.monitor = path,
+                        _ => return Err(format!("unknown key 'paths.{key}'")),
+                    }
+                }
+            }
+            _ => return Err(format!("unknown key '{key}'")),
+        }
+    }
+
+    Ok(())
+}
+
+/// Render effective configuration as TOML text.
+///
+/// Settings looked up in ROM database when not set are omitted.
+///
+/// # Parameters
+/// - `settings` - given runtime settings.
+/// - `paths`    - given default file paths.
+///
+/// # Returns
+/// - Configuration TOML text.
+pub fn render(settings: &Settings, paths: &Paths) -> String {
+    let mut table = Table::new();
+    let mut settings = settings.clone();
+
+    settings.defaults.clone().apply(&mut settings);
+
+    if let Some(speed) = settings.speed {
+        table.insert("speed".into(), Value::Integer(speed as i64));
+    }
+
+    if let Some(quirks) = settings.quirks {
+        table.insert("quirks".into(), quirks_value(&quirks));
+    }
+
+    if let Some(keymap) = &settings.terminal.keymap {
+        table.insert("keymap".into(), keymap.clone().into());
+    }
+
+    let braille = settings.terminal.glyphs == Glyphs::Braille;
+
+    table.insert("braille".into(), braille.into());
+    table.insert(
+        "scale".into(),
+        Value::Integer(settings.capture.scale.into()),
+    );
+
+    if let Some((lit, unlit)) = settings.capture.palette {
+        table.insert(
+            "palette".into(),
+            format!("{},{}", hex(lit), hex(unlit)).into(),
+        );
+    }
+
+    let mut sound = Table::new();
+    let options = &settings.sound;
+
+    sound.insert(
+        "sample_rate".into(),
+        Value::Integer(options.sample_rate.into()),
+    );
+    sound.insert("frequency".into(), options.frequency.into());
+    sound.insert("volume".into(), Value::Integer(options.volume.into()));
+    table.insert("sound".into(), sound.into());
+
+    let mut files = Table::new();
+    let entries = [
+        ("roms", &paths.roms),
+        ("romdb", &settings.database),
+        ("monitor", &paths.monitor),
+    ];
+
+    for (key, path) in entries {
+        if let Some(path) = path {
+            files.insert(key.into(), path.clone().into());
+        }
+    }
+
+    if !files.is_empty() {
+        table.insert("paths".into(), files.into());
+    }
+
+    table.to_string()
+}
+
+/// Set image and terminal pixel colors.
+///
+/// # Parameters
+/// - `settings` - given runtime settings to update.
+/// - `palette`  - given lit and unlit pixel colors.
+pub fn set_palette(settings: &mut Settings, palette: (Color, Color)) {
+    settings.capture.palette = Some(palette);
+    settings.terminal.palette = Some(palette);
+}
+
+/// Parse `[sound]` table.
+///
+/// # Parameters
+/// - `table`   - given sound table.
+/// - `options` - given sound output options to update.
+///
+/// # Returns
+/// - `Ok`  - in case of success.
+/// - `Err` - otherwise.
+fn parse_sound(
+    table: &Table,
+    options: &mut sound::Options,
+) -> EmulatorResult<()> {
+    for (key, value) in table {
+        match key.as_str() {
+            "sample_rate" => {
+                let rate = integer(key, value)?;
+
+                if !(1_000..=192_000).contains(&rate) {
+                    return Err(
+                        "sample rate must be 1000-192000 Hz".to_string()
+                    );
+                }
+
+                options.sample_rate = rate as u32;
+            }
+            "frequency" => {
+                let frequency = match value {
+                    Value::Float(frequency) => *frequency,
+                    _ => integer(key, value)? as f64,
+                };
+
+                if !(20.0..=20_000.0).contains(&frequency) {
+                    return Err("frequency must be 20-20000 Hz".to_string());
+                }
+
+                options.frequency = frequency;
+            }
+            "volume" => {
+                let volume = integer(key, value)?;
+
+                if !(0..=100).contains(&volume) {
+                    return Err("volume must be 0-100".to_string());
+                }
+
+                options.volume = volume as u8;
+            }
+            _ => return Err(format!("unknown key 'sound.{key}'")),
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse quirks preset name or table of individual quirks.
+///
+/// Table may set base `preset` (chip8 if not set) and override quirks by
+/// their names.
+///
+/// # Parameters
+/// - `value` - given quirks value.
+///
+/// # Returns
+/// - Parsed quirks - in case of success.
+/// - `Err`         - otherwise.
+fn parse_quirks(value: &Value) -> EmulatorResult<Quirks> {
+    let Value::Table(table) = value else {
+        return Quirks::from_preset(string("quirks", value)?);
+    };
+
+    let mut flags = match table.get("preset") {
+        Some(preset) => Quirks::from_preset(string("quirks.preset", preset)?)?,
+        None => Quirks::default(),
+    }
+    .flags();
+
+    for (key, value) in table {
+        if key == "preset" {
+            continue;
+        }
+
+        let Some(index) = quirks::NAMES.iter().position(|name| name == key)
+        else {
+            return Err(format!("unknown quirk '{key}'"));
+        };
+
+        flags[index] = boolean(key, value)?;
+    }
+
+    Ok(Quirks::from_flags(flags))
+}
+
+/// Convert quirks to preset name or table of individual quirks.
+///
+/// # Parameters
+/// - `quirks` - given quirks.
+///
+/// # Returns
+/// - Quirks configuration value.
+fn quirks_value(quirks: &Quirks) -> Value {
+    let preset = quirks::PRESETS
+        .iter()
+        .find(|&&name| Quirks::from_preset(name).as_ref() == Ok(quirks));
+
+    if let Some(preset) = preset {
+        return Value::from(*preset);
+    }
+
+    let table: Table = quirks::NAMES
+        .iter()
+        .zip(quirks.flags())
+        .map(|(name, enabled)| (name.to_string(), Value::from(enabled)))
+        .collect();
+
+    table.into()
+}
+
+/// Format color as `RRGGBB`.
+///
+/// # Parameters
+/// - `color` - given RGB color.
+///
+/// # Returns
+/// - Hexadecimal color string representation.
+fn hex((r, g, b): Color) -> String {
+    format!("{r:02X}{g:02X}{b:02X}")
+}
+
+/// Get string value.
+///
+/// # Parameters
+/// - `key`   - given value key.
+/// - `value` - given value.
+///
+/// # Returns
+/// - String - in case of success.
+/// - `Err`  - otherwise.
+fn string<'a>(key: &str, value: &'a Value) -> EmulatorResult<&'a str> {
+    value
+        .as_str()
+        .ok_or_else(|| format!("'{key}' should be a string"))
+}
+
+/// Get integer value.
+///
+/// # Parameters
+/// - `key`   - given value key.
+/// - `value` - given value.
+///
+/// # Returns
+/// - Integer - in case of success.
+/// - `Err`   - otherwise.
+fn integer(key: &str, value: &Value) -> EmulatorResult<i64> {
+    value
+        .as_integer()
+        .ok_or_else(|| format!("'{key}' should be an integer"))
+}
+
+/// Get boolean value.
+///
+/// # Parameters
+/// - `key`   - given value key.
+/// - `value` - given value.
+///
+/// # Returns
+/// - Boolean - in case of success.
+/// - `Err`   - otherwise.
+fn boolean(key: &str, value: &Value) -> EmulatorResult<bool> {
+    value
+        .as_bool()
+        .ok_or_else(|| format!("'{key}' should be true or false"))
+}
+
+/// Get table value.
+///
+/// # Parameters
+/// - `key`   - given value key.
+/// - `value` - given value.
+///
+/// # Returns
+/// - Table - in case of success.
+/// - `Err` - otherwise.
+fn table_of<'a>(key: &str, value: &'a Value) -> EmulatorResult<&'a Table> {
+    value
+        .as_table()
+        .ok_or_else(|| format!("'{key}' should be a table"))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let text = r#"
+            speed = 15
+            quirks = { preset = "schip", shifting = false }
+            keymap = "1234qwerasdfzxcv"
+            braille = true
+            scale = 4
+            palette = "FFFFFF,000000"
+
+            [sound]
+            frequency = 330.5
+            volume = 50
+
+            [paths]
+            roms = "roms"
+            romdb = "programs.json"
+        "#;
+
+        let mut settings = Settings::default();
+        let mut paths = Paths::default();
+        parse(text, &mut settings, &mut paths).unwrap();
+
+        let defaults = settings.defaults.clone();
+        let quirks = defaults.quirks.unwrap();
+        assert_eq!(None, settings.speed);
+        assert_eq!(Some(15), defaults.speed);
+        assert!(quirks.clipping && !quirks.shifting);
+        assert_eq!(Some("1234qwerasdfzxcv"), defaults.keymap.as_deref());
+        assert_eq!(None, settings.terminal.keymap);
+        assert_eq!(Glyphs::Braille, settings.terminal.glyphs);
+        assert_eq!(4, settings.capture.scale);
+        assert_eq!(Some(((0xFF, 0xFF, 0xFF), (0, 0, 0))), defaults.palette);
+        assert_eq!(330.5, settings.sound.frequency);
+        assert_eq!(50, settings.sound.volume);
+        assert_eq!(sound::DEFAULT_SAMPLE_RATE, settings.sound.sample_rate);
+        assert_eq!(Some("roms"), paths.roms.as_deref());
+        assert_eq!(Some("programs.json"), settings.database.as_deref());
+
+        for text in [
+            "speed = 0",
+            "speed = \"fast\"",
+            "quirks = \"unknown\"",
+            "quirks = { wrapping = true }",
+            "keymap = \"abc\"",
+            "[sound]\nvolume = 101",
+            "[paths]\nrom = \"roms\"",
+            "unknown = 1",
+            "speed = ",
+        ] {
+            let mut settings = Settings::default();
+            assert!(parse(text, &mut settings, &mut paths).is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let mut settings = Settings {
+            speed: Some(20),
+            quirks: Some(Quirks {
+                jumping: true,
+                ..Quirks::chip8()
+            }),
+            ..Settings::default()
+        };
+        settings.sound.frequency = 220.0;
+        set_palette(&mut settings, ((0xFF, 0xFF, 0xFF), (0, 0, 0)));
+
+        let paths = Paths {
+            monitor: Some("monitor.bin".to_string()),
+            ..Paths::default()
+        };
+
+        let text = render(&settings, &paths);
+        let mut parsed = Settings::default();
+        let mut parsed_paths = Paths::default();
+        parse(&text, &mut parsed, &mut parsed_paths).unwrap();
+
+        assert_eq!(settings.speed, parsed.defaults.speed);
+        assert_eq!(settings.quirks, parsed.defaults.quirks);
+        assert_eq!(settings.capture.palette, parsed.defaults.palette);
+        assert_eq!(220.0, parsed.sound.frequency);
+        assert_eq!(paths, parsed_paths);
+
+        // Presets are written by name, database settings are omitted.
+        let text = render(&Settings::default(), &Paths::default());
+        assert!(!text.contains("speed") && !text.contains("[paths]"));
+        assert!(!text.contains("keymap") && !text.contains("palette"));
+
+        settings.quirks = Some(Quirks::xochip());
+        assert!(render(&settings, &paths).contains("quirks = \"xochip\""));
+    }
+
+    #[test]
+    fn test_defaults() {
+        let mut settings = Settings::default();
+        let mut paths = Paths::default();
+        let text =
+            "speed = 15\nquirks = \"schip\"\nkeymap = \"1234qwerasdfzxcv\"";
+        parse(text, &mut settings, &mut paths).unwrap();
+
+        // Speed set by ROM database is kept, unset values are filled.
+        settings.speed = Some(30);
+        settings.defaults.clone().apply(&mut settings);
+
+        assert_eq!(Some(30), settings.speed);
+        assert_eq!(Some(Quirks::schip()), settings.quirks);
+        assert_eq!(
+            Some("1234qwerasdfzxcv"),
+            settings.terminal.keymap.as_deref()
+        );
+
+        // Command line keymap equal to the default still takes precedence.
+        let mut settings = Settings::default();
+        parse(text, &mut settings, &mut paths).unwrap();
+        settings.terminal.keymap = Some(terminal::DEFAULT_KEYMAP.to_string());
+        settings.defaults.clone().apply(&mut settings);
+
+        assert_eq!(
+            Some(terminal::DEFAULT_KEYMAP),
+            settings.terminal.keymap.as_deref()
+        );
+    }
+}
@@ -0,0 +1,249 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Program code and branch coverage.
+
+use crate::emulator::{
+    cpu::{Cpu, Observer, RAM_SIZE},
+    disasm::{self, Decodable},
+    opcode::OpCode,
+};
+use std::collections::BTreeMap;
+
+/// Conditional skip instruction outcomes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Branch {
+    /// Number of times next instruction was skipped.
+    pub taken: u64,
+    /// Number of times next instruction was not skipped.
+    pub not_taken: u64,
+}
+
+/// Code coverage collector struct.
+pub struct Coverage {
+    /// Whether instruction at memory address was executed.
+    executed: Vec<bool>,
+    /// Outcomes of conditional skip instructions per address.
+    branches: BTreeMap<u16, Branch>,
+    /// Address of currently executing conditional skip instruction.
+    branch: Option<u16>,
+}
+
+impl Coverage {
+    /// Construct new `Coverage` object.
+    ///
+    /// # Returns
+    /// - New `Coverage` object.
+    pub fn new() -> Self {
+        Self {
+            executed: vec![false; RAM_SIZE],
+            branches: BTreeMap::new(),
+            branch: None,
+        }
+    }
+
+    /// Check whether instruction at specified address was executed.
+    ///
+    /// # Parameters
+    /// - `addr` - given instruction address.
+    ///
+    /// # Returns
+    /// - `true` - if instruction was executed at least once.
+    pub fn is_executed(&self, addr: usize) -> bool {
+        self.executed.get(addr).copied().unwrap_or(false)
+    }
+
+    /// Get outcomes of conditional skip instruction.
+    ///
+    /// # Parameters
+    /// - `addr` - given instruction address.
+    ///
+    /// # Returns
+    /// - Branch outcomes (zeroed if never executed).
+    pub fn branch(&self, addr: u16) -> Branch {
+        self.branches.get(&addr).copied().unwrap_or_default()
+    }
+
+    /// Print disassembly listing annotated with coverage marks.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    /// - `load_addr`    - given program load address.
+    pub fn annotated_listing(&self, program_data: &[u8], load_addr: usize) {
+        for (addr, opcode) in disasm::listing(program_data, load_addr) {
+            let bytes = opcode.raw;
+            let mark = if self.is_executed(addr) { '+' } else { ' ' };
+            let mnemonic = opcode.decode();
+
+            if is_branch(&opcode) {
+                let Branch { taken, not_taken } = self.branch(addr as u16);
+
+                println!(
+                    "<{addr:#05X}>  |{bytes:04X}|  {mark}  {mnemonic:<20}  \
+                     taken: {taken}, not taken: {not_taken}"
+                );
+            } else {
+                println!("<{addr:#05X}>  |{bytes:04X}|  {mark}  {mnemonic}");
+            }
+        }
+    }
+
+    /// Print coverage summary.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    /// - `load_addr`    - given program load address.
+    pub fn summary(&self, program_data: &[u8], load_addr: usize) {
+        let (instructions, executed, outcomes, covered) =
+            self.totals(program_data, load_addr);
+        let percent = |part: usize, total: usize| {
+            if total == 0 {
+                100.0
+            } else {
+                part as f64 * 100.0 / total as f64
+            }
+        };
+
+        println!(
+            "\nInstructions: {executed}/{instructions} ({:.2}%)",
+            percent(executed, instructions)
+        );
+        println!(
+            "Branches:     {covered}/{outcomes} ({:.2}%)",
+            percent(covered, outcomes)
+        );
+    }
+
+    /// Count covered program instructions and branch outcomes.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    /// - `load_addr`    - given program load address.
+    ///
+    /// # Returns
+    /// - Tuple of:
+    ///   - Number of program instructions.
+    ///   - Number of executed program instructions.
+    ///   - Number of possible branch outcomes.
+    ///   - Number of exercised branch outcomes.
+    pub fn totals(
+        &self,
+        program_data: &[u8],
+        load_addr: usize,
+    ) -> (usize, usize, usize, usize) {
+        let mut totals = (0, 0, 0, 0);
+
+        for (addr, opcode) in disasm::listing(program_data, load_addr) {
+            totals.0 += 1;
+            totals.1 += self.is_executed(addr) as usize;
+
+            if is_branch(&opcode) {
+                let branch = self.branch(addr as u16);
+
+                totals.2 += 2;
+                totals.3 += (branch.taken > 0) as usize;
+                totals.3 += (branch.not_taken > 0) as usize;
+            }
+        }
+
+        totals
+    }
+}
+
+impl Default for Coverage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Observer for Coverage {
+    /// Mark instruction which is about to be executed and remember
+    /// conditional skip instruction.
+    ///
+    /// # Parameters
+    /// - `cpu` - given CPU before executing instruction.
+    fn before_step(&mut self, cpu: &Cpu) {
+        let pc = cpu.pc() as usize;
+        let memory = cpu.memory();
+
+        self.branch = None;
+
+        if pc + 1 >= RAM_SIZE {
+            return;
+        }
+
+        let raw = u16::from_be_bytes([memory[pc], memory[pc + 1]]);
+
+        self.executed[pc] = true;
+
+        if is_branch(&OpCode::new(raw)) {
+            self.branch = Some(pc as u16);
+        }
+    }
+
+    /// Record outcome of executed conditional skip instruction.
+    ///
+    /// # Parameters
+    /// - `cpu` - given CPU after executing instruction.
+    fn after_step(&mut self, cpu: &Cpu) {
+        if let Some(addr) = self.branch.take() {
+            let branch = self.branches.entry(addr).or_default();
+
+            if cpu.pc() == addr + 4 {
+                branch.taken += 1;
+            } else {
+                branch.not_taken += 1;
+            }
+        }
+    }
+}
+
+/// Check whether opcode is a conditional skip instruction.
+///
+/// # Parameters
+/// - `opcode` - given opcode.
+///
+/// # Returns
+/// - `true` - for SE, SNE, SKP and SKNP instructions.
+fn is_branch(opcode: &OpCode) -> bool {
+    match opcode.class {
+        0x3 | 0x4 => true,
+        0x5 | 0x9 => opcode.nibble == 0x0,
+        0xE => matches!(opcode.byte, 0x9E | 0xA1),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::emulator::{
+        cpu::{
+            START_ADDR,
+            tests::{cpu_with_program, program_bytes},
+        },
+        quirks::Quirks,
+    };
+
+    #[test]
+    fn test_coverage() {
+        // 0x200: SE V0, 00 skips 0x202 while V0 = 0, then V0 = 1.
+        let program: [u16; 5] = [0x3000, 0x1200, 0x7001, 0x1200, 0x00E0];
+        let bytes = program_bytes(&program);
+        let mut cpu = cpu_with_program(&program, Quirks::default());
+        let mut coverage = Coverage::new();
+
+        for _ in 0..5 {
+            coverage.before_step(&cpu);
+            cpu.step().unwrap();
+            coverage.after_step(&cpu);
+        }
+
+        assert!(coverage.is_executed(0x200));
+        assert!(coverage.is_executed(0x202));
+        assert!(!coverage.is_executed(0x208));
+        assert_eq!(1, coverage.branch(0x200).taken);
+        assert_eq!(1, coverage.branch(0x200).not_taken);
+        assert_eq!((5, 4, 2, 2), coverage.totals(&bytes, START_ADDR));
+    }
+}
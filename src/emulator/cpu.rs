@@ -3,22 +3,94 @@
 
 //! Emulated CPU related declarations.
 
-use crate::emulator::{disasm::Decodable, opcode::OpCode};
-use rand::Rng;
+use crate::emulator::{
+    EmulatorResult,
+    opcode::OpCode,
+    quirks::Quirks,
+    random::{Algorithm, Random},
+};
+use std::sync::Arc;
+
+mod decode;
+mod recompiler;
+mod state;
+mod timing;
+
+use decode::Instruction;
+use recompiler::BlockCache;
+pub use recompiler::Engine;
+pub use timing::Timing;
 
 /// CHIP-8 RAM size (4 KB).
-const RAM_SIZE: usize = 4096;
+pub const RAM_SIZE: usize = 4096;
 
 /// CHIP-8 stack size (number levels of nested subroutines).
-const STACK_SIZE: usize = 16;
+pub const STACK_SIZE: usize = 16;
 
 /// CHIP-8 general-purpose registers count.
-const REGISTER_COUNT: usize = 16;
+pub const REGISTER_COUNT: usize = 16;
 
 /// Program start memory address of most CHIP-8 programs.
 pub const START_ADDR: usize = 0x200;
 
+/// CHIP-8 display width in pixels.
+pub const DISPLAY_WIDTH: usize = 64;
+
+/// CHIP-8 display height in pixels.
+pub const DISPLAY_HEIGHT: usize = 32;
+
+/// CHIP-8 hexadecimal keypad keys count.
+pub const KEY_COUNT: usize = 16;
+
+/// XO-CHIP audio pattern buffer size in bytes.
+pub const AUDIO_PATTERN_SIZE: usize = 16;
+
+/// XO-CHIP audio pattern pitch corresponding to 4000 Hz playback rate.
+pub const DEFAULT_PITCH: u8 = 64;
+
+/// Memory address of the builtin hexadecimal font.
+pub const FONT_ADDR: usize = 0x050;
+
+/// Size of single builtin font character sprite in bytes.
+const FONT_CHAR_SIZE: usize = 5;
+
+/// Builtin hexadecimal font sprites (0-F).
+const FONT: [u8; KEY_COUNT * FONT_CHAR_SIZE] = [
+    0xF0, 0x90, 0x90, 0x90, 0xF0, // 0
+    0x20, 0x60, 0x20, 0x20, 0x70, // 1
+    0xF0, 0x10, 0xF0, 0x80, 0xF0, // 2
+    0xF0, 0x10, 0xF0, 0x10, 0xF0, // 3
+    0x90, 0x90, 0xF0, 0x10, 0x10, // 4
+    0xF0, 0x80, 0xF0, 0x10, 0xF0, // 5
+    0xF0, 0x80, 0xF0, 0x90, 0xF0, // 6
+    0xF0, 0x10, 0x20, 0x40, 0x40, // 7
+    0xF0, 0x90, 0xF0, 0x90, 0xF0, // 8
+    0xF0, 0x90, 0xF0, 0x10, 0xF0, // 9
+    0xF0, 0x90, 0xF0, 0x90, 0x90, // A
+    0xE0, 0x90, 0xE0, 0x90, 0xE0, // B
+    0xF0, 0x80, 0x80, 0x80, 0xF0, // C
+    0xE0, 0x90, 0x90, 0x90, 0xE0, // D
+    0xF0, 0x80, 0xF0, 0x80, 0xF0, // E
+    0xF0, 0x80, 0xF0, 0x80, 0x80, // F
+];
+
+/// CPU instructions execution observer trait.
+pub trait Observer {
+    /// Handle instruction which is about to be executed.
+    ///
+    /// # Parameters
+    /// - `cpu` - given CPU before executing instruction.
+    fn before_step(&mut self, cpu: &Cpu);
+
+    /// Handle executed instruction.
+    ///
+    /// # Parameters
+    /// - `cpu` - given CPU after executing instruction.
+    fn after_step(&mut self, cpu: &Cpu);
+}
+
 /// Emulated CPU main struct.
+#[derive(Clone)]
 pub struct Cpu {
     /// CHIP-8 RAM.
     memory: [u8; RAM_SIZE],
@@ -36,8 +108,40 @@ pub struct Cpu {
     dt: u8,
     /// Sound timer register.
     st: u8,
-    /// Current executing opcode.
-    opcode: OpCode,
+    /// Raw current executing opcode.
+    opcode: u16,
+    /// Monochrome display pixels in row-major order.
+    display: [bool; DISPLAY_WIDTH * DISPLAY_HEIGHT],
+    /// Hexadecimal keypad keys state.
+    keypad: [bool; KEY_COUNT],
+    /// Interpreter behavior quirks.
+    quirks: Quirks,
+    /// Random number generator used by RND instruction.
+    random: Random,
+    /// Number of executed instructions.
+    cycles: u64,
+    /// Cost of executed instructions in the current frame (instructions or
+    /// machine cycles depending on timing model).
+    frame_cycles: usize,
+    /// Whether the current frame was ended by sprite drawing.
+    vblank_wait: bool,
+    /// XO-CHIP audio pattern buffer (128 one-bit samples).
+    audio_pattern: Option<[u8; AUDIO_PATTERN_SIZE]>,
+    /// XO-CHIP audio pattern playback pitch.
+    pitch: u8,
+    /// Pre-decoded instruction at every memory address.
+    ///
+    /// The table takes 4 bytes per address (16 KiB). It is shared between
+    /// cloned machines and copied only when memory write changes decoding,
+    /// so clones running the same program cost RAM only until they modify
+    /// memory.
+    decoded: Arc<[Instruction]>,
+    /// Instruction execution engine.
+    engine: Engine,
+    /// Compiled basic blocks (only with recompiler engine).
+    blocks: Option<Box<BlockCache>>,
+    /// Instruction timing model.
+    timing: Timing,
 }
 
 impl Cpu {
@@ -46,13 +150,25 @@ impl Cpu {
     /// # Returns
     /// - New `Cpu` object.
     pub fn new() -> Self {
-        let memory = [0u8; RAM_SIZE];
+        Self::with_quirks(Quirks::default())
+    }
+
+    /// Construct new `Cpu` object with specific quirks.
+    ///
+    /// # Parameters
+    /// - `quirks` - given interpreter behavior quirks.
+    ///
+    /// # Returns
+    /// - New `Cpu` object.
+    pub fn with_quirks(quirks: Quirks) -> Self {
+        let mut memory = [0u8; RAM_SIZE];
         let registers = [0u8; REGISTER_COUNT];
         let stack = [0u16; STACK_SIZE];
         let pc = START_ADDR as u16;
-        let opcode = OpCode::new(0);
 
-        Self {
+        memory[FONT_ADDR..FONT_ADDR + FONT.len()].copy_from_slice(&FONT);
+
+        let mut cpu = Self {
             memory,
             registers,
             register_i: 0,
@@ -61,106 +177,500 @@ impl Cpu {
             stack,
             dt: 0,
             st: 0,
-            opcode,
-        }
+            opcode: 0,
+            display: [false; DISPLAY_WIDTH * DISPLAY_HEIGHT],
+            keypad: [false; KEY_COUNT],
+            quirks,
+            random: Random::new(Algorithm::SplitMix, rand::random()),
+            cycles: 0,
+            frame_cycles: 0,
+            vblank_wait: false,
+            audio_pattern: None,
+            pitch: DEFAULT_PITCH,
+            decoded: vec![Instruction::Unknown; RAM_SIZE].into(),
+            engine: Engine::Interpreter,
+            blocks: None,
+            timing: Timing::Instructions,
+        };
+
+        cpu.decode_range(0, RAM_SIZE);
+        cpu
     }
 
-    /// Load program to RAM.
+    /// Load program to RAM at default start address.
     ///
     /// # Parameters
     /// - `program_data` - given program data bytes.
-    pub fn load_program(&mut self, program_data: &[u8]) {
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - if program does not fit in memory.
+    pub fn load_program(&mut self, program_data: &[u8]) -> EmulatorResult<()> {
+        self.load_program_at(program_data, START_ADDR)
+    }
+
+    /// Load program to RAM at specific address and start execution there.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    /// - `addr`         - given load and entry address.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - if program does not fit in memory.
+    pub fn load_program_at(
+        &mut self,
+        program_data: &[u8],
+        addr: usize,
+    ) -> EmulatorResult<()> {
         let program_size = program_data.len();
-        let load_range = START_ADDR..START_ADDR + program_size;
-        let memory_slice = &mut self.memory[load_range];
 
-        memory_slice.copy_from_slice(program_data);
+        if addr >= RAM_SIZE {
+            return Err(format!(
+                "Load address {addr:#05X} is outside of memory"
+            ));
+        }
+
+        if program_size > RAM_SIZE - addr {
+            return Err(format!(
+                "Program of {program_size} bytes does not fit in memory at \
+                 {addr:#05X} ({} bytes available)",
+                RAM_SIZE - addr
+            ));
+        }
+
+        self.memory[addr..addr + program_size].copy_from_slice(program_data);
+        self.decode_range(addr.saturating_sub(1), addr + program_size);
+        self.pc = addr as u16;
+
+        if let Some(blocks) = &mut self.blocks {
+            blocks.clear();
+        }
+
+        Ok(())
     }
 
-    /// Run a CPU.
-    pub fn run(&mut self) {
-        while self.pc != RAM_SIZE as u16 {
-            self.fetch();
-            self.execute();
+    /// Get program counter.
+    ///
+    /// # Returns
+    /// - Address of the next instruction to execute.
+    pub fn pc(&self) -> u16 {
+        self.pc
+    }
 
-            self.pc += 2;
+    /// Get I register.
+    ///
+    /// # Returns
+    /// - Value of the I register.
+    pub fn register_i(&self) -> u16 {
+        self.register_i
+    }
+
+    /// Get general purpose registers.
+    ///
+    /// # Returns
+    /// - Values of V0-VF registers.
+    pub fn registers(&self) -> &[u8; REGISTER_COUNT] {
+        &self.registers
+    }
+
+    /// Get stack pointer.
+    ///
+    /// # Returns
+    /// - Number of occupied stack levels.
+    pub fn sp(&self) -> u8 {
+        self.sp
+    }
+
+    /// Get execution stack.
+    ///
+    /// # Returns
+    /// - Execution stack return addresses.
+    pub fn stack(&self) -> &[u16; STACK_SIZE] {
+        &self.stack
+    }
+
+    /// Get delay timer.
+    ///
+    /// # Returns
+    /// - Value of the delay timer register.
+    pub fn dt(&self) -> u8 {
+        self.dt
+    }
+
+    /// Get sound timer.
+    ///
+    /// # Returns
+    /// - Value of the sound timer register.
+    pub fn st(&self) -> u8 {
+        self.st
+    }
+
+    /// Get RAM.
+    ///
+    /// # Returns
+    /// - CHIP-8 RAM bytes.
+    pub fn memory(&self) -> &[u8; RAM_SIZE] {
+        &self.memory
+    }
+
+    /// Get last executed opcode.
+    ///
+    /// # Returns
+    /// - Last fetched opcode.
+    pub fn opcode(&self) -> OpCode {
+        OpCode::new(self.opcode)
+    }
+
+    /// Get display pixels.
+    ///
+    /// # Returns
+    /// - Display pixels in row-major order.
+    pub fn display(&self) -> &[bool; DISPLAY_WIDTH * DISPLAY_HEIGHT] {
+        &self.display
+    }
+
+    /// Get interpreter behavior quirks.
+    ///
+    /// # Returns
+    /// - Current quirks.
+    pub fn quirks(&self) -> &Quirks {
+        &self.quirks
+    }
+
+    /// Get random number generator.
+    ///
+    /// # Returns
+    /// - Random number generator used by RND instruction.
+    pub fn random(&self) -> &Random {
+        &self.random
+    }
+
+    /// Set random number generator.
+    ///
+    /// # Parameters
+    /// - `random` - given random number generator.
+    pub fn set_random(&mut self, random: Random) {
+        self.random = random;
+    }
+
+    /// Get number of executed instructions.
+    ///
+    /// # Returns
+    /// - Executed instructions count.
+    pub fn cycles(&self) -> u64 {
+        self.cycles
+    }
+
+    /// Get XO-CHIP audio pattern.
+    ///
+    /// # Returns
+    /// - Audio pattern buffer - if program loaded one.
+    /// - `None`               - otherwise.
+    pub fn audio_pattern(&self) -> Option<&[u8; AUDIO_PATTERN_SIZE]> {
+        self.audio_pattern.as_ref()
+    }
+
+    /// Get XO-CHIP audio pattern playback pitch.
+    ///
+    /// # Returns
+    /// - Pitch register value.
+    pub fn pitch(&self) -> u8 {
+        self.pitch
+    }
+
+    /// Get keypad state.
+    ///
+    /// # Returns
+    /// - Whether each keypad key (0x0-0xF) is pressed.
+    pub fn keypad(&self) -> &[bool; KEY_COUNT] {
+        &self.keypad
+    }
+
+    /// Set keypad key state.
+    ///
+    /// # Parameters
+    /// - `key`     - given keypad key (0x0-0xF).
+    /// - `pressed` - given key state.
+    pub fn set_key(&mut self, key: u8, pressed: bool) {
+        self.keypad[(key & 0xF) as usize] = pressed;
+    }
+
+    /// Execute single instruction as a part of a frame.
+    ///
+    /// # Parameters
+    /// - `speed` - given number of instructions per frame (ignored by VIP
+    ///   timing model).
+    ///
+    /// # Returns
+    /// - `true`  - if the frame was finished and timers were decremented.
+    /// - `false` - if the frame is still in progress.
+    /// - `Err`   - in case of execution error.
+    pub fn cycle(&mut self, speed: usize) -> EmulatorResult<bool> {
+        let cost = self.instruction_cost();
+        let length = self.frame_length(speed);
+
+        self.step()?;
+        self.frame_cycles += cost;
+
+        if self.frame_cycles >= length || self.vblank_wait {
+            // Cycles of instructions overlapping the frame end are carried
+            // over unless the frame was ended by vertical blank.
+            self.frame_cycles = match self.vblank_wait {
+                true => 0,
+                false => self.frame_cycles - length,
+            };
+            self.vblank_wait = false;
+            self.tick_timers();
+
+            return Ok(true);
         }
+
+        Ok(false)
     }
 
-    /// Extract next opcode from memory.
+    /// Execute single frame of instructions.
+    ///
+    /// # Parameters
+    /// - `speed` - given number of instructions per frame.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    pub fn frame(&mut self, speed: usize) -> EmulatorResult<()> {
+        if self.engine != Engine::Recompiler
+            || self.timing != Timing::Instructions
+        {
+            while !self.cycle(speed)? {}
+            return Ok(());
+        }
+
+        loop {
+            let budget = speed.saturating_sub(self.frame_cycles).max(1);
+            let (executed, result) = self.run_block(budget);
+
+            self.frame_cycles += executed;
+            result?;
+
+            if self.frame_cycles >= speed || self.vblank_wait {
+                self.frame_cycles = 0;
+                self.vblank_wait = false;
+                self.tick_timers();
+
+                return Ok(());
+            }
+        }
+    }
+
+    /// Fetch and execute single instruction.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    pub fn step(&mut self) -> EmulatorResult<()> {
+        match self.engine {
+            Engine::Reference => self.step_uncached(),
+            Engine::Interpreter => self.step_instruction(),
+            Engine::Recompiler => self.run_block(1).1,
+        }
+    }
+
+    /// Fetch and execute single pre-decoded instruction.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
     #[inline(always)]
-    fn fetch(&mut self) {
+    fn step_instruction(&mut self) -> EmulatorResult<()> {
+        let instruction = self.fetch()?;
+        self.cycles += 1;
+        self.execute(instruction)
+    }
+
+    /// Fetch, decode and execute single instruction bypassing instruction
+    /// cache.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn step_uncached(&mut self) -> EmulatorResult<()> {
+        self.fetch()?;
+        self.cycles += 1;
+        self.execute(Instruction::decode(self.opcode))
+    }
+
+    /// Decrement delay and sound timers at 60 Hz rate.
+    pub fn tick_timers(&mut self) {
+        self.dt = self.dt.saturating_sub(1);
+        self.st = self.st.saturating_sub(1);
+    }
+
+    /// Decode instructions starting in specified memory range.
+    ///
+    /// # Parameters
+    /// - `start` - given first address.
+    /// - `end`   - given address after the last one.
+    fn decode_range(&mut self, start: usize, end: usize) {
+        for addr in start..end.min(RAM_SIZE) {
+            let instruction = match self.memory.get(addr..addr + 2) {
+                Some(&[high, low]) => {
+                    Instruction::decode(u16::from_be_bytes([high, low]))
+                }
+                _ => Instruction::Unknown,
+            };
+
+            // Keep table shared with clones unless decoding changes.
+            if self.decoded[addr] != instruction {
+                Arc::make_mut(&mut self.decoded)[addr] = instruction;
+            }
+        }
+    }
+
+    /// Write byte to RAM, re-decoding instructions which include it.
+    ///
+    /// # Parameters
+    /// - `addr`  - given RAM index.
+    /// - `value` - given byte to write.
+    #[inline(always)]
+    fn write(&mut self, addr: usize, value: u8) {
+        self.memory[addr] = value;
+        self.decode_range(addr.saturating_sub(1), addr + 1);
+
+        if let Some(blocks) = &mut self.blocks {
+            blocks.invalidate(addr);
+        }
+    }
+
+    /// Extract next instruction from memory.
+    ///
+    /// # Returns
+    /// - Pre-decoded instruction - in case of success.
+    /// - `Err`                   - otherwise.
+    #[inline(always)]
+    fn fetch(&mut self) -> EmulatorResult<Instruction> {
         let pos = self.pc as usize;
-        let raw = u16::from_be_bytes([self.memory[pos], self.memory[pos + 1]]);
 
-        self.opcode = OpCode::new(raw);
+        if pos + 1 >= RAM_SIZE {
+            return Err(format!("program counter out of memory: {pos:#05X}"));
+        }
+
+        self.opcode =
+            u16::from_be_bytes([self.memory[pos], self.memory[pos + 1]]);
+        self.pc += 2;
+
+        Ok(self.decoded[pos])
     }
 
     /// Execute CPU instruction.
+    ///
+    /// # Parameters
+    /// - `instruction` - given decoded instruction.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
     #[inline(always)]
-    fn execute(&mut self) {
-        let opcode = &self.opcode;
-
-        println!("Executing: |{:04X}|   {}", opcode.raw, opcode.decode());
-
-        match opcode.class {
-            0x0 => self.execute_0xxx(),
-            0x1 => self.execute_0nnn(),
-            0x2 => self.execute_0nnn(),
-            0x3 => self.execute_xkk(),
-            0x4 => self.execute_xkk(),
-            0x5 => unimplemented!(),
-            0x6 => self.execute_xkk(),
-            0x7 => self.execute_xkk(),
-            0x8 => unimplemented!(),
-            0x9 => unimplemented!(),
-            0xA => self.execute_0nnn(),
-            0xB => self.execute_0nnn(),
-            0xC => self.execute_xkk(),
-            0xD => unimplemented!(),
-            0xE => self.execute_ex(),
-            0xF => unimplemented!(),
-            _ => self.unknown(),
+    fn execute(&mut self, instruction: Instruction) -> EmulatorResult<()> {
+        match instruction {
+            Instruction::Cls => self.clear_display(),
+            Instruction::Ret => self.ret()?,
+            Instruction::Sys(addr) => self.sys(addr),
+            Instruction::Jump(addr) => self.jump(addr),
+            Instruction::Call(addr) => self.call(addr)?,
+            Instruction::SkipEq(reg, byte) => self.skip_eq(reg, byte),
+            Instruction::SkipNe(reg, byte) => self.skip_ne(reg, byte),
+            Instruction::SkipEqReg(x, y) => self.skip_eq_reg(x, y),
+            Instruction::SetByte(reg, byte) => self.set_reg_byte(reg, byte),
+            Instruction::AddByte(reg, byte) => self.add_reg_byte(reg, byte),
+            Instruction::SetReg(x, y) => self.set_reg_reg(x, y),
+            Instruction::Or(x, y) => self.or(x, y),
+            Instruction::And(x, y) => self.and(x, y),
+            Instruction::Xor(x, y) => self.xor(x, y),
+            Instruction::AddReg(x, y) => self.add_reg_reg(x, y),
+            Instruction::Sub(x, y) => self.sub(x, y),
+            Instruction::Shr(x, y) => self.shr(x, y),
+            Instruction::Subn(x, y) => self.subn(x, y),
+            Instruction::Shl(x, y) => self.shl(x, y),
+            Instruction::SkipNeReg(x, y) => self.skip_ne_reg(x, y),
+            Instruction::SetI(addr) => self.set_reg_i(addr),
+            Instruction::JumpOffset(reg, addr) => {
+                self.jump_by_offset(reg, addr)
+            }
+            Instruction::Rnd(reg, byte) => self.rnd(reg, byte),
+            Instruction::Draw(x, y, height) => self.draw(x, y, height),
+            Instruction::SkipKey(reg) => self.skip_if_key_pressed(reg),
+            Instruction::SkipNotKey(reg) => self.skip_if_key_not_pressed(reg),
+            Instruction::LoadAudio => self.load_audio_pattern(),
+            Instruction::GetDelay(reg) => {
+                self.registers[reg as usize] = self.dt
+            }
+            Instruction::WaitKey(reg) => self.wait_key(reg),
+            Instruction::SetDelay(reg) => {
+                self.dt = self.registers[reg as usize]
+            }
+            Instruction::SetSound(reg) => {
+                self.st = self.registers[reg as usize]
+            }
+            Instruction::AddI(reg) => self.add_reg_i(reg),
+            Instruction::Font(reg) => self.set_font_char(reg),
+            Instruction::Bcd(reg) => self.store_bcd(reg),
+            Instruction::SetPitch(reg) => {
+                self.pitch = self.registers[reg as usize]
+            }
+            Instruction::Store(reg) => self.store_registers(reg),
+            Instruction::Load(reg) => self.load_registers(reg),
+            Instruction::Unknown => return self.unknown(),
         }
+
+        Ok(())
     }
 
     /// Handle unknown instruction.
     ///
-    /// # Parameters
-    /// - `opcode` - given unknown opcode to handle.
+    /// # Returns
+    /// - `Err` with unknown opcode description.
     #[inline(always)]
-    fn unknown(&self) {
-        let opcode = &self.opcode;
+    fn unknown(&self) -> EmulatorResult<()> {
+        let addr = self.pc.wrapping_sub(2);
 
-        println!("UNKNOWN: |{:04X}|   {}", opcode.raw, opcode.decode());
-        std::process::exit(1); // TODO: replace with Err(...).
+        Err(format!("unknown opcode {:04X} at {addr:#05X}", self.opcode))
     }
 
-    /// Execute CPU 0xxx opcode class instructions.
+    /// Get RAM index relative to I register.
+    ///
+    /// # Parameters
+    /// - `offset` - given offset from I register.
+    ///
+    /// # Returns
+    /// - RAM index wrapped around RAM size.
     #[inline(always)]
-    fn execute_0xxx(&mut self) {
-        match self.opcode.raw {
-            0x00E0 => self.clear_display(),
-            0x00EE => self.ret(),
-            _ => self.sys(self.opcode.addr),
-        }
+    fn addr_i(&self, offset: usize) -> usize {
+        (self.register_i as usize + offset) % RAM_SIZE
     }
 
     /// Clear the display.
     #[inline(always)]
-    fn clear_display(&self) {
-        unimplemented!()
+    fn clear_display(&mut self) {
+        self.display.fill(false);
     }
 
     /// Return from a subroutine.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - if the stack is empty.
     #[inline(always)]
-    fn ret(&mut self) {
+    fn ret(&mut self) -> EmulatorResult<()> {
         // The interpreter sets the program counter to the address at the top of
         // the stack, then subtracts 1 from the stack pointer.
-        if self.sp > 0 {
-            self.sp -= 1;
-            self.pc = self.stack[self.sp as usize];
+        if self.sp == 0 {
+            return Err("stack underflow".to_string());
         }
+
+        self.sp -= 1;
+        self.pc = self.stack[self.sp as usize];
+
+        Ok(())
     }
 
     /// Jump to a machine code routine at specified address.
@@ -174,20 +684,6 @@ impl Cpu {
         // It is ignored by modern interpreters.
     }
 
-    /// Execute CPU 0nnn opcode class instructions.
-    #[inline(always)]
-    fn execute_0nnn(&mut self) {
-        let addr = self.opcode.addr;
-
-        match self.opcode.class {
-            0x1 => self.jump(addr),
-            0x2 => self.call(addr),
-            0xA => self.set_reg_i(addr),
-            0xB => self.jump_by_offset(addr),
-            _ => self.unknown(),
-        }
-    }
-
     /// Jump to specified location.
     ///
     /// # Parameters
@@ -201,11 +697,21 @@ impl Cpu {
     ///
     /// # Parameters
     /// - `addr` - given memory address to call.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - if the stack is full.
     #[inline(always)]
-    fn call(&mut self, addr: u16) {
-        self.sp += 1;
+    fn call(&mut self, addr: u16) -> EmulatorResult<()> {
+        if self.sp as usize >= STACK_SIZE {
+            return Err("stack overflow".to_string());
+        }
+
         self.stack[self.sp as usize] = self.pc;
+        self.sp += 1;
         self.pc = addr;
+
+        Ok(())
     }
 
     /// Set register I.
@@ -220,26 +726,17 @@ impl Cpu {
     /// Jump to location by offset.
     ///
     /// # Parameters
-    /// - `addr` - given memory address.
+    /// - `reg_x` - given register used with jumping quirk.
+    /// - `addr`  - given memory address.
     #[inline(always)]
-    fn jump_by_offset(&mut self, addr: u16) {
-        self.pc = self.registers[0] as u16 + addr;
-    }
+    fn jump_by_offset(&mut self, reg_x: u8, addr: u16) {
+        let reg = if self.quirks.jumping {
+            reg_x as usize
+        } else {
+            0
+        };
 
-    /// Execute xkk opcode class instructions.
-    #[inline(always)]
-    fn execute_xkk(&mut self) {
-        let reg_x = self.opcode.reg_x;
-        let byte = self.opcode.byte;
-
-        match self.opcode.class {
-            0x3 => self.skip_eq(reg_x, byte),
-            0x4 => self.skip_ne(reg_x, byte),
-            0x6 => self.set_reg_byte(reg_x, byte),
-            0x7 => self.add_reg_byte(reg_x, byte),
-            0xC => self.rnd(reg_x, byte),
-            _ => self.unknown(),
-        }
+        self.pc = self.registers[reg] as u16 + addr;
     }
 
     /// Skip next instruction if `reg` = `byte`.
@@ -283,7 +780,8 @@ impl Cpu {
     /// - `byte` - given byte to compare.
     #[inline(always)]
     fn add_reg_byte(&mut self, reg: u8, byte: u8) {
-        self.registers[reg as usize] += byte;
+        let value = self.registers[reg as usize];
+        self.registers[reg as usize] = value.wrapping_add(byte);
     }
 
     /// Assign to register random byte AND `byte`.
@@ -293,20 +791,233 @@ impl Cpu {
     /// - `byte` - given byte to compare.
     #[inline(always)]
     fn rnd(&mut self, reg: u8, byte: u8) {
-        let mut generator = rand::rng();
-        let random_byte = generator.random_range(0..255);
+        let random_byte = self.random.next_byte(&self.memory);
         self.registers[reg as usize] = random_byte & byte;
     }
 
-    /// Execute Ex opcode class instructions.
-    fn execute_ex(&mut self) {
-        let reg_x = self.opcode.reg_x;
+    /// Skip next instruction if `reg_x` = `reg_y`.
+    ///
+    /// # Parameters
+    /// - `reg_x` - given first register.
+    /// - `reg_y` - given second register.
+    #[inline(always)]
+    fn skip_eq_reg(&mut self, reg_x: u8, reg_y: u8) {
+        if self.registers[reg_x as usize] == self.registers[reg_y as usize] {
+            self.pc += 2;
+        }
+    }
+
+    /// Skip next instruction if `reg_x` != `reg_y`.
+    ///
+    /// # Parameters
+    /// - `reg_x` - given first register.
+    /// - `reg_y` - given second register.
+    #[inline(always)]
+    fn skip_ne_reg(&mut self, reg_x: u8, reg_y: u8) {
+        if self.registers[reg_x as usize] != self.registers[reg_y as usize] {
+            self.pc += 2;
+        }
+    }
+
+    /// Assign `reg_y` to `reg_x`.
+    ///
+    /// # Parameters
+    /// - `reg_x` - given first register.
+    /// - `reg_y` - given second register.
+    #[inline(always)]
+    fn set_reg_reg(&mut self, reg_x: u8, reg_y: u8) {
+        self.registers[reg_x as usize] = self.registers[reg_y as usize];
+    }
+
+    /// Assign `reg_x` OR `reg_y` to `reg_x`.
+    ///
+    /// # Parameters
+    /// - `reg_x` - given first register.
+    /// - `reg_y` - given second register.
+    #[inline(always)]
+    fn or(&mut self, reg_x: u8, reg_y: u8) {
+        self.registers[reg_x as usize] |= self.registers[reg_y as usize];
+        self.reset_vf();
+    }
+
+    /// Assign `reg_x` AND `reg_y` to `reg_x`.
+    ///
+    /// # Parameters
+    /// - `reg_x` - given first register.
+    /// - `reg_y` - given second register.
+    #[inline(always)]
+    fn and(&mut self, reg_x: u8, reg_y: u8) {
+        self.registers[reg_x as usize] &= self.registers[reg_y as usize];
+        self.reset_vf();
+    }
+
+    /// Assign `reg_x` XOR `reg_y` to `reg_x`.
+    ///
+    /// # Parameters
+    /// - `reg_x` - given first register.
+    /// - `reg_y` - given second register.
+    #[inline(always)]
+    fn xor(&mut self, reg_x: u8, reg_y: u8) {
+        self.registers[reg_x as usize] ^= self.registers[reg_y as usize];
+        self.reset_vf();
+    }
+
+    /// Reset VF register after logical instructions if quirk is enabled.
+    #[inline(always)]
+    fn reset_vf(&mut self) {
+        if self.quirks.vf_reset {
+            self.registers[0xF] = 0;
+        }
+    }
+
+    /// Add `reg_y` to `reg_x`, set VF = carry.
+    ///
+    /// # Parameters
+    /// - `reg_x` - given first register.
+    /// - `reg_y` - given second register.
+    #[inline(always)]
+    fn add_reg_reg(&mut self, reg_x: u8, reg_y: u8) {
+        let x = self.registers[reg_x as usize];
+        let y = self.registers[reg_y as usize];
+        let (result, carry) = x.overflowing_add(y);
+
+        self.registers[reg_x as usize] = result;
+        self.registers[0xF] = carry as u8;
+    }
+
+    /// Subtract `reg_y` from `reg_x`, set VF = NOT borrow.
+    ///
+    /// # Parameters
+    /// - `reg_x` - given first register.
+    /// - `reg_y` - given second register.
+    #[inline(always)]
+    fn sub(&mut self, reg_x: u8, reg_y: u8) {
+        let x = self.registers[reg_x as usize];
+        let y = self.registers[reg_y as usize];
+        let (result, borrow) = x.overflowing_sub(y);
+
+        self.registers[reg_x as usize] = result;
+        self.registers[0xF] = !borrow as u8;
+    }
+
+    /// Assign `reg_y` minus `reg_x` to `reg_x`, set VF = NOT borrow.
+    ///
+    /// # Parameters
+    /// - `reg_x` - given first register.
+    /// - `reg_y` - given second register.
+    #[inline(always)]
+    fn subn(&mut self, reg_x: u8, reg_y: u8) {
+        let x = self.registers[reg_x as usize];
+        let y = self.registers[reg_y as usize];
+        let (result, borrow) = y.overflowing_sub(x);
+
+        self.registers[reg_x as usize] = result;
+        self.registers[0xF] = !borrow as u8;
+    }
+
+    /// Shift register right by one bit, set VF = shifted out bit.
+    ///
+    /// # Parameters
+    /// - `reg_x` - given first register.
+    /// - `reg_y` - given second register.
+    #[inline(always)]
+    fn shr(&mut self, reg_x: u8, reg_y: u8) {
+        let source = self.shift_source(reg_x, reg_y);
+
+        self.registers[reg_x as usize] = source >> 1;
+        self.registers[0xF] = source & 0x1;
+    }
+
+    /// Shift register left by one bit, set VF = shifted out bit.
+    ///
+    /// # Parameters
+    /// - `reg_x` - given first register.
+    /// - `reg_y` - given second register.
+    #[inline(always)]
+    fn shl(&mut self, reg_x: u8, reg_y: u8) {
+        let source = self.shift_source(reg_x, reg_y);
+
+        self.registers[reg_x as usize] = source << 1;
+        self.registers[0xF] = source >> 7;
+    }
+
+    /// Get shift instructions source value depending on quirks.
+    ///
+    /// # Parameters
+    /// - `reg_x` - given first register.
+    /// - `reg_y` - given second register.
+    ///
+    /// # Returns
+    /// - Value to shift.
+    #[inline(always)]
+    fn shift_source(&self, reg_x: u8, reg_y: u8) -> u8 {
+        if self.quirks.shifting {
+            self.registers[reg_x as usize]
+        } else {
+            self.registers[reg_y as usize]
+        }
+    }
+
+    /// Draw sprite at (`reg_x`, `reg_y`), set VF = collision.
+    ///
+    /// # Parameters
+    /// - `reg_x`  - given register with X coordinate.
+    /// - `reg_y`  - given register with Y coordinate.
+    /// - `height` - given sprite height in bytes.
+    #[inline(always)]
+    fn draw(&mut self, reg_x: u8, reg_y: u8, height: u8) {
+        let origin_x = self.registers[reg_x as usize] as usize % DISPLAY_WIDTH;
+        let origin_y = self.registers[reg_y as usize] as usize % DISPLAY_HEIGHT;
+        let mut collision = false;
+
+        for row in 0..height as usize {
+            let mut y = origin_y + row;
+
+            if y >= DISPLAY_HEIGHT {
+                if self.quirks.clipping {
+                    break;
+                }
+                y %= DISPLAY_HEIGHT;
+            }
+
+            let sprite = self.memory[self.addr_i(row)];
 
-        match self.opcode.byte {
-            0x9E => self.skip_if_key_pressed(reg_x),
-            0xA1 => self.skip_if_key_not_pressed(reg_x),
-            _ => self.unknown(),
+            for column in 0..8 {
+                if sprite & (0x80 >> column) == 0 {
+                    continue;
+                }
+
+                let mut x = origin_x + column;
+
+                if x >= DISPLAY_WIDTH {
+                    if self.quirks.clipping {
+                        break;
+                    }
+                    x %= DISPLAY_WIDTH;
+                }
+
+                let pixel = &mut self.display[y * DISPLAY_WIDTH + x];
+
+                collision |= *pixel;
+                *pixel ^= true;
+            }
         }
+
+        self.registers[0xF] = collision as u8;
+        self.vblank_wait = self.quirks.display_wait;
+    }
+
+    /// Check whether key with the value of `reg` is pressed.
+    ///
+    /// # Parameters
+    /// - `reg` - given register.
+    ///
+    /// # Returns
+    /// - `true` - if key is pressed.
+    #[inline(always)]
+    fn is_key_pressed(&self, reg: u8) -> bool {
+        let key = self.registers[reg as usize] & 0xF;
+        self.keypad[key as usize]
     }
 
     /// Skip next instruction if key with the value of `reg` is pressed.
@@ -314,8 +1025,10 @@ impl Cpu {
     /// # Parameters
     /// - `reg` - given register.
     #[inline(always)]
-    fn skip_if_key_pressed(&mut self, _reg: u8) {
-        unimplemented!()
+    fn skip_if_key_pressed(&mut self, reg: u8) {
+        if self.is_key_pressed(reg) {
+            self.pc += 2;
+        }
     }
 
     /// Skip next instruction if key with the value of `reg` is not pressed.
@@ -323,7 +1036,433 @@ impl Cpu {
     /// # Parameters
     /// - `reg` - given register.
     #[inline(always)]
-    fn skip_if_key_not_pressed(&mut self, _reg: u8) {
-        unimplemented!()
+    fn skip_if_key_not_pressed(&mut self, reg: u8) {
+        if !self.is_key_pressed(reg) {
+            self.pc += 2;
+        }
+    }
+
+    /// Load XO-CHIP audio pattern from memory starting at I.
+    #[inline(always)]
+    fn load_audio_pattern(&mut self) {
+        let mut pattern = [0u8; AUDIO_PATTERN_SIZE];
+
+        for (i, byte) in pattern.iter_mut().enumerate() {
+            *byte = self.memory[self.addr_i(i)];
+        }
+
+        self.audio_pattern = Some(pattern);
+    }
+
+    /// Wait for a key press, store the value of the key in `reg`.
+    ///
+    /// # Parameters
+    /// - `reg` - given register.
+    #[inline(always)]
+    fn wait_key(&mut self, reg: u8) {
+        match self.keypad.iter().position(|&pressed| pressed) {
+            Some(key) => self.registers[reg as usize] = key as u8,
+            None => self.pc -= 2,
+        }
+    }
+
+    /// Add `reg` to register I.
+    ///
+    /// # Parameters
+    /// - `reg` - given register.
+    #[inline(always)]
+    fn add_reg_i(&mut self, reg: u8) {
+        let value = self.registers[reg as usize] as u16;
+        self.register_i = self.register_i.wrapping_add(value);
+    }
+
+    /// Set register I to location of font sprite for digit in `reg`.
+    ///
+    /// # Parameters
+    /// - `reg` - given register.
+    #[inline(always)]
+    fn set_font_char(&mut self, reg: u8) {
+        let digit = (self.registers[reg as usize] & 0xF) as usize;
+        self.register_i = (FONT_ADDR + digit * FONT_CHAR_SIZE) as u16;
+    }
+
+    /// Store BCD representation of `reg` in memory at I, I+1 and I+2.
+    ///
+    /// # Parameters
+    /// - `reg` - given register.
+    #[inline(always)]
+    fn store_bcd(&mut self, reg: u8) {
+        let value = self.registers[reg as usize];
+
+        self.write(self.addr_i(0), value / 100);
+        self.write(self.addr_i(1), value / 10 % 10);
+        self.write(self.addr_i(2), value % 10);
+    }
+
+    /// Store registers V0 through `reg` in memory starting at I.
+    ///
+    /// # Parameters
+    /// - `reg` - given last register to store.
+    #[inline(always)]
+    fn store_registers(&mut self, reg: u8) {
+        for i in 0..=reg as usize {
+            self.write(self.addr_i(i), self.registers[i]);
+        }
+
+        self.increment_reg_i(reg);
+    }
+
+    /// Read registers V0 through `reg` from memory starting at I.
+    ///
+    /// # Parameters
+    /// - `reg` - given last register to read.
+    #[inline(always)]
+    fn load_registers(&mut self, reg: u8) {
+        for i in 0..=reg as usize {
+            self.registers[i] = self.memory[self.addr_i(i)];
+        }
+
+        self.increment_reg_i(reg);
+    }
+
+    /// Increment register I after bulk memory access if quirk is enabled.
+    ///
+    /// # Parameters
+    /// - `reg` - given last accessed register.
+    #[inline(always)]
+    fn increment_reg_i(&mut self, reg: u8) {
+        if self.quirks.memory {
+            self.register_i = self.register_i.wrapping_add(reg as u16 + 1);
+        }
+    }
+}
+
+impl Default for Cpu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// Convert opcodes to program data bytes.
+    ///
+    /// # Parameters
+    /// - `program` - given program opcodes.
+    ///
+    /// # Returns
+    /// - Program data bytes.
+    pub fn program_bytes(program: &[u16]) -> Vec<u8> {
+        program
+            .iter()
+            .flat_map(|opcode| opcode.to_be_bytes())
+            .collect()
+    }
+
+    /// Construct CPU with given program loaded.
+    ///
+    /// # Parameters
+    /// - `program` - given program opcodes.
+    /// - `quirks`  - given interpreter behavior quirks.
+    ///
+    /// # Returns
+    /// - New `Cpu` object.
+    pub fn cpu_with_program(program: &[u16], quirks: Quirks) -> Cpu {
+        let mut cpu = Cpu::with_quirks(quirks);
+
+        cpu.load_program(&program_bytes(program)).unwrap();
+        cpu
+    }
+
+    /// Execute given number of instructions.
+    ///
+    /// # Parameters
+    /// - `cpu`   - given CPU to run.
+    /// - `count` - given number of instructions to execute.
+    pub fn run_steps(cpu: &mut Cpu, count: usize) {
+        for _ in 0..count {
+            cpu.step().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_font_loaded() {
+        let cpu = Cpu::new();
+        assert_eq!(&FONT[..], &cpu.memory()[FONT_ADDR..FONT_ADDR + 80]);
+    }
+
+    #[test]
+    fn test_jump_and_call() {
+        let program = [0x2206, 0x0000, 0x0000, 0x00EE];
+        let mut cpu = cpu_with_program(&program, Quirks::default());
+
+        cpu.step().unwrap();
+        assert_eq!(0x206, cpu.pc());
+        assert_eq!(1, cpu.sp());
+        assert_eq!(0x202, cpu.stack()[0]);
+
+        cpu.step().unwrap();
+        assert_eq!(0x202, cpu.pc());
+        assert_eq!(0, cpu.sp());
+
+        let mut cpu = cpu_with_program(&[0x1208], Quirks::default());
+        cpu.step().unwrap();
+        assert_eq!(0x208, cpu.pc());
+    }
+
+    #[test]
+    fn test_stack_errors() {
+        let mut cpu = cpu_with_program(&[0x00EE], Quirks::default());
+        assert!(cpu.step().is_err());
+
+        let mut cpu = cpu_with_program(&[0x2200], Quirks::default());
+        run_steps(&mut cpu, STACK_SIZE);
+        assert!(cpu.step().is_err());
+    }
+
+    #[test]
+    fn test_skips() {
+        let program = [0x6105, 0x3105, 0x0000, 0x4105, 0x5120, 0x0000];
+        let mut cpu = cpu_with_program(&program, Quirks::default());
+
+        run_steps(&mut cpu, 2);
+        assert_eq!(0x206, cpu.pc());
+
+        run_steps(&mut cpu, 1);
+        assert_eq!(0x208, cpu.pc());
+
+        run_steps(&mut cpu, 1);
+        assert_eq!(0x20A, cpu.pc());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let program = [0x60FF, 0x6102, 0x8014, 0x6203, 0x8125, 0x6301, 0x8317];
+        let mut cpu = cpu_with_program(&program, Quirks::default());
+
+        run_steps(&mut cpu, 3);
+        assert_eq!(0x01, cpu.registers()[0]);
+        assert_eq!(0x01, cpu.registers()[0xF]);
+
+        run_steps(&mut cpu, 2);
+        assert_eq!(0xFF, cpu.registers()[1]);
+        assert_eq!(0x00, cpu.registers()[0xF]);
+
+        run_steps(&mut cpu, 2);
+        assert_eq!(0xFE, cpu.registers()[3]);
+        assert_eq!(0x01, cpu.registers()[0xF]);
+    }
+
+    #[test]
+    fn test_logic_vf_reset_quirk() {
+        let program = [0x6F05, 0x6003, 0x6105, 0x8011];
+
+        let mut cpu = cpu_with_program(&program, Quirks::chip8());
+        run_steps(&mut cpu, 4);
+        assert_eq!(0x07, cpu.registers()[0]);
+        assert_eq!(0x00, cpu.registers()[0xF]);
+
+        let mut cpu = cpu_with_program(&program, Quirks::schip());
+        run_steps(&mut cpu, 4);
+        assert_eq!(0x05, cpu.registers()[0xF]);
+    }
+
+    #[test]
+    fn test_shift_quirk() {
+        let program = [0x6081, 0x6104, 0x8016];
+
+        let mut cpu = cpu_with_program(&program, Quirks::chip8());
+        run_steps(&mut cpu, 3);
+        assert_eq!(0x02, cpu.registers()[0]);
+        assert_eq!(0x00, cpu.registers()[0xF]);
+
+        let mut cpu = cpu_with_program(&program, Quirks::schip());
+        run_steps(&mut cpu, 3);
+        assert_eq!(0x40, cpu.registers()[0]);
+        assert_eq!(0x01, cpu.registers()[0xF]);
+    }
+
+    #[test]
+    fn test_memory_quirk() {
+        let program = [0xA300, 0x6012, 0x6134, 0xF155, 0xF165];
+
+        let mut cpu = cpu_with_program(&program, Quirks::chip8());
+        run_steps(&mut cpu, 4);
+        assert_eq!([0x12, 0x34], cpu.memory()[0x300..0x302]);
+        assert_eq!(0x302, cpu.register_i());
+
+        let mut cpu = cpu_with_program(&program, Quirks::schip());
+        run_steps(&mut cpu, 5);
+        assert_eq!(0x300, cpu.register_i());
+        assert_eq!(0x12, cpu.registers()[0]);
+    }
+
+    #[test]
+    fn test_bcd() {
+        let program = [0xA300, 0x60FE, 0xF033];
+        let mut cpu = cpu_with_program(&program, Quirks::default());
+
+        run_steps(&mut cpu, 3);
+        assert_eq!([2, 5, 4], cpu.memory()[0x300..0x303]);
+    }
+
+    #[test]
+    fn test_draw_and_collision() {
+        let program = [0x6000, 0xF029, 0xD005, 0xD005];
+        let mut cpu = cpu_with_program(&program, Quirks::default());
+
+        run_steps(&mut cpu, 3);
+        assert!(cpu.display()[0]);
+        assert!(cpu.display()[3]);
+        assert!(!cpu.display()[4]);
+        assert_eq!(0, cpu.registers()[0xF]);
+
+        run_steps(&mut cpu, 1);
+        assert!(cpu.display().iter().all(|&pixel| !pixel));
+        assert_eq!(1, cpu.registers()[0xF]);
+    }
+
+    #[test]
+    fn test_draw_clipping_quirk() {
+        let program = [0x603E, 0xF029, 0xD015];
+
+        let mut cpu = cpu_with_program(&program, Quirks::chip8());
+        run_steps(&mut cpu, 3);
+        assert!(cpu.display()[62]);
+        assert!(!cpu.display()[0]);
+
+        let mut cpu = cpu_with_program(&program, Quirks::xochip());
+        run_steps(&mut cpu, 3);
+        assert!(cpu.display()[0]);
+    }
+
+    #[test]
+    fn test_keypad() {
+        let program = [0x6005, 0xE09E, 0x0000, 0xF10A];
+        let mut cpu = cpu_with_program(&program, Quirks::default());
+
+        cpu.set_key(0x5, true);
+        run_steps(&mut cpu, 2);
+        assert_eq!(0x206, cpu.pc());
+
+        cpu.set_key(0x5, false);
+        run_steps(&mut cpu, 2);
+        assert_eq!(0x206, cpu.pc());
+
+        cpu.set_key(0x7, true);
+        run_steps(&mut cpu, 1);
+        assert_eq!(0x208, cpu.pc());
+        assert_eq!(0x07, cpu.registers()[1]);
+    }
+
+    #[test]
+    fn test_timers_and_frames() {
+        let program = [0x6003, 0xF015, 0x1204];
+        let mut cpu = cpu_with_program(&program, Quirks::default());
+
+        cpu.frame(10).unwrap();
+        assert_eq!(2, cpu.dt());
+        assert_eq!(10, cpu.cycles());
+
+        cpu.frame(10).unwrap();
+        cpu.frame(10).unwrap();
+        cpu.frame(10).unwrap();
+        assert_eq!(0, cpu.dt());
+    }
+
+    #[test]
+    fn test_display_wait_quirk() {
+        let program = [0xD001, 0x1202];
+
+        let mut cpu = cpu_with_program(&program, Quirks::chip8());
+        cpu.frame(10).unwrap();
+        assert_eq!(1, cpu.cycles());
+
+        let mut cpu = cpu_with_program(&program, Quirks::schip());
+        cpu.frame(10).unwrap();
+        assert_eq!(10, cpu.cycles());
+    }
+
+    #[test]
+    fn test_rnd_seeded() {
+        let program = [0xC0FF, 0xC1FF, 0xC20F];
+        let mut first = cpu_with_program(&program, Quirks::default());
+        let mut second = cpu_with_program(&program, Quirks::default());
+
+        first.set_random(Random::new(Algorithm::SplitMix, 1234));
+        second.set_random(Random::new(Algorithm::SplitMix, 1234));
+        run_steps(&mut first, 3);
+        run_steps(&mut second, 3);
+
+        assert_eq!(first.registers(), second.registers());
+        assert_eq!(0, first.registers()[2] & 0xF0);
+    }
+
+    #[test]
+    fn test_audio_pattern() {
+        let program = [0x6030, 0xF03A, 0xA050, 0xF002];
+        let mut cpu = cpu_with_program(&program, Quirks::xochip());
+
+        assert!(cpu.audio_pattern().is_none());
+        assert_eq!(DEFAULT_PITCH, cpu.pitch());
+
+        run_steps(&mut cpu, 4);
+        assert_eq!(0x30, cpu.pitch());
+        assert_eq!(
+            FONT[..AUDIO_PATTERN_SIZE],
+            cpu.audio_pattern().unwrap()[..]
+        );
+    }
+
+    #[test]
+    fn test_unknown_opcode() {
+        let mut cpu = cpu_with_program(&[0x5121], Quirks::default());
+        assert!(cpu.step().is_err());
+    }
+
+    #[test]
+    fn test_self_modifying_code() {
+        // Store "LD V2, 0x33" over the NOP at 0x20A and execute it.
+        let program = [0x6062, 0x6133, 0xA20A, 0xF155, 0x0000, 0x0000];
+        let mut cpu = cpu_with_program(&program, Quirks::default());
+        let mut reference = cpu.clone();
+        reference.set_engine(Engine::Reference);
+
+        assert!(Arc::ptr_eq(&cpu.decoded, &reference.decoded));
+        assert_eq!(4, size_of::<Instruction>());
+
+        run_steps(&mut cpu, 6);
+
+        // Modified machine owns its table, clone keeps the shared one.
+        assert!(!Arc::ptr_eq(&cpu.decoded, &reference.decoded));
+        assert_eq!(Instruction::Sys(0), reference.decoded[0x20A]);
+
+        run_steps(&mut reference, 6);
+
+        assert_eq!(0x33, cpu.registers()[2]);
+        assert_eq!(reference.save_state(), cpu.save_state());
+        assert_eq!(0x6233, cpu.opcode().raw);
+    }
+
+    #[test]
+    fn test_load_address() {
+        // ETI-660 programs start at 0x600 and jump within that page.
+        let mut cpu = Cpu::new();
+        cpu.load_program_at(&[0x16, 0x04, 0x00, 0x00, 0x60, 0x01], 0x600)
+            .unwrap();
+
+        assert_eq!(0x600, cpu.pc());
+        run_steps(&mut cpu, 2);
+        assert_eq!(1, cpu.registers()[0]);
+
+        let mut cpu = Cpu::new();
+        let free = RAM_SIZE - START_ADDR;
+
+        assert!(cpu.load_program(&vec![0; free]).is_ok());
+        assert!(cpu.load_program(&vec![0; free + 2]).is_err());
+        assert!(cpu.load_program_at(&[0x00, 0xE0], RAM_SIZE).is_err());
+        assert!(cpu.load_program_at(&[0x00, 0xE0], RAM_SIZE - 1).is_err());
     }
 }
@@ -0,0 +1,179 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Pre-decoded CPU instructions.
+
+/// CPU instruction with extracted operands.
+///
+/// Registers are stored as indices, `u16` operands as memory addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    /// 00E0 - clear the display.
+    Cls,
+    /// 00EE - return from a subroutine.
+    Ret,
+    /// 0nnn - jump to a machine code routine (ignored).
+    Sys(u16),
+    /// 1nnn - jump to address.
+    Jump(u16),
+    /// 2nnn - call subroutine at address.
+    Call(u16),
+    /// 3xkk - skip next instruction if Vx = kk.
+    SkipEq(u8, u8),
+    /// 4xkk - skip next instruction if Vx != kk.
+    SkipNe(u8, u8),
+    /// 5xy0 - skip next instruction if Vx = Vy.
+    SkipEqReg(u8, u8),
+    /// 6xkk - set Vx = kk.
+    SetByte(u8, u8),
+    /// 7xkk - set Vx = Vx + kk.
+    AddByte(u8, u8),
+    /// 8xy0 - set Vx = Vy.
+    SetReg(u8, u8),
+    /// 8xy1 - set Vx = Vx OR Vy.
+    Or(u8, u8),
+    /// 8xy2 - set Vx = Vx AND Vy.
+    And(u8, u8),
+    /// 8xy3 - set Vx = Vx XOR Vy.
+    Xor(u8, u8),
+    /// 8xy4 - set Vx = Vx + Vy, VF = carry.
+    AddReg(u8, u8),
+    /// 8xy5 - set Vx = Vx - Vy, VF = NOT borrow.
+    Sub(u8, u8),
+    /// 8xy6 - shift right, VF = shifted out bit.
+    Shr(u8, u8),
+    /// 8xy7 - set Vx = Vy - Vx, VF = NOT borrow.
+    Subn(u8, u8),
+    /// 8xyE - shift left, VF = shifted out bit.
+    Shl(u8, u8),
+    /// 9xy0 - skip next instruction if Vx != Vy.
+    SkipNeReg(u8, u8),
+    /// Annn - set I = nnn.
+    SetI(u16),
+    /// Bnnn - jump to nnn plus V0 (or Vx with jumping quirk).
+    JumpOffset(u8, u16),
+    /// Cxkk - set Vx = random byte AND kk.
+    Rnd(u8, u8),
+    /// Dxyn - draw n-byte sprite at (Vx, Vy).
+    Draw(u8, u8, u8),
+    /// Ex9E - skip next instruction if key Vx is pressed.
+    SkipKey(u8),
+    /// ExA1 - skip next instruction if key Vx is not pressed.
+    SkipNotKey(u8),
+    /// F002 - load XO-CHIP audio pattern from I.
+    LoadAudio,
+    /// Fx07 - set Vx = delay timer.
+    GetDelay(u8),
+    /// Fx0A - wait for key press, store key in Vx.
+    WaitKey(u8),
+    /// Fx15 - set delay timer = Vx.
+    SetDelay(u8),
+    /// Fx18 - set sound timer = Vx.
+    SetSound(u8),
+    /// Fx1E - set I = I + Vx.
+    AddI(u8),
+    /// Fx29 - set I = location of font sprite for digit Vx.
+    Font(u8),
+    /// Fx33 - store BCD of Vx at I, I+1 and I+2.
+    Bcd(u8),
+    /// Fx3A - set XO-CHIP audio pitch = Vx.
+    SetPitch(u8),
+    /// Fx55 - store V0 through Vx at I.
+    Store(u8),
+    /// Fx65 - read V0 through Vx from I.
+    Load(u8),
+    /// Unknown opcode.
+    Unknown,
+}
+
+impl Instruction {
+    /// Decode raw opcode.
+    ///
+    /// # Parameters
+    /// - `raw` - given opcode raw bytes.
+    ///
+    /// # Returns
+    /// - Decoded instruction.
+    pub fn decode(raw: u16) -> Self {
+        let class = (raw >> 12) as u8;
+        let addr = raw & 0x0FFF;
+        let x = ((raw >> 8) & 0xF) as u8;
+        let y = ((raw >> 4) & 0xF) as u8;
+        let byte = raw as u8;
+        let nibble = (raw & 0xF) as u8;
+
+        match (class, nibble) {
+            (0x0, _) => match raw {
+                0x00E0 => Self::Cls,
+                0x00EE => Self::Ret,
+                _ => Self::Sys(addr),
+            },
+            (0x1, _) => Self::Jump(addr),
+            (0x2, _) => Self::Call(addr),
+            (0x3, _) => Self::SkipEq(x, byte),
+            (0x4, _) => Self::SkipNe(x, byte),
+            (0x5, 0x0) => Self::SkipEqReg(x, y),
+            (0x6, _) => Self::SetByte(x, byte),
+            (0x7, _) => Self::AddByte(x, byte),
+            (0x8, 0x0) => Self::SetReg(x, y),
+            (0x8, 0x1) => Self::Or(x, y),
+            (0x8, 0x2) => Self::And(x, y),
+            (0x8, 0x3) => Self::Xor(x, y),
+            (0x8, 0x4) => Self::AddReg(x, y),
+            (0x8, 0x5) => Self::Sub(x, y),
+            (0x8, 0x6) => Self::Shr(x, y),
+            (0x8, 0x7) => Self::Subn(x, y),
+            (0x8, 0xE) => Self::Shl(x, y),
+            (0x9, 0x0) => Self::SkipNeReg(x, y),
+            (0xA, _) => Self::SetI(addr),
+            (0xB, _) => Self::JumpOffset(x, addr),
+            (0xC, _) => Self::Rnd(x, byte),
+            (0xD, _) => Self::Draw(x, y, nibble),
+            (0xE, _) => match byte {
+                0x9E => Self::SkipKey(x),
+                0xA1 => Self::SkipNotKey(x),
+                _ => Self::Unknown,
+            },
+            (0xF, _) => match byte {
+                0x02 if x == 0 => Self::LoadAudio,
+                0x07 => Self::GetDelay(x),
+                0x0A => Self::WaitKey(x),
+                0x15 => Self::SetDelay(x),
+                0x18 => Self::SetSound(x),
+                0x1E => Self::AddI(x),
+                0x29 => Self::Font(x),
+                0x33 => Self::Bcd(x),
+                0x3A => Self::SetPitch(x),
+                0x55 => Self::Store(x),
+                0x65 => Self::Load(x),
+                _ => Self::Unknown,
+            },
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(Instruction::Cls, Instruction::decode(0x00E0));
+        assert_eq!(Instruction::Sys(0x123), Instruction::decode(0x0123));
+        assert_eq!(Instruction::Call(0x345), Instruction::decode(0x2345));
+        assert_eq!(Instruction::Shl(0x1, 0x2), Instruction::decode(0x812E));
+        assert_eq!(
+            Instruction::JumpOffset(0x2, 0x234),
+            Instruction::decode(0xB234)
+        );
+        assert_eq!(
+            Instruction::Draw(0xA, 0xB, 0xC),
+            Instruction::decode(0xDABC)
+        );
+        assert_eq!(Instruction::LoadAudio, Instruction::decode(0xF002));
+        assert_eq!(Instruction::Unknown, Instruction::decode(0xF102));
+        assert_eq!(Instruction::Unknown, Instruction::decode(0x5121));
+        assert_eq!(Instruction::Unknown, Instruction::decode(0xE1FF));
+    }
+}
@@ -0,0 +1,332 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Basic-block recompiling execution engine.
+
+use crate::emulator::{
+    EmulatorResult,
+    cpu::{Cpu, RAM_SIZE, decode::Instruction},
+};
+
+/// Maximum number of instructions in single block.
+const MAX_BLOCK_SIZE: usize = 64;
+
+/// Instruction execution engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Engine {
+    /// Decode every fetched opcode (reference implementation).
+    Reference,
+    /// Execute pre-decoded instructions one by one.
+    Interpreter,
+    /// Execute cached basic blocks of pre-decoded instructions.
+    Recompiler,
+}
+
+impl Engine {
+    /// All execution engines.
+    pub const ALL: [Self; 3] =
+        [Self::Reference, Self::Interpreter, Self::Recompiler];
+
+    /// Get engine by name.
+    ///
+    /// # Parameters
+    /// - `name` - given engine name.
+    ///
+    /// # Returns
+    /// - Engine - in case of success.
+    /// - `Err`  - otherwise.
+    pub fn from_name(name: &str) -> EmulatorResult<Self> {
+        match name {
+            "reference" => Ok(Self::Reference),
+            "interpreter" => Ok(Self::Interpreter),
+            "recompiler" => Ok(Self::Recompiler),
+            _ => Err(format!(
+                "unknown engine '{name}' (expected reference, interpreter or \
+                 recompiler)"
+            )),
+        }
+    }
+
+    /// Get engine name.
+    ///
+    /// # Returns
+    /// - Name accepted by `from_name`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Reference => "reference",
+            Self::Interpreter => "interpreter",
+            Self::Recompiler => "recompiler",
+        }
+    }
+}
+
+/// Block instruction with its raw opcode.
+type Op = (Instruction, u16);
+
+/// Instruction sequence in execution order, following unconditional jumps
+/// and calls, ending with conditional or computed control transfer.
+type Block = Box<[Op]>;
+
+/// Compiled blocks cache.
+#[derive(Debug, Clone)]
+pub struct BlockCache {
+    /// Compiled blocks indexed by start address.
+    blocks: Vec<Option<Block>>,
+    /// Whether memory byte may belong to compiled block.
+    covered: Vec<bool>,
+    /// Counter of invalidations, used to drop blocks overwritten while
+    /// being executed.
+    generation: u64,
+}
+
+impl BlockCache {
+    /// Construct new `BlockCache` object.
+    ///
+    /// # Returns
+    /// - New empty `BlockCache` object.
+    pub fn new() -> Self {
+        Self {
+            blocks: vec![None; RAM_SIZE],
+            covered: vec![false; RAM_SIZE],
+            generation: 0,
+        }
+    }
+
+    /// Drop all compiled blocks.
+    pub fn clear(&mut self) {
+        self.blocks.fill(None);
+        self.covered.fill(false);
+        self.generation += 1;
+    }
+
+    /// Drop compiled blocks if written memory byte belongs to any of them.
+    ///
+    /// Blocks may span several memory ranges, so all blocks are dropped.
+    ///
+    /// # Parameters
+    /// - `addr` - given written RAM index.
+    pub fn invalidate(&mut self, addr: usize) {
+        if self.covered[addr] {
+            self.clear();
+        }
+    }
+}
+
+/// Check whether instruction ends basic block.
+///
+/// # Parameters
+/// - `instruction` - given decoded instruction.
+///
+/// # Returns
+/// - `true` - if instruction may change program counter to unknown address,
+///   write memory or end the frame.
+fn ends_block(instruction: Instruction) -> bool {
+    matches!(
+        instruction,
+        Instruction::Ret
+            | Instruction::SkipEq(..)
+            | Instruction::SkipNe(..)
+            | Instruction::SkipEqReg(..)
+            | Instruction::SkipNeReg(..)
+            | Instruction::JumpOffset(..)
+            | Instruction::Draw(..)
+            | Instruction::SkipKey(_)
+            | Instruction::SkipNotKey(_)
+            | Instruction::WaitKey(_)
+            | Instruction::Bcd(_)
+            | Instruction::Store(_)
+            | Instruction::Unknown
+    )
+}
+
+impl Cpu {
+    /// Select instruction execution engine.
+    ///
+    /// # Parameters
+    /// - `engine` - given execution engine.
+    pub fn set_engine(&mut self, engine: Engine) {
+        self.engine = engine;
+        self.blocks = match engine {
+            Engine::Recompiler => Some(Box::new(BlockCache::new())),
+            _ => None,
+        };
+    }
+
+    /// Get selected instruction execution engine.
+    ///
+    /// # Returns
+    /// - Execution engine.
+    pub fn engine(&self) -> Engine {
+        self.engine
+    }
+
+    /// Execute instructions of block starting at program counter.
+    ///
+    /// # Parameters
+    /// - `budget` - given maximum number of instructions to execute.
+    ///
+    /// # Returns
+    /// - Number of successfully executed instructions and execution result.
+    pub(super) fn run_block(
+        &mut self,
+        budget: usize,
+    ) -> (usize, EmulatorResult<()>) {
+        let start = self.pc as usize;
+        let Some((block, generation)) = self.take_block(start) else {
+            // Program counter is out of memory, report fetch error.
+            return (0, self.step_instruction());
+        };
+
+        let ops = &block[..budget.min(block.len())];
+        let mut executed = 0;
+        let mut result = Ok(());
+
+        // Only the last opcode is observable after the block. Unknown
+        // opcodes always end blocks, so they report the right one as well.
+        self.opcode = ops[ops.len() - 1].1;
+
+        for &(instruction, raw) in ops {
+            self.pc += 2;
+
+            if let Err(error) = self.execute(instruction) {
+                self.opcode = raw;
+                result = Err(error);
+                break;
+            }
+
+            executed += 1;
+        }
+
+        self.cycles += (executed + result.is_err() as usize) as u64;
+
+        // Put block back unless memory under any block was overwritten.
+        if let Some(cache) = &mut self.blocks
+            && cache.generation == generation
+        {
+            cache.blocks[start] = Some(block);
+        }
+
+        (executed, result)
+    }
+
+    /// Take compiled block out of cache, compiling it on cache miss.
+    ///
+    /// # Parameters
+    /// - `start` - given block start address.
+    ///
+    /// # Returns
+    /// - Block and cache generation - if block has at least one instruction.
+    /// - `None`                     - if address is out of memory.
+    fn take_block(&mut self, start: usize) -> Option<(Block, u64)> {
+        let cache = self.blocks.as_mut()?;
+
+        if let Some(block) = cache.blocks.get_mut(start)?.take() {
+            return Some((block, cache.generation));
+        }
+
+        let mut addr = start;
+        let mut ops = Vec::new();
+
+        while addr + 1 < RAM_SIZE && ops.len() < MAX_BLOCK_SIZE {
+            let instruction = self.decoded[addr];
+            let raw =
+                u16::from_be_bytes([self.memory[addr], self.memory[addr + 1]]);
+
+            ops.push((instruction, raw));
+            cache.covered[addr..addr + 2].fill(true);
+
+            addr = match instruction {
+                Instruction::Jump(target) | Instruction::Call(target) => {
+                    target as usize
+                }
+                _ if ends_block(instruction) => break,
+                _ => addr + 2,
+            };
+        }
+
+        if ops.is_empty() {
+            return None;
+        }
+
+        Some((ops.into(), cache.generation))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::emulator::{
+        cpu::tests::{cpu_with_program, run_steps},
+        quirks::Quirks,
+    };
+
+    /// Programs exercising all instruction classes.
+    const PROGRAMS: [&[u16]; 6] = [
+        // Arithmetic and logic with loop.
+        &[
+            0x6005, 0x6103, 0x8014, 0x8015, 0x8106, 0x810E, 0x7001, 0x1204,
+        ],
+        // Subroutine calls and skips.
+        &[
+            0x2208, 0x3001, 0x1200, 0x1206, 0x7001, 0x4001, 0x00EE, 0x1200,
+        ],
+        // Sprite drawing with font and BCD.
+        &[
+            0x6007, 0xF029, 0xD015, 0xA300, 0xF033, 0xF265, 0x7001, 0x1200,
+        ],
+        // Self-modifying code.
+        &[
+            0x6062, 0x6133, 0xA210, 0x2210, 0xF155, 0x2210, 0x120C, 0x0000,
+            0x0000, 0x00EE,
+        ],
+        // Random numbers and timers.
+        &[0xC0FF, 0xF015, 0xF107, 0xB200, 0x0000],
+        // Unknown opcode after straight-line code.
+        &[0x6001, 0x6102, 0x5121],
+    ];
+
+    #[test]
+    fn test_engines_match_reference() {
+        for quirks in [Quirks::chip8(), Quirks::schip()] {
+            for program in PROGRAMS {
+                let machine = cpu_with_program(program, quirks);
+                let mut reference = machine.clone();
+                reference.set_engine(Engine::Reference);
+
+                let expected: Vec<_> =
+                    (0..20).map(|_| reference.frame(7)).collect();
+
+                for engine in [Engine::Interpreter, Engine::Recompiler] {
+                    let mut cpu = machine.clone();
+                    cpu.set_engine(engine);
+
+                    let actual: Vec<_> =
+                        (0..20).map(|_| cpu.frame(7)).collect();
+
+                    assert_eq!(expected, actual);
+                    assert_eq!(reference.save_state(), cpu.save_state());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_block_invalidation() {
+        let program = PROGRAMS[3];
+        let mut cpu = cpu_with_program(program, Quirks::default());
+        cpu.set_engine(Engine::Recompiler);
+
+        // Execute the subroutine, overwrite its first instruction and call it
+        // again.
+        run_steps(&mut cpu, 6);
+        assert_eq!(0, cpu.registers()[2]);
+
+        run_steps(&mut cpu, 4);
+        assert_eq!(0x33, cpu.registers()[2]);
+        assert_eq!(Engine::Recompiler, cpu.engine());
+
+        for engine in Engine::ALL {
+            assert_eq!(engine, Engine::from_name(engine.name()).unwrap());
+        }
+    }
+}
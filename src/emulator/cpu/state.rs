@@ -0,0 +1,318 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! CPU save states serialization.
+
+use crate::emulator::{
+    EmulatorResult,
+    cpu::{
+        AUDIO_PATTERN_SIZE, Cpu, DISPLAY_HEIGHT, DISPLAY_WIDTH, RAM_SIZE,
+        REGISTER_COUNT, STACK_SIZE,
+    },
+    quirks::Quirks,
+    random::{Algorithm, Random},
+};
+
+/// Save state file signature.
+const MAGIC: &[u8; 4] = b"C8ST";
+
+/// Save state format version.
+const VERSION: u8 = 1;
+
+/// Save state bytes writer.
+struct Writer {
+    /// Written bytes.
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    /// Write raw bytes.
+    ///
+    /// # Parameters
+    /// - `bytes` - given bytes to write.
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    /// Write single byte.
+    ///
+    /// # Parameters
+    /// - `value` - given byte to write.
+    fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    /// Write big endian 16-bit value.
+    ///
+    /// # Parameters
+    /// - `value` - given value to write.
+    fn u16(&mut self, value: u16) {
+        self.bytes(&value.to_be_bytes());
+    }
+
+    /// Write big endian 64-bit value.
+    ///
+    /// # Parameters
+    /// - `value` - given value to write.
+    fn u64(&mut self, value: u64) {
+        self.bytes(&value.to_be_bytes());
+    }
+}
+
+/// Save state bytes reader.
+struct Reader<'a> {
+    /// Remaining bytes.
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// Read raw bytes.
+    ///
+    /// # Parameters
+    /// - `count` - given number of bytes to read.
+    ///
+    /// # Returns
+    /// - Read bytes - in case of success.
+    /// - `Err`      - if state is truncated.
+    fn bytes(&mut self, count: usize) -> EmulatorResult<&'a [u8]> {
+        if self.bytes.len() < count {
+            return Err("save state is truncated".to_string());
+        }
+
+        let (head, tail) = self.bytes.split_at(count);
+        self.bytes = tail;
+
+        Ok(head)
+    }
+
+    /// Read single byte.
+    ///
+    /// # Returns
+    /// - Read byte - in case of success.
+    /// - `Err`     - if state is truncated.
+    fn u8(&mut self) -> EmulatorResult<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    /// Read big endian 16-bit value.
+    ///
+    /// # Returns
+    /// - Read value - in case of success.
+    /// - `Err`      - if state is truncated.
+    fn u16(&mut self) -> EmulatorResult<u16> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    /// Read big endian 64-bit value.
+    ///
+    /// # Returns
+    /// - Read value - in case of success.
+    /// - `Err`      - if state is truncated.
+    fn u64(&mut self) -> EmulatorResult<u64> {
+        let mut value = [0u8; 8];
+        value.copy_from_slice(self.bytes(8)?);
+
+        Ok(u64::from_be_bytes(value))
+    }
+}
+
+impl Cpu {
+    /// Serialize complete machine state.
+    ///
+    /// # Returns
+    /// - Save state bytes.
+    pub fn save_state(&self) -> Vec<u8> {
+        let mut writer = Writer { bytes: Vec::new() };
+
+        writer.bytes(MAGIC);
+        writer.u8(VERSION);
+        writer.bytes(&self.memory);
+        writer.bytes(&self.registers);
+        writer.u16(self.register_i);
+        writer.u16(self.pc);
+        writer.u8(self.sp);
+        self.stack.iter().for_each(|&addr| writer.u16(addr));
+        writer.u8(self.dt);
+        writer.u8(self.st);
+        writer.u16(self.opcode);
+
+        for pixels in self.display.chunks(8) {
+            let byte = pixels
+                .iter()
+                .fold(0u8, |byte, &pixel| (byte << 1) | pixel as u8);
+            writer.u8(byte);
+        }
+
+        let keypad = self
+            .keypad
+            .iter()
+            .rev()
+            .fold(0u16, |keys, &pressed| (keys << 1) | pressed as u16);
+        writer.u16(keypad);
+
+        writer.u8(encode_quirks(&self.quirks));
+        writer.u8(match self.random.algorithm() {
+            Algorithm::SplitMix => 0,
+            Algorithm::Vip => 1,
+        });
+        writer.u64(self.random.seed());
+        writer.u64(self.random.state());
+        writer.u64(self.cycles);
+        writer.u64(self.frame_cycles as u64);
+        writer.u8(self.vblank_wait as u8);
+        writer.u8(self.audio_pattern.is_some() as u8);
+        writer.bytes(&self.audio_pattern.unwrap_or_default());
+        writer.u8(self.pitch);
+
+        writer.bytes
+    }
+
+    /// Restore complete machine state.
+    ///
+    /// # Parameters
+    /// - `data` - given save state bytes.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - if save state is malformed (CPU is left unchanged).
+    pub fn load_state(&mut self, data: &[u8]) -> EmulatorResult<()> {
+        let mut reader = Reader { bytes: data };
+
+        if reader.bytes(MAGIC.len())? != MAGIC {
+            return Err("not a save state".to_string());
+        }
+
+        let version = reader.u8()?;
+
+        if version != VERSION {
+            return Err(format!("unsupported save state version {version}"));
+        }
+
+        let mut cpu = self.clone();
+
+        cpu.memory.copy_from_slice(reader.bytes(RAM_SIZE)?);
+        cpu.decode_range(0, RAM_SIZE);
+
+        if let Some(blocks) = &mut cpu.blocks {
+            blocks.clear();
+        }
+        cpu.registers.copy_from_slice(reader.bytes(REGISTER_COUNT)?);
+        cpu.register_i = reader.u16()?;
+        cpu.pc = reader.u16()?;
+        cpu.sp = reader.u8()?;
+
+        for addr in cpu.stack.iter_mut() {
+            *addr = reader.u16()?;
+        }
+
+        cpu.dt = reader.u8()?;
+        cpu.st = reader.u8()?;
+        cpu.opcode = reader.u16()?;
+
+        let display = reader.bytes(DISPLAY_WIDTH * DISPLAY_HEIGHT / 8)?;
+
+        for (i, pixel) in cpu.display.iter_mut().enumerate() {
+            *pixel = display[i / 8] & (0x80 >> (i % 8)) != 0;
+        }
+
+        let keypad = reader.u16()?;
+
+        for (key, pressed) in cpu.keypad.iter_mut().enumerate() {
+            *pressed = keypad & (1 << key) != 0;
+        }
+
+        cpu.quirks = decode_quirks(reader.u8()?);
+
+        let algorithm = match reader.u8()? {
+            0 => Algorithm::SplitMix,
+            1 => Algorithm::Vip,
+            other => return Err(format!("unknown random algorithm {other}")),
+        };
+        let seed = reader.u64()?;
+        let state = reader.u64()?;
+
+        cpu.random = Random::from_state(algorithm, seed, state);
+        cpu.cycles = reader.u64()?;
+        cpu.frame_cycles = reader.u64()? as usize;
+        cpu.vblank_wait = reader.u8()? != 0;
+
+        let loaded = reader.u8()? != 0;
+        let mut pattern = [0u8; AUDIO_PATTERN_SIZE];
+
+        pattern.copy_from_slice(reader.bytes(AUDIO_PATTERN_SIZE)?);
+        cpu.audio_pattern = loaded.then_some(pattern);
+        cpu.pitch = reader.u8()?;
+
+        if cpu.sp as usize > STACK_SIZE {
+            return Err("save state stack pointer is out of range".to_string());
+        }
+
+        *self = cpu;
+
+        Ok(())
+    }
+}
+
+/// Pack quirks into bit flags.
+///
+/// # Parameters
+/// - `quirks` - given quirks.
+///
+/// # Returns
+/// - Quirks bit flags.
+fn encode_quirks(quirks: &Quirks) -> u8 {
+    quirks
+        .flags()
+        .iter()
+        .enumerate()
+        .fold(0, |flags, (i, &enabled)| flags | (enabled as u8) << i)
+}
+
+/// Unpack quirks from bit flags.
+///
+/// # Parameters
+/// - `flags` - given quirks bit flags.
+///
+/// # Returns
+/// - Unpacked quirks.
+fn decode_quirks(flags: u8) -> Quirks {
+    Quirks::from_flags(std::array::from_fn(|i| flags & (1 << i) != 0))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::emulator::cpu::tests::cpu_with_program;
+
+    #[test]
+    fn test_state_roundtrip() {
+        let program = [0xC0FF, 0x6105, 0xF129, 0xD015, 0x2200];
+        let mut cpu = cpu_with_program(&program, Quirks::xochip());
+
+        cpu.set_random(Random::new(Algorithm::SplitMix, 99));
+        cpu.set_key(0xA, true);
+        cpu.frame(5).unwrap();
+
+        let state = cpu.save_state();
+        let mut restored = Cpu::new();
+        restored.load_state(&state).unwrap();
+
+        assert_eq!(state, restored.save_state());
+        assert_eq!(Quirks::xochip(), *restored.quirks());
+        assert_eq!(cpu.random(), restored.random());
+
+        cpu.frame(5).unwrap();
+        restored.frame(5).unwrap();
+        assert_eq!(cpu.save_state(), restored.save_state());
+    }
+
+    #[test]
+    fn test_state_malformed() {
+        let mut cpu = Cpu::new();
+        let state = cpu.save_state();
+
+        assert!(cpu.load_state(b"C8XX").is_err());
+        assert!(cpu.load_state(&state[..state.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,266 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! COSMAC VIP instruction timing model.
+//!
+//! Costs are measured in RCA 1802 machine cycles (8 clock periods of the
+//! 1.76 MHz VIP clock) spent by the original CHIP-8 interpreter.
+
+use crate::emulator::{
+    EmulatorResult,
+    cpu::{Cpu, decode::Instruction},
+};
+
+/// Machine cycles per 60 Hz frame (262 display lines of 14 cycles).
+pub const VIP_FRAME_CYCLES: usize = 3668;
+
+/// Machine cycles per frame taken by the display interrupt routine and
+/// display DMA (128 visible lines of 14 cycles plus routine entry and exit).
+pub const VIP_INTERRUPT_CYCLES: usize = 1832;
+
+/// Machine cycles per frame available to the interpreter.
+pub const VIP_AVAILABLE_CYCLES: usize = VIP_FRAME_CYCLES - VIP_INTERRUPT_CYCLES;
+
+/// Machine cycles of fetching and dispatching single instruction.
+const FETCH_CYCLES: usize = 40;
+
+/// Additional machine cycles of taken skip.
+const SKIP_CYCLES: usize = 4;
+
+/// Additional machine cycles of address arithmetic crossing memory page.
+const PAGE_CYCLES: usize = 4;
+
+/// Instruction timing model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Timing {
+    /// Execute fixed number of instructions per frame.
+    Instructions,
+    /// Charge each instruction its COSMAC VIP machine cycles cost.
+    Vip,
+}
+
+impl Timing {
+    /// Get timing model by name.
+    ///
+    /// # Parameters
+    /// - `name` - given timing model name.
+    ///
+    /// # Returns
+    /// - Timing model - in case of success.
+    /// - `Err`        - otherwise.
+    pub fn from_name(name: &str) -> EmulatorResult<Self> {
+        match name {
+            "instructions" => Ok(Self::Instructions),
+            "vip" => Ok(Self::Vip),
+            _ => Err(format!(
+                "unknown timing '{name}' (expected instructions or vip)"
+            )),
+        }
+    }
+
+    /// Get timing model name.
+    ///
+    /// # Returns
+    /// - Name accepted by `from_name`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Instructions => "instructions",
+            Self::Vip => "vip",
+        }
+    }
+}
+
+impl Cpu {
+    /// Select instruction timing model.
+    ///
+    /// # Parameters
+    /// - `timing` - given timing model.
+    pub fn set_timing(&mut self, timing: Timing) {
+        self.timing = timing;
+        self.frame_cycles = 0;
+    }
+
+    /// Get selected instruction timing model.
+    ///
+    /// # Returns
+    /// - Timing model.
+    pub fn timing(&self) -> Timing {
+        self.timing
+    }
+
+    /// Get frame length in timing model units.
+    ///
+    /// # Parameters
+    /// - `speed` - given number of instructions per frame.
+    ///
+    /// # Returns
+    /// - Instructions or machine cycles per frame.
+    pub(super) fn frame_length(&self, speed: usize) -> usize {
+        match self.timing {
+            Timing::Instructions => speed,
+            Timing::Vip => VIP_AVAILABLE_CYCLES,
+        }
+    }
+
+    /// Get cost of instruction at program counter in timing model units.
+    ///
+    /// Must be called before the instruction is executed.
+    ///
+    /// # Returns
+    /// - Instruction cost.
+    #[inline(always)]
+    pub(super) fn instruction_cost(&self) -> usize {
+        match self.timing {
+            Timing::Instructions => 1,
+            Timing::Vip => self
+                .decoded
+                .get(self.pc as usize)
+                .map_or(0, |&instruction| self.vip_cycles(instruction)),
+        }
+    }
+
+    /// Get COSMAC VIP machine cycles of instruction in current state.
+    ///
+    /// # Parameters
+    /// - `instruction` - given instruction about to be executed.
+    ///
+    /// # Returns
+    /// - Machine cycles including fetch.
+    fn vip_cycles(&self, instruction: Instruction) -> usize {
+        let reg = |reg: u8| self.registers[reg as usize];
+        let skip = |taken: bool| taken as usize * SKIP_CYCLES;
+        let key = |reg_x: u8| self.keypad[(reg(reg_x) & 0xF) as usize];
+
+        let cycles = match instruction {
+            Instruction::Cls => 3078,
+            Instruction::Ret => 10,
+            Instruction::Sys(_) => 26,
+            Instruction::Jump(_) => 12,
+            Instruction::Call(_) => 26,
+            Instruction::SkipEq(x, byte) => 10 + skip(reg(x) == byte),
+            Instruction::SkipNe(x, byte) => 10 + skip(reg(x) != byte),
+            Instruction::SkipEqReg(x, y) => 14 + skip(reg(x) == reg(y)),
+            Instruction::SkipNeReg(x, y) => 14 + skip(reg(x) != reg(y)),
+            Instruction::SetByte(..) => 6,
+            Instruction::AddByte(..) => 10,
+            Instruction::SetReg(..) => 12,
+            Instruction::Or(..)
+            | Instruction::And(..)
+            | Instruction::Xor(..)
+            | Instruction::AddReg(..)
+            | Instruction::Sub(..)
+            | Instruction::Shr(..)
+            | Instruction::Subn(..)
+            | Instruction::Shl(..) => 44,
+            Instruction::SetI(_) => 12,
+            Instruction::JumpOffset(x, addr) => {
+                let reg_x = if self.quirks.jumping { x } else { 0 };
+                22 + page_crossing(addr, reg(reg_x))
+            }
+            Instruction::Rnd(..) => 36,
+            Instruction::Draw(x, _, height) => draw_cycles(reg(x), height),
+            Instruction::SkipKey(x) => 14 + skip(key(x)),
+            Instruction::SkipNotKey(x) => 14 + skip(!key(x)),
+            Instruction::GetDelay(_)
+            | Instruction::WaitKey(_)
+            | Instruction::SetDelay(_)
+            | Instruction::SetSound(_)
+            | Instruction::SetPitch(_) => 10,
+            Instruction::AddI(x) => 16 + page_crossing(self.register_i, reg(x)),
+            Instruction::Font(_) => 16,
+            Instruction::Bcd(x) => {
+                let value = reg(x);
+                let digits = value / 100 + value / 10 % 10 + value % 10;
+
+                80 + 16 * digits as usize
+            }
+            Instruction::Store(x) | Instruction::Load(x) => {
+                14 + 14 * (x as usize + 1)
+            }
+            Instruction::LoadAudio => 14 + 14 * 16,
+            Instruction::Unknown => 0,
+        };
+
+        FETCH_CYCLES + cycles
+    }
+}
+
+/// Get additional machine cycles of adding byte to address.
+///
+/// # Parameters
+/// - `addr`  - given address.
+/// - `value` - given added byte.
+///
+/// # Returns
+/// - Page crossing penalty.
+fn page_crossing(addr: u16, value: u8) -> usize {
+    ((addr & 0xFF) + value as u16 > 0xFF) as usize * PAGE_CYCLES
+}
+
+/// Get machine cycles of sprite drawing.
+///
+/// Sprite rows not aligned to display bytes are shifted bit by bit and
+/// combined with two display bytes instead of one.
+///
+/// # Parameters
+/// - `x`      - given sprite X coordinate.
+/// - `height` - given sprite height in bytes.
+///
+/// # Returns
+/// - Machine cycles excluding fetch.
+fn draw_cycles(x: u8, height: u8) -> usize {
+    let shift = x as usize % 8;
+    let bytes = if shift == 0 { 1 } else { 2 };
+    let row = 24 + 8 * shift + 16 * bytes;
+
+    26 + height as usize * row
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::emulator::{cpu::tests::cpu_with_program, quirks::Quirks};
+
+    #[test]
+    fn test_vip_cycles() {
+        let mut cpu = cpu_with_program(&[0x6012], Quirks::chip8());
+
+        assert_eq!(46, cpu.vip_cycles(Instruction::SetByte(0, 0x12)));
+        assert_eq!(50, cpu.vip_cycles(Instruction::SkipEq(0, 1)));
+        assert_eq!(54, cpu.vip_cycles(Instruction::SkipNe(0, 1)));
+
+        let aligned = cpu.vip_cycles(Instruction::Draw(0, 0, 5));
+        cpu.step().unwrap();
+        let unaligned = cpu.vip_cycles(Instruction::Draw(0, 0, 5));
+
+        assert_eq!(40 + 26 + 5 * 40, aligned);
+        assert!(unaligned > aligned);
+        assert!(
+            cpu.vip_cycles(Instruction::Draw(0, 0, 15))
+                > cpu.vip_cycles(Instruction::Draw(0, 0, 5))
+        );
+    }
+
+    #[test]
+    fn test_vip_frame() {
+        // Clear the screen in a loop, clearing takes longer than a frame.
+        let mut cpu = cpu_with_program(&[0x00E0, 0x1200], Quirks::schip());
+        cpu.set_timing(Timing::Vip);
+
+        // Leftover cycles of long instructions are carried to next frames.
+        let expected = [1, 3, 4, 5];
+
+        for cycles in expected {
+            cpu.frame(1).unwrap();
+            assert_eq!(cycles, cpu.cycles());
+        }
+
+        let mut cpu = cpu_with_program(&[0x7001, 0x1200], Quirks::schip());
+        cpu.set_timing(Timing::Vip);
+        cpu.frame(1).unwrap();
+
+        // Each loop iteration takes 50 + 52 machine cycles.
+        assert_eq!(VIP_AVAILABLE_CYCLES / 102, cpu.registers()[0] as usize);
+        assert_eq!(Timing::Vip, Timing::from_name("vip").unwrap());
+    }
+}
@@ -3,7 +3,7 @@
 
 //! Emulator builtin disassembler main module.
 
-use crate::emulator::{EmulatorResult, cpu::START_ADDR, opcode::OpCode};
+use crate::emulator::opcode::OpCode;
 
 /// Opcode decodable trait.
 pub trait Decodable {
@@ -14,28 +14,52 @@ pub trait Decodable {
     fn decode(&self) -> String;
 }
 
-/// Display assembly mnemonics of specified binary file.
+/// Split program data into instructions.
 ///
 /// # Parameters
 /// - `program_data` - given program data bytes.
+/// - `load_addr`    - given program load address.
 ///
 /// # Returns
-/// - `Ok`  - in case of success.
-/// - `Err` - otherwise.
-pub fn disassemble(program_data: &[u8]) -> EmulatorResult<()> {
-    // Convert file bytes to vector of u16 big endian bytes.
-    let mut buffer: Vec<u16> = Vec::with_capacity(program_data.len() / 2);
-
-    for chunk in program_data.chunks_exact(2) {
-        buffer.push(u16::from_be_bytes([chunk[0], chunk[1]]));
-    }
+/// - Iterator over instruction address and opcode pairs.
+pub fn listing(
+    program_data: &[u8],
+    load_addr: usize,
+) -> impl Iterator<Item = (usize, OpCode)> {
+    program_data
+        .chunks_exact(2)
+        .enumerate()
+        .map(move |(i, chunk)| {
+            let bytes = u16::from_be_bytes([chunk[0], chunk[1]]);
+            (load_addr + i * 2, OpCode::new(bytes))
+        })
+}
+
+/// Get assembly mnemonics listing of specified binary file.
+///
+/// # Parameters
+/// - `program_data` - given program data bytes.
+/// - `load_addr`    - given program load address.
+///
+/// # Returns
+/// - Listing text, one instruction per line.
+pub fn disassemble(program_data: &[u8], load_addr: usize) -> String {
+    let mut text = String::new();
 
-    for (i, bytes) in buffer.iter().enumerate() {
-        let opcode = OpCode::new(*bytes).decode();
-        let addr = START_ADDR + i * 2;
+    for (addr, opcode) in listing(program_data, load_addr) {
+        let bytes = opcode.raw;
+        let opcode = opcode.decode();
+
+        text += &format!("<{addr:#05X}>  |{bytes:04X}|  {opcode}\n");
+    }
 
-        println!("<{addr:#05X}>  |{bytes:04X}|  {opcode}");
+    // Odd length programs end with single data byte.
+    if let [.., byte] = program_data
+        && !program_data.len().is_multiple_of(2)
+    {
+        let addr = load_addr + program_data.len() - 1;
+        text += &format!("<{addr:#05X}>  |{byte:02X}  |  DB {byte:#04X}\n");
     }
 
-    Ok(())
+    text
 }
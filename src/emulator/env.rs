@@ -0,0 +1,418 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Reinforcement-learning environment over deterministic machine.
+
+use crate::emulator::{
+    EmulatorResult,
+    cpu::{
+        Cpu, DISPLAY_HEIGHT, DISPLAY_WIDTH, KEY_COUNT, RAM_SIZE, REGISTER_COUNT,
+    },
+    quirks::Quirks,
+    random::{Algorithm, Random},
+    tas::Keys,
+};
+use std::sync::Arc;
+
+/// Display pixels in row-major order.
+pub type Observation = [bool; DISPLAY_WIDTH * DISPLAY_HEIGHT];
+
+/// Reward function of machine states before and after step.
+///
+/// Functions are shared between cloned environments and may be called
+/// from worker threads.
+pub type RewardFn = Arc<dyn Fn(&Cpu, &Cpu) -> f64 + Send + Sync>;
+
+/// Termination function of machine state after step.
+pub type DoneFn = Arc<dyn Fn(&Cpu) -> bool + Send + Sync>;
+
+/// Result of single environment step.
+#[derive(Debug, Clone, Copy)]
+pub struct Step {
+    /// Display after the step.
+    pub observation: Observation,
+    /// Reward collected during the step.
+    pub reward: f64,
+    /// Whether episode is finished.
+    pub done: bool,
+}
+
+/// Saved episode state.
+#[derive(Clone)]
+pub struct Snapshot {
+    /// Machine state.
+    cpu: Cpu,
+    /// Number of executed frames.
+    frame: u64,
+    /// Whether episode is finished.
+    done: bool,
+}
+
+/// Environment stepping machine frame by frame with keypad actions.
+///
+/// Episodes are deterministic: the same ROM, seed and action sequence
+/// always produce the same observations and rewards.
+#[derive(Clone)]
+pub struct Environment {
+    /// Current machine.
+    cpu: Cpu,
+    /// Interpreter behavior quirks.
+    quirks: Quirks,
+    /// Random number generator algorithm.
+    algorithm: Algorithm,
+    /// Number of instructions executed per frame.
+    speed: usize,
+    /// Number of executed frames in current episode.
+    frame: u64,
+    /// Maximum number of frames per episode.
+    max_frames: Option<u64>,
+    /// Whether episode is finished.
+    done: bool,
+    /// User-supplied reward function.
+    reward: Option<RewardFn>,
+    /// User-supplied termination function.
+    terminated: Option<DoneFn>,
+}
+
+impl Environment {
+    /// Construct new `Environment` object.
+    ///
+    /// # Parameters
+    /// - `quirks`    - given interpreter behavior quirks.
+    /// - `algorithm` - given random number generator algorithm.
+    /// - `speed`     - given number of instructions per frame.
+    ///
+    /// # Returns
+    /// - New `Environment` object.
+    pub fn new(quirks: Quirks, algorithm: Algorithm, speed: usize) -> Self {
+        Self {
+            cpu: Cpu::with_quirks(quirks),
+            quirks,
+            algorithm,
+            speed,
+            frame: 0,
+            max_frames: None,
+            done: false,
+            reward: None,
+            terminated: None,
+        }
+    }
+
+    /// Set reward function.
+    ///
+    /// # Parameters
+    /// - `reward` - given function of machine states before and after step.
+    pub fn set_reward(
+        &mut self,
+        reward: impl Fn(&Cpu, &Cpu) -> f64 + Send + Sync + 'static,
+    ) {
+        self.reward = Some(Arc::new(reward));
+    }
+
+    /// Set termination function.
+    ///
+    /// # Parameters
+    /// - `terminated` - given function of machine state after step.
+    pub fn set_done(
+        &mut self,
+        terminated: impl Fn(&Cpu) -> bool + Send + Sync + 'static,
+    ) {
+        self.terminated = Some(Arc::new(terminated));
+    }
+
+    /// Set episode length limit.
+    ///
+    /// # Parameters
+    /// - `max_frames` - given maximum number of frames per episode.
+    pub fn set_max_frames(&mut self, max_frames: Option<u64>) {
+        self.max_frames = max_frames;
+    }
+
+    /// Start new episode.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    /// - `seed`         - given random number generator seed.
+    ///
+    /// # Returns
+    /// - Initial observation - in case of success.
+    /// - `Err`               - if program does not fit in memory.
+    pub fn reset(
+        &mut self,
+        program_data: &[u8],
+        seed: u64,
+    ) -> EmulatorResult<Observation> {
+        let mut cpu = Cpu::with_quirks(self.quirks);
+
+        cpu.set_random(Random::new(self.algorithm, seed));
+        cpu.load_program(program_data)?;
+        self.cpu = cpu;
+        self.frame = 0;
+        self.done = false;
+
+        Ok(self.observation())
+    }
+
+    /// Execute frames holding specified keys.
+    ///
+    /// # Parameters
+    /// - `action` - given keys held during the step.
+    /// - `frames` - given number of frames to execute.
+    ///
+    /// # Returns
+    /// - Step result - in case of success.
+    /// - `Err`       - in case of execution error.
+    pub fn step(&mut self, action: Keys, frames: u64) -> EmulatorResult<Step> {
+        if self.done {
+            return Err("Episode is finished, reset is required".to_string());
+        }
+
+        let before = self.reward.as_ref().map(|_| self.cpu.clone());
+
+        for key in 0..KEY_COUNT {
+            self.cpu.set_key(key as u8, action & (1 << key) != 0);
+        }
+
+        for _ in 0..frames {
+            self.cpu.frame(self.speed)?;
+            self.frame += 1;
+
+            if self.finished() {
+                self.done = true;
+                break;
+            }
+        }
+
+        let reward = match (&self.reward, &before) {
+            (Some(reward), Some(before)) => reward(before, &self.cpu),
+            _ => 0.0,
+        };
+
+        Ok(Step {
+            observation: self.observation(),
+            reward,
+            done: self.done,
+        })
+    }
+
+    /// Get current display.
+    ///
+    /// # Returns
+    /// - Display pixels in row-major order.
+    pub fn observation(&self) -> Observation {
+        *self.cpu.display()
+    }
+
+    /// Get current machine.
+    ///
+    /// # Returns
+    /// - Machine state after the last step.
+    pub fn cpu(&self) -> &Cpu {
+        &self.cpu
+    }
+
+    /// Get number of executed frames in current episode.
+    ///
+    /// # Returns
+    /// - Frame number.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Check whether episode is finished.
+    ///
+    /// # Returns
+    /// - `true` - if reset is required before next step.
+    pub fn done(&self) -> bool {
+        self.done
+    }
+
+    /// Save current episode state.
+    ///
+    /// # Returns
+    /// - Episode snapshot.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            cpu: self.cpu.clone(),
+            frame: self.frame,
+            done: self.done,
+        }
+    }
+
+    /// Restore episode state.
+    ///
+    /// # Parameters
+    /// - `snapshot` - given episode snapshot.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.cpu = snapshot.cpu.clone();
+        self.frame = snapshot.frame;
+        self.done = snapshot.done;
+    }
+
+    /// Check termination conditions after frame.
+    ///
+    /// # Returns
+    /// - `true` - if episode should finish.
+    fn finished(&self) -> bool {
+        let limit = self.max_frames.is_some_and(|max| self.frame >= max);
+        let terminated = self.terminated.as_ref().is_some_and(|f| f(&self.cpu));
+
+        limit || terminated
+    }
+}
+
+/// Reward of memory byte increase, e.g. score counter.
+///
+/// # Parameters
+/// - `address` - given memory address.
+///
+/// # Returns
+/// - Reward function - in case of success.
+/// - `Err`           - if address is out of memory.
+pub fn memory_delta(
+    address: u16,
+) -> EmulatorResult<impl Fn(&Cpu, &Cpu) -> f64> {
+    let address = address as usize;
+
+    if address >= RAM_SIZE {
+        return Err(format!("memory address out of range: {address:#05X}"));
+    }
+
+    Ok(move |before: &Cpu, after: &Cpu| {
+        after.memory()[address] as f64 - before.memory()[address] as f64
+    })
+}
+
+/// Reward of register increase.
+///
+/// # Parameters
+/// - `register` - given register index (0-F).
+///
+/// # Returns
+/// - Reward function - in case of success.
+/// - `Err`           - if register index is out of range.
+pub fn register_delta(
+    register: u8,
+) -> EmulatorResult<impl Fn(&Cpu, &Cpu) -> f64> {
+    let register = register as usize;
+
+    if register >= REGISTER_COUNT {
+        return Err(format!("register index out of range: {register:#X}"));
+    }
+
+    Ok(move |before: &Cpu, after: &Cpu| {
+        after.registers()[register] as f64 - before.registers()[register] as f64
+    })
+}
+
+/// Check whether machine is stuck in jump to itself, common game over loop.
+///
+/// # Parameters
+/// - `cpu` - given machine state.
+///
+/// # Returns
+/// - `true` - if next instruction jumps to its own address.
+pub fn halted(cpu: &Cpu) -> bool {
+    let pc = cpu.pc() as usize;
+
+    if pc + 1 >= RAM_SIZE {
+        return false;
+    }
+
+    let instruction =
+        u16::from_be_bytes([cpu.memory()[pc], cpu.memory()[pc + 1]]);
+    instruction == 0x1000 | pc as u16
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::emulator::cpu::tests::cpu_with_program;
+
+    /// Add 1 to V0 while key 5 is held, store V0 at 0x300, loop.
+    const PROGRAM: [u8; 16] = [
+        0x61, 0x05, 0xE1, 0xA1, 0x70, 0x01, 0xA3, 0x00, 0xF0, 0x55, 0x00, 0xE0,
+        0x12, 0x00, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn test_step_reward() {
+        let mut env =
+            Environment::new(Quirks::default(), Algorithm::SplitMix, 8);
+        env.set_reward(memory_delta(0x300).unwrap());
+        env.set_max_frames(Some(5));
+
+        let observation = env.reset(&PROGRAM, 1).unwrap();
+        assert!(observation.iter().all(|&pixel| !pixel));
+
+        let step = env.step(0, 1).unwrap();
+        assert_eq!(0.0, step.reward);
+        assert!(!step.done);
+
+        let step = env.step(1 << 5, 2).unwrap();
+        assert!(step.reward > 0.0);
+        assert_eq!(step.reward, env.cpu().memory()[0x300] as f64);
+
+        let step = env.step(1 << 5, 10).unwrap();
+        assert!(step.done);
+        assert_eq!(5, env.frame());
+        assert!(env.step(0, 1).is_err());
+    }
+
+    #[test]
+    fn test_register_delta() {
+        let before = cpu_with_program(&[0x6003], Quirks::default());
+        let mut after = before.clone();
+        after.step().unwrap();
+
+        assert_eq!(3.0, register_delta(0).unwrap()(&before, &after));
+        assert_eq!(0.0, register_delta(0xF).unwrap()(&before, &after));
+        assert!(register_delta(16).is_err());
+        assert!(memory_delta(RAM_SIZE as u16).is_err());
+    }
+
+    #[test]
+    fn test_snapshot_determinism() {
+        let mut env =
+            Environment::new(Quirks::default(), Algorithm::SplitMix, 8);
+        env.set_reward(register_delta(0).unwrap());
+        env.reset(&PROGRAM, 7).unwrap();
+        env.step(1 << 5, 3).unwrap();
+
+        let snapshot = env.snapshot();
+        let mut fork = env.clone();
+        let first = env.step(1 << 5, 4).unwrap();
+
+        env.restore(&snapshot);
+        let second = env.step(1 << 5, 4).unwrap();
+        let third = fork.step(1 << 5, 4).unwrap();
+
+        assert_eq!(first.reward, second.reward);
+        assert_eq!(first.reward, third.reward);
+        assert_eq!(env.cpu().registers(), fork.cpu().registers());
+    }
+
+    #[test]
+    fn test_send() {
+        let mut env =
+            Environment::new(Quirks::default(), Algorithm::SplitMix, 8);
+        env.set_reward(register_delta(0).unwrap());
+        env.set_done(halted);
+        env.reset(&PROGRAM, 3).unwrap();
+
+        let worker = std::thread::spawn(move || env.step(1 << 5, 2));
+        assert!(worker.join().unwrap().is_ok());
+    }
+
+    #[test]
+    fn test_halted() {
+        let mut env =
+            Environment::new(Quirks::default(), Algorithm::SplitMix, 8);
+        env.set_done(halted);
+
+        env.reset(&[0x60, 0x01, 0x12, 0x02], 0).unwrap();
+        assert!(env.step(0, 100).unwrap().done);
+        assert_eq!(1, env.frame());
+        assert!(env.reset(&[0; RAM_SIZE], 0).is_err());
+    }
+}
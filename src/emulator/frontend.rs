@@ -0,0 +1,320 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Frontend abstraction for display, audio and input backends.
+
+use crate::emulator::{
+    EmulatorResult,
+    cpu::{Cpu, KEY_COUNT},
+    headless::KeyScript,
+    sound::{self, Synth},
+};
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Duration of single 60 Hz frame.
+pub const FRAME_DURATION: Duration = Duration::from_micros(16_667);
+
+/// Machine state presented at the end of each frame.
+#[derive(Debug, Clone, Copy)]
+pub struct Frame<'a> {
+    /// Frame number starting from zero.
+    pub number: u64,
+    /// Display pixels in row-major order.
+    pub display: &'a [bool],
+    /// Number of executed instructions.
+    pub cycles: u64,
+    /// Program counter.
+    pub pc: u16,
+    /// Delay timer.
+    pub dt: u8,
+    /// Sound timer.
+    pub st: u8,
+    /// Measured instructions per second.
+    pub ips: u64,
+}
+
+/// Host display, audio and input backend driven by emulation loop.
+///
+/// Each frame the loop polls keys, checks for quit request, executes
+/// instructions, updates tone and presents the display.
+pub trait Frontend {
+    /// Update keypad state from host input.
+    ///
+    /// # Parameters
+    /// - `keypad` - given keypad state to update.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn poll_keys(
+        &mut self,
+        keypad: &mut [bool; KEY_COUNT],
+    ) -> EmulatorResult<()>;
+
+    /// Check whether user requested to stop emulation.
+    ///
+    /// # Returns
+    /// - `true` - if emulation loop should stop.
+    fn quit_requested(&self) -> bool;
+
+    /// Check whether user requested screenshot, clearing the request.
+    ///
+    /// # Returns
+    /// - `true` - if screenshot of the current frame should be saved.
+    fn screenshot_requested(&mut self) -> bool {
+        false
+    }
+
+    /// Present frame to the user.
+    ///
+    /// # Parameters
+    /// - `frame` - given finished frame.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn present(&mut self, frame: &Frame) -> EmulatorResult<()>;
+
+    /// Start or stop the buzzer tone.
+    ///
+    /// # Parameters
+    /// - `playing` - given whether sound timer is active.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn set_tone(&mut self, playing: bool) -> EmulatorResult<()> {
+        let _ = playing;
+        Ok(())
+    }
+
+    /// Queue generated audio samples.
+    ///
+    /// # Parameters
+    /// - `samples` - given signed 16-bit mono PCM samples.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn push_samples(&mut self, samples: &[i16]) -> EmulatorResult<()> {
+        let _ = samples;
+        Ok(())
+    }
+
+    /// Get options of PCM audio accepted by `push_samples`.
+    ///
+    /// # Returns
+    /// - Sound output options - if frontend accepts samples.
+    /// - `None`               - if frontend only uses `set_tone`.
+    fn audio(&self) -> Option<&sound::Options> {
+        None
+    }
+
+    /// Check whether frames should be paced to 60 Hz.
+    ///
+    /// # Returns
+    /// - `true` - if loop should sleep until the end of each frame.
+    fn realtime(&self) -> bool {
+        true
+    }
+}
+
+/// Frontend without display and audio driven by key script.
+#[derive(Debug, Clone, Default)]
+pub struct Null {
+    /// Number of frames to run.
+    frames: u64,
+    /// Number of presented frames.
+    frame: u64,
+    /// Scripted keypad input.
+    script: KeyScript,
+}
+
+impl Null {
+    /// Construct new `Null` object.
+    ///
+    /// # Parameters
+    /// - `frames` - given number of frames to run.
+    /// - `script` - given key script.
+    ///
+    /// # Returns
+    /// - New `Null` object.
+    pub fn new(frames: u64, script: KeyScript) -> Self {
+        Self {
+            frames,
+            frame: 0,
+            script,
+        }
+    }
+}
+
+impl Frontend for Null {
+    fn poll_keys(
+        &mut self,
+        keypad: &mut [bool; KEY_COUNT],
+    ) -> EmulatorResult<()> {
+        self.script.apply(self.frame, keypad);
+        Ok(())
+    }
+
+    fn quit_requested(&self) -> bool {
+        self.frame >= self.frames
+    }
+
+    fn present(&mut self, _frame: &Frame) -> EmulatorResult<()> {
+        self.frame += 1;
+        Ok(())
+    }
+
+    fn realtime(&self) -> bool {
+        false
+    }
+}
+
+/// Run emulation loop until frontend requests to quit.
+///
+/// # Parameters
+/// - `cpu`      - given CPU with loaded program.
+/// - `frontend` - given host backend.
+/// - `speed`    - given number of instructions per frame.
+///
+/// # Returns
+/// - `Ok`  - in case of success.
+/// - `Err` - otherwise.
+pub fn run(
+    cpu: &mut Cpu,
+    frontend: &mut dyn Frontend,
+    speed: usize,
+) -> EmulatorResult<()> {
+    let mut keypad = *cpu.keypad();
+    let mut synth = frontend.audio().map(Synth::new);
+    let mut number = 0;
+    let mut ips = 0;
+    let mut second_start = Instant::now();
+    let mut second_cycles = cpu.cycles();
+
+    loop {
+        let frame_start = Instant::now();
+
+        frontend.poll_keys(&mut keypad)?;
+
+        if frontend.quit_requested() {
+            return Ok(());
+        }
+
+        for (key, &pressed) in keypad.iter().enumerate() {
+            cpu.set_key(key as u8, pressed);
+        }
+
+        // Sound plays during frame if timer was active at its start or end.
+        let tone = cpu.st() > 0;
+        cpu.frame(speed)?;
+        let playing = tone || cpu.st() > 0;
+
+        frontend.set_tone(playing)?;
+
+        if let Some(synth) = &mut synth {
+            let pattern = cpu.audio_pattern();
+            frontend.push_samples(&synth.frame_samples(
+                playing,
+                pattern,
+                cpu.pitch(),
+            ))?;
+        }
+
+        if second_start.elapsed() >= Duration::from_secs(1) {
+            ips = cpu.cycles() - second_cycles;
+            second_cycles = cpu.cycles();
+            second_start = Instant::now();
+        }
+
+        frontend.present(&Frame {
+            number,
+            display: cpu.display(),
+            cycles: cpu.cycles(),
+            pc: cpu.pc(),
+            dt: cpu.dt(),
+            st: cpu.st(),
+            ips,
+        })?;
+
+        number += 1;
+
+        if frontend.realtime() {
+            thread::sleep(FRAME_DURATION.saturating_sub(frame_start.elapsed()));
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::emulator::{cpu::tests::cpu_with_program, quirks::Quirks};
+
+    /// Frontend recording loop calls.
+    struct Recorder {
+        /// Presented frame numbers.
+        frames: Vec<u64>,
+        /// Tone state per frame.
+        tones: Vec<bool>,
+    }
+
+    impl Frontend for Recorder {
+        fn poll_keys(
+            &mut self,
+            keypad: &mut [bool; KEY_COUNT],
+        ) -> EmulatorResult<()> {
+            keypad[0x5] = self.frames.len() >= 2;
+            Ok(())
+        }
+
+        fn quit_requested(&self) -> bool {
+            self.frames.len() >= 4
+        }
+
+        fn present(&mut self, frame: &Frame) -> EmulatorResult<()> {
+            self.frames.push(frame.number);
+            Ok(())
+        }
+
+        fn set_tone(&mut self, playing: bool) -> EmulatorResult<()> {
+            self.tones.push(playing);
+            Ok(())
+        }
+
+        fn realtime(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        // Set sound timer to 2 when key 5 is pressed.
+        let program = [0x6005, 0x6102, 0xE09E, 0x1202, 0xF118, 0x120A];
+        let mut cpu = cpu_with_program(&program, Quirks::default());
+        let mut recorder = Recorder {
+            frames: Vec::new(),
+            tones: Vec::new(),
+        };
+
+        run(&mut cpu, &mut recorder, 10).unwrap();
+
+        assert_eq!(vec![0, 1, 2, 3], recorder.frames);
+        assert_eq!(vec![false, false, true, true], recorder.tones);
+        assert!(cpu.keypad()[0x5]);
+    }
+
+    #[test]
+    fn test_null() {
+        let mut cpu = cpu_with_program(&[0x1200], Quirks::default());
+        let script = KeyScript::parse("1:+A").unwrap();
+
+        run(&mut cpu, &mut Null::new(3, script), 10).unwrap();
+
+        assert_eq!(30, cpu.cycles());
+        assert!(cpu.keypad()[0xA]);
+    }
+}
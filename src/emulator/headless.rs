@@ -0,0 +1,309 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Headless runner with scripted input and golden screen assertions.
+
+use crate::emulator::{
+    EmulatorResult,
+    cpu::{Cpu, DISPLAY_HEIGHT, DISPLAY_WIDTH, KEY_COUNT},
+    frontend::{self, Null},
+};
+
+/// Screen snapshot hash prefix.
+const HASH_PREFIX: &str = "fnv1a:";
+
+/// Lit pixel character of ASCII-art snapshots.
+const PIXEL_ON: char = '#';
+
+/// Unlit pixel character of ASCII-art snapshots.
+const PIXEL_OFF: char = '.';
+
+/// Headless run options.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Key script file name.
+    pub keys: Option<String>,
+    /// Expected screen snapshot file name.
+    pub expect: Option<String>,
+    /// Output file name for actual screen snapshot.
+    pub snapshot: Option<String>,
+}
+
+/// Single scripted keypad event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyEvent {
+    /// Frame before which event is applied.
+    pub frame: u64,
+    /// Keypad key (0x0-0xF).
+    pub key: u8,
+    /// Whether key is pressed or released.
+    pub pressed: bool,
+}
+
+/// Scripted keypad input sorted by frame.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyScript {
+    /// Keypad events.
+    events: Vec<KeyEvent>,
+}
+
+impl KeyScript {
+    /// Parse key script.
+    ///
+    /// Script consists of `<frame>:<+|-><key>` tokens separated by
+    /// whitespace or commas, e.g. `30:+5 35:-5`. Text after `#` on a line
+    /// is ignored.
+    ///
+    /// # Parameters
+    /// - `text` - given key script text.
+    ///
+    /// # Returns
+    /// - New `KeyScript` object - in case of success.
+    /// - `Err`                  - otherwise.
+    pub fn parse(text: &str) -> EmulatorResult<Self> {
+        let mut events = Vec::new();
+
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or_default();
+
+            for token in line.split([' ', '\t', ',']).filter(|t| !t.is_empty())
+            {
+                events.push(parse_event(token)?);
+            }
+        }
+
+        events.sort_by_key(|event| event.frame);
+
+        Ok(Self { events })
+    }
+
+    /// Get script events.
+    ///
+    /// # Returns
+    /// - Keypad events sorted by frame.
+    pub fn events(&self) -> &[KeyEvent] {
+        &self.events
+    }
+
+    /// Apply events scheduled for specified frame.
+    ///
+    /// # Parameters
+    /// - `frame`  - given frame number.
+    /// - `keypad` - given keypad state to apply events to.
+    pub fn apply(&self, frame: u64, keypad: &mut [bool; KEY_COUNT]) {
+        let start = self.events.partition_point(|event| event.frame < frame);
+
+        for event in self.events[start..].iter() {
+            if event.frame != frame {
+                break;
+            }
+
+            keypad[event.key as usize] = event.pressed;
+        }
+    }
+}
+
+/// Parse single key script event.
+///
+/// # Parameters
+/// - `token` - given event token.
+///
+/// # Returns
+/// - Key event - in case of success.
+/// - `Err`     - otherwise.
+fn parse_event(token: &str) -> EmulatorResult<KeyEvent> {
+    let error = || format!("malformed key event '{token}'");
+    let (frame, action) = token.split_once(':').ok_or_else(error)?;
+    let frame = frame.parse().map_err(|_| error())?;
+
+    let (pressed, key) = match action.split_at_checked(1) {
+        Some(("+", key)) => (true, key),
+        Some(("-", key)) => (false, key),
+        _ => return Err(error()),
+    };
+
+    let key = u8::from_str_radix(key, 16).map_err(|_| error())?;
+
+    if key > 0xF {
+        return Err(error());
+    }
+
+    Ok(KeyEvent {
+        frame,
+        key,
+        pressed,
+    })
+}
+
+/// Run machine for number of frames with scripted input.
+///
+/// # Parameters
+/// - `cpu`    - given CPU with loaded program.
+/// - `script` - given key script.
+/// - `frames` - given number of frames to run.
+/// - `speed`  - given number of instructions per frame.
+///
+/// # Returns
+/// - `Ok`  - in case of success.
+/// - `Err` - otherwise.
+pub fn run(
+    cpu: &mut Cpu,
+    script: &KeyScript,
+    frames: u64,
+    speed: usize,
+) -> EmulatorResult<()> {
+    frontend::run(cpu, &mut Null::new(frames, script.clone()), speed)
+}
+
+/// Render display as ASCII-art snapshot.
+///
+/// # Parameters
+/// - `display` - given display pixels.
+///
+/// # Returns
+/// - ASCII-art with one text line per display row.
+pub fn ascii(display: &[bool]) -> String {
+    let mut result =
+        String::with_capacity((DISPLAY_WIDTH + 1) * DISPLAY_HEIGHT);
+
+    for row in display.chunks(DISPLAY_WIDTH) {
+        for &pixel in row {
+            result.push(if pixel { PIXEL_ON } else { PIXEL_OFF });
+        }
+
+        result.push('\n');
+    }
+
+    result
+}
+
+/// Calculate display hash snapshot.
+///
+/// # Parameters
+/// - `display` - given display pixels.
+///
+/// # Returns
+/// - Display hash string representation.
+pub fn hash(display: &[bool]) -> String {
+    let hash = display
+        .iter()
+        .fold(0xCBF2_9CE4_8422_2325u64, |hash, &pixel| {
+            (hash ^ pixel as u64).wrapping_mul(0x0000_0100_0000_01B3)
+        });
+
+    format!("{HASH_PREFIX}{hash:016x}")
+}
+
+/// Compare display with expected snapshot.
+///
+/// # Parameters
+/// - `display`  - given display pixels.
+/// - `expected` - given ASCII-art or hash snapshot.
+///
+/// # Returns
+/// - `Ok`  - if display matches snapshot.
+/// - `Err` - otherwise.
+pub fn assert_snapshot(display: &[bool], expected: &str) -> EmulatorResult<()> {
+    if expected.trim().starts_with(HASH_PREFIX) {
+        let expected = expected.trim();
+        let actual = hash(display);
+
+        if actual != expected {
+            return Err(format!(
+                "screen mismatch: expected {expected}, got {actual}\n{}",
+                ascii(display)
+            ));
+        }
+
+        return Ok(());
+    }
+
+    let rows: Vec<&str> = expected
+        .lines()
+        .map(|row| row.trim_end_matches('\r'))
+        .filter(|row| !row.is_empty())
+        .collect();
+
+    if rows.len() != DISPLAY_HEIGHT
+        || rows.iter().any(|row| row.chars().count() != DISPLAY_WIDTH)
+    {
+        return Err(format!(
+            "malformed snapshot: expected {DISPLAY_WIDTH}x{DISPLAY_HEIGHT} \
+             ASCII-art or '{HASH_PREFIX}' hash"
+        ));
+    }
+
+    let pixels = rows.iter().flat_map(|row| row.chars().map(is_lit));
+    let mismatches = display
+        .iter()
+        .zip(pixels)
+        .filter(|(actual, expected)| **actual != *expected)
+        .count();
+
+    if mismatches > 0 {
+        return Err(format!(
+            "screen mismatch: {mismatches} pixels differ\n{}",
+            ascii(display)
+        ));
+    }
+
+    Ok(())
+}
+
+/// Check whether ASCII-art character represents lit pixel.
+///
+/// # Parameters
+/// - `pixel` - given snapshot character.
+///
+/// # Returns
+/// - `true` - if pixel is lit.
+fn is_lit(pixel: char) -> bool {
+    !matches!(pixel, '.' | ' ' | '0' | '-' | '_')
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::emulator::{cpu::tests::cpu_with_program, quirks::Quirks};
+
+    #[test]
+    fn test_parse_script() {
+        let script = KeyScript::parse("10:-A, 5:+A # comment\n7:+f").unwrap();
+        let events = script.events();
+
+        assert_eq!(3, events.len());
+        assert_eq!(5, events[0].frame);
+        assert_eq!(0xA, events[0].key);
+        assert!(events[0].pressed);
+        assert_eq!(0xF, events[1].key);
+        assert!(!events[2].pressed);
+
+        assert!(KeyScript::parse("5:A").is_err());
+        assert!(KeyScript::parse("5:+10").is_err());
+        assert!(KeyScript::parse("x:+1").is_err());
+    }
+
+    #[test]
+    fn test_run_with_script() {
+        // Draw font digit of the pressed key once any key is pressed.
+        let program = [0xF00A, 0xF029, 0xD115, 0x1206];
+        let mut cpu = cpu_with_program(&program, Quirks::schip());
+        let script = KeyScript::parse("3:+1 4:-1").unwrap();
+
+        run(&mut cpu, &KeyScript::default(), 3, 10).unwrap();
+        assert!(cpu.display().iter().all(|&pixel| !pixel));
+
+        run(&mut cpu, &script, 5, 10).unwrap();
+        assert_eq!(0x1, cpu.registers()[0]);
+
+        let snapshot = ascii(cpu.display());
+        assert!(snapshot.starts_with("..#....."));
+        assert!(assert_snapshot(cpu.display(), &snapshot).is_ok());
+        assert!(assert_snapshot(cpu.display(), &hash(cpu.display())).is_ok());
+
+        let blank = ascii(&[false; DISPLAY_WIDTH * DISPLAY_HEIGHT]);
+        assert!(assert_snapshot(cpu.display(), &blank).is_err());
+        assert!(assert_snapshot(cpu.display(), "fnv1a:00").is_err());
+        assert!(assert_snapshot(cpu.display(), "#").is_err());
+    }
+}
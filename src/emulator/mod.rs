@@ -3,18 +3,141 @@
 
 //! Emulator main module.
 
-use crate::emulator::cpu::Cpu;
-use std::{fs::File, io::Read};
+use crate::emulator::{
+    analysis::Analysis,
+    capture::Capture,
+    coverage::Coverage,
+    cpu::{Cpu, Observer},
+    frontend::{Frontend, Null},
+    movie::{Movie, MoviePlayer, MovieRecorder},
+    profiler::Profiler,
+    quirks::Quirks,
+    random::{Algorithm, Random},
+    rom::Rom,
+    romdb::{Database, Entry},
+    sound::WavRecorder,
+    terminal::Terminal,
+    vip::Vip,
+};
+use std::{fs, io};
 
-mod cpu;
+pub use cpu::{Engine, Timing};
+
+pub mod analysis;
+pub mod batch;
+mod bench;
+pub mod capture;
+pub mod config_file;
+pub mod coverage;
+pub mod cpu;
 mod disasm;
+pub mod env;
+pub mod frontend;
+pub mod headless;
+pub mod movie;
+mod octo;
 mod opcode;
+pub mod profiler;
+pub mod quirks;
+pub mod random;
+pub mod rom;
+pub mod romdb;
+pub mod sound;
+pub mod tas;
+pub mod terminal;
+mod trace;
+pub mod vip;
+
+/// Default number of instructions executed per frame.
+const DEFAULT_SPEED: usize = 10;
+
+/// Maximum number of instructions executed in headless trace modes.
+const TRACE_LIMIT: u64 = 100_000;
 
 /// Emulator operation mode.
 #[derive(Debug)]
 pub enum Mode {
     Emulator,
-    Disassembler,
+    /// Write program listing to file (standard output if not set).
+    Disassembler(Option<String>),
+    /// Assemble Octo source to program file in given format.
+    Assemble(String, rom::Format),
+    /// Print program ROM information.
+    Info,
+    /// Print static analysis warnings.
+    Lint,
+    /// Print execution trace of the program.
+    Trace,
+    /// Run program under two quirks sets and report first divergence.
+    Compare(Quirks, Quirks),
+    /// Compare program trace file with other trace file.
+    TraceDiff(String),
+    /// Print instruction-level profiling report of the program.
+    Profile,
+    /// Print code coverage report of the program.
+    Coverage,
+    /// Run program headless for number of frames and check the screen.
+    Headless(u64, headless::Options),
+    /// Step program frame by frame with console commands.
+    Tas,
+    /// Compare throughput of instruction execution engines.
+    Bench,
+    /// Run program on emulated COSMAC VIP and report first divergence.
+    Vip(vip::Options),
+}
+
+/// Emulator runtime settings.
+#[derive(Debug, Clone)]
+pub struct Settings {
+    /// Random number generator seed (chosen randomly if not set).
+    pub seed: Option<u64>,
+    /// Random number generation algorithm.
+    pub random: Algorithm,
+    /// Instruction execution engine.
+    pub engine: Engine,
+    /// Instruction timing model.
+    pub timing: Timing,
+    /// Program load and entry address.
+    pub load_addr: usize,
+    /// Interpreter behavior quirks (looked up in ROM database if not set).
+    pub quirks: Option<Quirks>,
+    /// Instructions per frame (looked up in ROM database if not set).
+    pub speed: Option<usize>,
+    /// Whether to look up ROM in database.
+    pub lookup: bool,
+    /// ROM database file (no lookup if not set).
+    pub database: Option<String>,
+    /// Terminal frontend options.
+    pub terminal: terminal::Options,
+    /// Sound output options.
+    pub sound: sound::Options,
+    /// Display capture options.
+    pub capture: capture::Options,
+    /// Input movie options.
+    pub movie: movie::Options,
+    /// Configuration file defaults applied after per-ROM settings.
+    pub defaults: config_file::Defaults,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            seed: None,
+            random: Algorithm::SplitMix,
+            engine: Engine::Interpreter,
+            timing: Timing::Instructions,
+            load_addr: cpu::START_ADDR,
+            quirks: None,
+            speed: None,
+            lookup: true,
+            database: None,
+            terminal: terminal::Options::default(),
+            sound: sound::Options::default(),
+            capture: capture::Options::default(),
+            movie: movie::Options::default(),
+            defaults: config_file::Defaults::default(),
+        }
+    }
 }
 
 /// Result wrapper for emulator.
@@ -24,50 +147,93 @@ pub type EmulatorResult<T> = Result<T, String>;
 pub struct Emulator {
     /// Emulated CPU.
     cpu: Cpu,
+    /// Runtime settings.
+    settings: Settings,
+    /// Random number generator seed shared by all emulated machines.
+    seed: u64,
 }
 
 impl Emulator {
     /// Construct new `Emulator` object.
     ///
+    /// # Parameters
+    /// - `settings` - given runtime settings.
+    ///
     /// # Returns
     /// - New `Emulator` object.
-    pub fn new() -> Self {
-        let cpu = Cpu::new();
+    pub fn new(settings: Settings) -> Self {
+        let seed = settings.seed.unwrap_or_else(rand::random);
+        let random = Random::new(settings.random, seed);
+        let mut cpu = Cpu::with_quirks(settings.quirks.unwrap_or_default());
+
+        cpu.set_random(random);
+        cpu.set_engine(settings.engine);
+        cpu.set_timing(settings.timing);
 
-        Self { cpu }
+        Self {
+            cpu,
+            settings,
+            seed,
+        }
+    }
+
+    /// Get number of instructions executed per frame.
+    ///
+    /// # Returns
+    /// - Configured or default speed.
+    fn speed(&self) -> usize {
+        self.settings.speed.unwrap_or(DEFAULT_SPEED)
     }
 
-    /// Extract program data from binary file.
+    /// Apply program settings embedded in ROM container or found in ROM
+    /// database.
+    ///
+    /// Settings given on the command line take precedence, then settings
+    /// from the container, the database and the configuration file.
     ///
     /// # Parameters
-    /// - `filename` - given binary file name.
+    /// - `rom` - given loaded ROM.
     ///
     /// # Returns
-    /// - Program data bytes - in case of success.
-    /// - `Err`              - otherwise.
-    fn extract_program(&self, filename: &String) -> EmulatorResult<Vec<u8>> {
-        match File::open(filename) {
-            Ok(mut file) => {
-                let mut buffer = Vec::new();
+    /// - Database entry of the ROM - in case of success.
+    /// - `Err`                     - if database file is malformed.
+    fn configure(&mut self, rom: &Rom) -> EmulatorResult<Option<Entry>> {
+        if let Some(settings) = &rom.settings {
+            settings.apply(&mut self.settings);
+        }
 
-                if let Err(error) = file.read_to_end(&mut buffer) {
-                    return Err(format!(
-                        "Error read '{filename}' to buffer: {error}"
-                    ));
-                }
+        let mut entry = None;
 
-                if buffer.len() % 2 != 0 {
-                    return Err(
-                        "Buffer should have even byte length".to_string()
-                    );
-                }
+        if self.settings.lookup
+            && let Some(filename) = &self.settings.database
+        {
+            let database = Database::parse(&read_text(filename)?)?;
+            entry = database.lookup(&rom.data).cloned();
+        }
 
-                Ok(buffer)
-            }
-            Err(error) => {
-                Err(format!("Error during opening of '{filename}': {error}"))
-            }
+        if let Some(entry) = &entry {
+            entry.apply(&mut self.settings);
         }
+
+        self.settings.defaults.clone().apply(&mut self.settings);
+        self.cpu = self.machine(self.settings.quirks.unwrap_or_default());
+        Ok(entry)
+    }
+
+    /// Construct new machine using runtime settings.
+    ///
+    /// # Parameters
+    /// - `quirks` - given interpreter behavior quirks.
+    ///
+    /// # Returns
+    /// - New `Cpu` object.
+    fn machine(&self, quirks: Quirks) -> Cpu {
+        let mut cpu = Cpu::with_quirks(quirks);
+
+        cpu.set_random(Random::new(self.settings.random, self.seed));
+        cpu.set_engine(self.settings.engine);
+        cpu.set_timing(self.settings.timing);
+        cpu
     }
 
     /// Run an emulator.
@@ -80,11 +246,139 @@ impl Emulator {
     /// - `Ok`  - in case of success.
     /// - `Err` - otherwise.
     pub fn run(&mut self, mode: Mode, filename: String) -> EmulatorResult<()> {
-        let program_data = self.extract_program(&filename)?;
+        match &mode {
+            Mode::TraceDiff(other) => return self.trace_diff(&filename, other),
+            Mode::Assemble(output, format) => {
+                return self.assemble(&filename, output, *format);
+            }
+            _ => {}
+        }
+
+        // Disassembly is not limited by the emulated machine memory.
+        let variant = match mode {
+            Mode::Disassembler(_) | Mode::Info | Mode::Lint => {
+                rom::Variant::XoChip
+            }
+            _ => rom::Variant::Chip8,
+        };
+        let rom = rom::load(&filename, variant, self.settings.load_addr)?;
+        let entry = self.configure(&rom)?;
+        let program_data = rom.data.clone();
 
         match mode {
             Mode::Emulator => self.emulate(&program_data),
-            Mode::Disassembler => disasm::disassemble(&program_data),
+            Mode::Disassembler(output) => {
+                let listing =
+                    disasm::disassemble(&program_data, self.settings.load_addr);
+
+                match output {
+                    Some(filename) => write_file(&filename, listing),
+                    None => {
+                        print!("{listing}");
+                        Ok(())
+                    }
+                }
+            }
+            Mode::Info => self.info(&rom, entry.as_ref()),
+            Mode::Lint => self.lint(&program_data),
+            Mode::Trace => self.trace(&program_data),
+            Mode::Compare(first, second) => {
+                self.compare(&program_data, first, second)
+            }
+            Mode::Profile => self.profile(&program_data),
+            Mode::Coverage => self.coverage(&program_data),
+            Mode::Headless(frames, options) => {
+                self.headless(&program_data, frames, &options)
+            }
+            Mode::Tas => self.tas(&program_data),
+            Mode::Bench => self.bench(&program_data),
+            Mode::Vip(options) => self.vip(&program_data, &options),
+            Mode::TraceDiff(_) | Mode::Assemble(..) => unreachable!(),
+        }
+    }
+
+    /// Assemble Octo source file.
+    ///
+    /// # Parameters
+    /// - `source` - given Octo source file name.
+    /// - `output` - given output program file name.
+    /// - `format` - given output container format.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn assemble(
+        &self,
+        source: &str,
+        output: &str,
+        format: rom::Format,
+    ) -> EmulatorResult<()> {
+        let data = octo::assemble(&read_text(source)?)
+            .map_err(|error| format!("{source}: {error}"))?;
+
+        write_file(output, rom::encode(&data, format, cpu::START_ADDR)?)
+    }
+
+    /// Print program ROM information.
+    ///
+    /// # Parameters
+    /// - `rom`   - given loaded ROM.
+    /// - `entry` - given ROM database entry.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn info(&self, rom: &Rom, entry: Option<&Entry>) -> EmulatorResult<()> {
+        let data = &rom.data;
+
+        println!("Format:    {}", rom.format.name());
+        println!("Size:      {} bytes", data.len());
+        println!("SHA-1:     {}", romdb::hex_digest(data));
+
+        if let Some(entry) = entry {
+            println!("Title:     {}", entry.title);
+
+            if !entry.authors.is_empty() {
+                println!("Authors:   {}", entry.authors.join(", "));
+            }
+        }
+
+        if let Some(quirks) = self.settings.quirks {
+            let flags: Vec<&str> = quirks::NAMES
+                .iter()
+                .zip(quirks.flags())
+                .filter_map(|(name, enabled)| enabled.then_some(*name))
+                .collect();
+
+            println!("Quirks:    {}", flags.join(", "));
+        }
+
+        println!("Speed:     {} instructions per frame", self.speed());
+
+        Analysis::new(data, self.settings.load_addr).report();
+        Ok(())
+    }
+
+    /// Print static analysis warnings of program.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    ///
+    /// # Returns
+    /// - `Ok`  - if program has no warnings.
+    /// - `Err` - otherwise.
+    fn lint(&self, program_data: &[u8]) -> EmulatorResult<()> {
+        let analysis = Analysis::new(program_data, self.settings.load_addr);
+        let warnings = analysis.warnings();
+
+        for warning in &warnings {
+            println!("warning: {warning}");
+        }
+
+        match warnings.len() {
+            0 => Ok(()),
+            1 => Err("1 warning".to_string()),
+            count => Err(format!("{count} warnings")),
         }
     }
 
@@ -97,9 +391,433 @@ impl Emulator {
     /// - `Ok`  - in case of success.
     /// - `Err` - otherwise.
     fn emulate(&mut self, program_data: &[u8]) -> EmulatorResult<()> {
-        self.cpu.load_program(program_data);
-        self.cpu.run();
+        self.cpu
+            .load_program_at(program_data, self.settings.load_addr)?;
+
+        let mut terminal = Terminal::new(&self.settings.terminal)?;
+
+        self.drive(&mut terminal, program_data)
+    }
+
+    /// Run emulation loop, replaying or recording input movie and capturing
+    /// display and audio if requested.
+    ///
+    /// # Parameters
+    /// - `frontend`     - given host backend.
+    /// - `program_data` - given loaded program data bytes.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn drive(
+        &mut self,
+        frontend: &mut dyn Frontend,
+        program_data: &[u8],
+    ) -> EmulatorResult<()> {
+        let settings = self.settings.clone();
+        let mut speed = self.speed();
+        let mut player = None;
+        let mut recorder = None;
+        let mut wav = None;
+
+        let frontend: &mut dyn Frontend = match &settings.movie.play {
+            Some(filename) => {
+                let movie: Movie = read_text(filename)?.parse()?;
+
+                movie.check_rom(program_data)?;
+                self.cpu = self.machine(movie.quirks);
+                self.cpu.set_random(Random::new(movie.random, movie.seed));
+                self.cpu.set_timing(movie.timing);
+                self.cpu.load_program_at(program_data, movie.load_addr)?;
+                speed = movie.speed;
+
+                player.insert(MoviePlayer::new(frontend, movie))
+            }
+            None => frontend,
+        };
+
+        let frontend: &mut dyn Frontend = match &settings.movie.record {
+            Some(_) => {
+                let random = self.cpu.random();
+                let header = Movie::new(
+                    program_data,
+                    random.seed(),
+                    random.algorithm(),
+                    *self.cpu.quirks(),
+                    speed,
+                    self.cpu.timing(),
+                    self.settings.load_addr,
+                );
+                let keypad = *self.cpu.keypad();
+
+                recorder.insert(MovieRecorder::new(frontend, header, keypad))
+            }
+            None => frontend,
+        };
+
+        let mut capture = Capture::new(frontend, &settings.capture)?;
+        let frontend: &mut dyn Frontend = match &settings.sound.wav {
+            Some(_) => {
+                wav.insert(WavRecorder::new(&mut capture, &settings.sound))
+            }
+            None => &mut capture,
+        };
+
+        let result = frontend::run(&mut self.cpu, frontend, speed);
+
+        if let (Some(wav), Some(filename)) = (wav, &settings.sound.wav) {
+            wav.save(filename)?;
+        }
+
+        capture.finish()?;
+
+        if let (Some(recorder), Some(filename)) =
+            (recorder, &settings.movie.record)
+        {
+            recorder.into_movie().save(filename)?;
+        }
+
+        // Without specific frames screenshot is taken at the end of session.
+        let options = &settings.capture;
+
+        if let Some(filename) = &options.screenshot
+            && options.frames.is_empty()
+        {
+            capture::save_screenshot(filename, self.cpu.display(), options)?;
+        }
+
+        result
+    }
+
+    /// Run program headless and check the screen against snapshot.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    /// - `frames`       - given number of frames to run.
+    /// - `options`      - given headless run options.
+    ///
+    /// # Returns
+    /// - `Ok`  - if the screen matches expected snapshot.
+    /// - `Err` - otherwise.
+    fn headless(
+        &mut self,
+        program_data: &[u8],
+        frames: u64,
+        options: &headless::Options,
+    ) -> EmulatorResult<()> {
+        let script = match &options.keys {
+            Some(filename) => {
+                headless::KeyScript::parse(&read_text(filename)?)?
+            }
+            None => headless::KeyScript::default(),
+        };
+
+        self.cpu
+            .load_program_at(program_data, self.settings.load_addr)?;
+        self.drive(&mut Null::new(frames, script), program_data)?;
+
+        let display = self.cpu.display();
+
+        if let Some(filename) = &options.snapshot {
+            fs::write(filename, headless::ascii(display)).map_err(|error| {
+                format!("Error write '{filename}': {error}")
+            })?;
+        }
+
+        match &options.expect {
+            Some(filename) => {
+                headless::assert_snapshot(display, &read_text(filename)?)
+            }
+            None => {
+                print!("{}", headless::ascii(display));
+                println!("{}", headless::hash(display));
+                Ok(())
+            }
+        }
+    }
+
+    /// Step program frame by frame with console commands from stdin.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn tas(&mut self, program_data: &[u8]) -> EmulatorResult<()> {
+        let mut cpu = self.machine(*self.cpu.quirks());
+        cpu.load_program_at(program_data, self.settings.load_addr)?;
+
+        let header = Movie::new(
+            program_data,
+            self.seed,
+            self.settings.random,
+            *cpu.quirks(),
+            self.speed(),
+            cpu.timing(),
+            self.settings.load_addr,
+        );
+        let mut session = tas::Session::new(cpu, self.speed());
+
+        println!("{}", tas::HELP);
+
+        for line in io::stdin().lines() {
+            let line =
+                line.map_err(|error| format!("Error read command: {error}"))?;
+
+            match tas::execute(&mut session, &line, &header) {
+                Ok(Some(output)) => println!("{output}"),
+                Ok(None) => break,
+                Err(error) => println!("error: {error}"),
+            }
+        }
 
         Ok(())
     }
+
+    /// Print execution engines throughput benchmark of the program.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn bench(&mut self, program_data: &[u8]) -> EmulatorResult<()> {
+        let mut cpu = self.machine(*self.cpu.quirks());
+        cpu.load_program_at(program_data, self.settings.load_addr)?;
+
+        let report = bench::measure(&cpu, bench::DEFAULT_FRAMES, self.speed())?;
+        println!("{report}");
+
+        Ok(())
+    }
+
+    /// Run program on emulated COSMAC VIP in lockstep with high-level CPU
+    /// and report first divergence.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    /// - `options`      - given COSMAC VIP options.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn vip(
+        &mut self,
+        program_data: &[u8],
+        options: &vip::Options,
+    ) -> EmulatorResult<()> {
+        let interpreter = read_binary(&options.interpreter)?;
+        let monitor = match &options.monitor {
+            Some(filename) => Some(read_binary(filename)?),
+            None => None,
+        };
+
+        let mut machine = Vip::new(&interpreter, monitor.as_deref())?;
+        machine.load_program(program_data)?;
+
+        let mut cpu = self.machine(Quirks::chip8());
+        cpu.set_timing(Timing::Vip);
+        machine.copy_random_table(&mut cpu)?;
+        cpu.load_program(program_data)?;
+
+        let result =
+            vip::diff(&mut cpu, &mut machine, self.speed(), TRACE_LIMIT)?;
+
+        match result {
+            Some(divergence) => divergence.report(),
+            None => println!("No divergence in {TRACE_LIMIT} instructions"),
+        }
+
+        Ok(())
+    }
+
+    /// Print execution trace of the program.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn trace(&mut self, program_data: &[u8]) -> EmulatorResult<()> {
+        self.cpu
+            .load_program_at(program_data, self.settings.load_addr)?;
+
+        for _ in 0..TRACE_LIMIT {
+            println!("{}", trace::TraceEntry::capture(&self.cpu));
+            self.cpu.cycle(self.speed())?;
+        }
+
+        Ok(())
+    }
+
+    /// Print instruction-level profiling report of the program.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn profile(&mut self, program_data: &[u8]) -> EmulatorResult<()> {
+        let mut profiler = Profiler::new();
+        let result = self.observe(program_data, &mut profiler);
+
+        profiler.report(&self.cpu);
+        profiler.annotated_listing(program_data, self.settings.load_addr);
+
+        result
+    }
+
+    /// Print code coverage report of the program.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn coverage(&mut self, program_data: &[u8]) -> EmulatorResult<()> {
+        let mut coverage = Coverage::new();
+        let result = self.observe(program_data, &mut coverage);
+
+        coverage.annotated_listing(program_data, self.settings.load_addr);
+        coverage.summary(program_data, self.settings.load_addr);
+
+        result
+    }
+
+    /// Run program headless under execution observer.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    /// - `observer`     - given execution observer.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn observe(
+        &mut self,
+        program_data: &[u8],
+        observer: &mut impl Observer,
+    ) -> EmulatorResult<()> {
+        self.cpu
+            .load_program_at(program_data, self.settings.load_addr)?;
+
+        for _ in 0..TRACE_LIMIT {
+            observer.before_step(&self.cpu);
+            self.cpu.cycle(self.speed())?;
+            observer.after_step(&self.cpu);
+        }
+
+        Ok(())
+    }
+
+    /// Run program under two quirks sets and report first divergence.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    /// - `first`        - given first quirks set.
+    /// - `second`       - given second quirks set.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn compare(
+        &mut self,
+        program_data: &[u8],
+        first: Quirks,
+        second: Quirks,
+    ) -> EmulatorResult<()> {
+        let mut first = self.machine(first);
+        let mut second = self.machine(second);
+
+        first.load_program_at(program_data, self.settings.load_addr)?;
+        second.load_program_at(program_data, self.settings.load_addr)?;
+
+        let result = trace::diff_machines(
+            &mut first,
+            &mut second,
+            self.speed(),
+            TRACE_LIMIT,
+        )?;
+
+        match result {
+            Some(divergence) => divergence.report(),
+            None => println!("No divergence in {TRACE_LIMIT} instructions"),
+        }
+
+        Ok(())
+    }
+
+    /// Compare two execution trace files.
+    ///
+    /// # Parameters
+    /// - `first`  - given first trace file name.
+    /// - `second` - given second trace file name.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn trace_diff(
+        &self,
+        first: &String,
+        second: &String,
+    ) -> EmulatorResult<()> {
+        let read = |filename: &String| {
+            read_text(filename).and_then(|text| trace::parse(&text))
+        };
+
+        match trace::diff_traces(&read(first)?, &read(second)?) {
+            Some(divergence) => divergence.report(),
+            None => println!("Traces are identical"),
+        }
+
+        Ok(())
+    }
+}
+
+/// Read text file.
+///
+/// # Parameters
+/// - `filename` - given text file name.
+///
+/// # Returns
+/// - File contents - in case of success.
+/// - `Err`         - otherwise.
+fn read_text(filename: &str) -> EmulatorResult<String> {
+    fs::read_to_string(filename)
+        .map_err(|error| format!("Error read '{filename}': {error}"))
+}
+
+/// Write file.
+///
+/// # Parameters
+/// - `filename` - given file name.
+/// - `contents` - given file contents.
+///
+/// # Returns
+/// - `Ok`  - in case of success.
+/// - `Err` - otherwise.
+fn write_file(
+    filename: &str,
+    contents: impl AsRef<[u8]>,
+) -> EmulatorResult<()> {
+    fs::write(filename, contents)
+        .map_err(|error| format!("Error write '{filename}': {error}"))
+}
+
+/// Read binary file.
+///
+/// # Parameters
+/// - `filename` - given binary file name.
+///
+/// # Returns
+/// - File contents - in case of success.
+/// - `Err`         - otherwise.
+fn read_binary(filename: &str) -> EmulatorResult<Vec<u8>> {
+    fs::read(filename)
+        .map_err(|error| format!("Error read '{filename}': {error}"))
 }
@@ -0,0 +1,589 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Input movies recording and deterministic playback.
+
+use crate::emulator::{
+    EmulatorResult,
+    cpu::{KEY_COUNT, START_ADDR, Timing},
+    frontend::{Frame, Frontend},
+    quirks::{self, Quirks},
+    random::Algorithm,
+    romdb, sound,
+};
+use std::{fmt, fs, str::FromStr};
+
+/// Movie file signature line.
+const SIGNATURE: &str = "chip8-movie 1";
+
+/// Movie recording and playback options.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Output movie file name.
+    pub record: Option<String>,
+    /// Input movie file name.
+    pub play: Option<String>,
+}
+
+/// Single recorded keypad event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MovieEvent {
+    /// Frame before which event is applied.
+    pub frame: u64,
+    /// Number of instructions executed before event.
+    pub cycle: u64,
+    /// Keypad key (0x0-0xF).
+    pub key: u8,
+    /// Whether key is pressed or released.
+    pub pressed: bool,
+}
+
+/// Keypad input movie with machine configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Movie {
+    /// Hash of the program the movie was recorded with.
+    pub rom: String,
+    /// Random number generator seed.
+    pub seed: u64,
+    /// Random number generation algorithm.
+    pub random: Algorithm,
+    /// Interpreter behavior quirks.
+    pub quirks: Quirks,
+    /// Number of instructions executed per frame.
+    pub speed: usize,
+    /// Instruction timing model.
+    pub timing: Timing,
+    /// Program load and entry address.
+    pub load_addr: usize,
+    /// Number of recorded frames.
+    pub frames: u64,
+    /// Keypad events sorted by frame.
+    pub events: Vec<MovieEvent>,
+}
+
+impl Movie {
+    /// Construct new empty `Movie` object.
+    ///
+    /// # Parameters
+    /// - `program_data` - given recorded program data bytes.
+    /// - `seed`         - given random number generator seed.
+    /// - `random`       - given random number generation algorithm.
+    /// - `quirks`       - given interpreter behavior quirks.
+    /// - `speed`        - given number of instructions per frame.
+    /// - `timing`       - given instruction timing model.
+    /// - `load_addr`    - given program load address.
+    ///
+    /// # Returns
+    /// - New `Movie` object.
+    pub fn new(
+        program_data: &[u8],
+        seed: u64,
+        random: Algorithm,
+        quirks: Quirks,
+        speed: usize,
+        timing: Timing,
+        load_addr: usize,
+    ) -> Self {
+        Self {
+            rom: rom_hash(program_data),
+            seed,
+            random,
+            quirks,
+            speed,
+            timing,
+            load_addr,
+            frames: 0,
+            events: Vec::new(),
+        }
+    }
+
+    /// Check that movie was recorded with specified program.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    ///
+    /// # Returns
+    /// - `Ok`  - if program matches.
+    /// - `Err` - otherwise.
+    pub fn check_rom(&self, program_data: &[u8]) -> EmulatorResult<()> {
+        let actual = rom_hash(program_data);
+
+        if actual != self.rom {
+            return Err(format!(
+                "movie was recorded with other program ({}, got {actual})",
+                self.rom
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Write movie to file.
+    ///
+    /// # Parameters
+    /// - `filename` - given output file name.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    pub fn save(&self, filename: &str) -> EmulatorResult<()> {
+        fs::write(filename, self.to_string())
+            .map_err(|error| format!("Error write '{filename}': {error}"))
+    }
+}
+
+impl fmt::Display for Movie {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{SIGNATURE}")?;
+        writeln!(f, "rom {}", self.rom)?;
+        writeln!(f, "seed {}", self.seed)?;
+        writeln!(f, "random {}", self.random.name())?;
+        write!(f, "quirks")?;
+
+        for (name, enabled) in quirks::NAMES.iter().zip(self.quirks.flags()) {
+            write!(f, " {name}={}", enabled as u8)?;
+        }
+
+        writeln!(f, "\nspeed {}", self.speed)?;
+        writeln!(f, "timing {}", self.timing.name())?;
+        writeln!(f, "load {:#05X}", self.load_addr)?;
+        writeln!(f, "frames {}", self.frames)?;
+        writeln!(f, "# <frame> <cycle> <+|-><key>")?;
+
+        for event in &self.events {
+            let action = if event.pressed { '+' } else { '-' };
+            writeln!(
+                f,
+                "{} {} {action}{:X}",
+                event.frame, event.cycle, event.key
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Movie {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut lines = text
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default().trim())
+            .filter(|line| !line.is_empty());
+
+        if lines.next() != Some(SIGNATURE) {
+            return Err("not a chip8 input movie".to_string());
+        }
+
+        let mut movie = Movie::new(
+            &[],
+            0,
+            Algorithm::SplitMix,
+            Quirks::default(),
+            0,
+            Timing::Instructions,
+            START_ADDR,
+        );
+        let error = |line: &str| format!("malformed movie line '{line}'");
+
+        for line in lines {
+            let (keyword, value) =
+                line.split_once(' ').ok_or_else(|| error(line))?;
+
+            match keyword {
+                "rom" => movie.rom = value.to_string(),
+                "seed" => {
+                    movie.seed = value.parse().map_err(|_| error(line))?
+                }
+                "random" => movie.random = Algorithm::from_name(value)?,
+                "quirks" => movie.quirks = parse_quirks(value)?,
+                "speed" => {
+                    movie.speed = value.parse().map_err(|_| error(line))?
+                }
+                "timing" => movie.timing = Timing::from_name(value)?,
+                "load" => {
+                    let addr = match value.strip_prefix("0x") {
+                        Some(hex) => usize::from_str_radix(hex, 16),
+                        None => value.parse(),
+                    };
+                    movie.load_addr = addr.map_err(|_| error(line))?;
+                }
+                "frames" => {
+                    movie.frames = value.parse().map_err(|_| error(line))?
+                }
+                _ => movie
+                    .events
+                    .push(parse_event(line).ok_or_else(|| error(line))?),
+            }
+        }
+
+        if movie.speed == 0 {
+            return Err("movie speed is not set".to_string());
+        }
+
+        movie.events.sort_by_key(|event| event.frame);
+
+        Ok(movie)
+    }
+}
+
+/// Parse quirks flags, e.g. `vf_reset=1 memory=0 ...`.
+///
+/// # Parameters
+/// - `text` - given quirks flags.
+///
+/// # Returns
+/// - Quirks - in case of success.
+/// - `Err`  - otherwise.
+fn parse_quirks(text: &str) -> EmulatorResult<Quirks> {
+    let mut flags = Quirks::default().flags();
+
+    for token in text.split_whitespace() {
+        let error = || format!("malformed quirk '{token}'");
+        let (name, value) = token.split_once('=').ok_or_else(error)?;
+        let index = quirks::NAMES
+            .iter()
+            .position(|&quirk| quirk == name)
+            .ok_or_else(error)?;
+
+        flags[index] = match value {
+            "0" => false,
+            "1" => true,
+            _ => return Err(error()),
+        };
+    }
+
+    Ok(Quirks::from_flags(flags))
+}
+
+/// Parse movie event line `<frame> <cycle> <+|-><key>`.
+///
+/// # Parameters
+/// - `line` - given event line.
+///
+/// # Returns
+/// - Movie event - in case of success.
+/// - `None`      - otherwise.
+fn parse_event(line: &str) -> Option<MovieEvent> {
+    let mut tokens = line.split_whitespace();
+    let frame = tokens.next()?.parse().ok()?;
+    let cycle = tokens.next()?.parse().ok()?;
+    let action = tokens.next()?;
+
+    if tokens.next().is_some() {
+        return None;
+    }
+
+    let (pressed, key) = match action.split_at_checked(1)? {
+        ("+", key) => (true, key),
+        ("-", key) => (false, key),
+        _ => return None,
+    };
+    let key = u8::from_str_radix(key, 16).ok().filter(|&key| key <= 0xF)?;
+
+    Some(MovieEvent {
+        frame,
+        cycle,
+        key,
+        pressed,
+    })
+}
+
+/// Calculate program identity hash.
+///
+/// # Parameters
+/// - `program_data` - given program data bytes.
+///
+/// # Returns
+/// - Program hash string representation.
+pub fn rom_hash(program_data: &[u8]) -> String {
+    format!("sha1:{}", romdb::hex_digest(program_data))
+}
+
+/// Frontend wrapper recording keypad events into movie.
+pub struct MovieRecorder<'a> {
+    /// Wrapped frontend.
+    inner: &'a mut dyn Frontend,
+    /// Recorded movie.
+    movie: Movie,
+    /// Keypad state after previous poll.
+    keypad: [bool; KEY_COUNT],
+    /// Number of presented frames.
+    frame: u64,
+    /// Number of executed instructions at the end of previous frame.
+    cycles: u64,
+}
+
+impl<'a> MovieRecorder<'a> {
+    /// Construct new `MovieRecorder` object.
+    ///
+    /// # Parameters
+    /// - `inner`  - given frontend to wrap.
+    /// - `header` - given empty movie with machine configuration.
+    /// - `keypad` - given initial keypad state.
+    ///
+    /// # Returns
+    /// - New `MovieRecorder` object.
+    pub fn new(
+        inner: &'a mut dyn Frontend,
+        header: Movie,
+        keypad: [bool; KEY_COUNT],
+    ) -> Self {
+        Self {
+            inner,
+            movie: header,
+            keypad,
+            frame: 0,
+            cycles: 0,
+        }
+    }
+
+    /// Finish recording.
+    ///
+    /// # Returns
+    /// - Recorded movie.
+    pub fn into_movie(self) -> Movie {
+        self.movie
+    }
+}
+
+impl Frontend for MovieRecorder<'_> {
+    fn poll_keys(
+        &mut self,
+        keypad: &mut [bool; KEY_COUNT],
+    ) -> EmulatorResult<()> {
+        self.inner.poll_keys(keypad)?;
+
+        for (key, (&pressed, previous)) in
+            keypad.iter().zip(self.keypad.iter_mut()).enumerate()
+        {
+            if pressed != *previous {
+                self.movie.events.push(MovieEvent {
+                    frame: self.frame,
+                    cycle: self.cycles,
+                    key: key as u8,
+                    pressed,
+                });
+                *previous = pressed;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn quit_requested(&self) -> bool {
+        self.inner.quit_requested()
+    }
+
+    fn screenshot_requested(&mut self) -> bool {
+        self.inner.screenshot_requested()
+    }
+
+    fn present(&mut self, frame: &Frame) -> EmulatorResult<()> {
+        self.frame = frame.number + 1;
+        self.cycles = frame.cycles;
+        self.movie.frames = self.frame;
+        self.inner.present(frame)
+    }
+
+    fn set_tone(&mut self, playing: bool) -> EmulatorResult<()> {
+        self.inner.set_tone(playing)
+    }
+
+    fn push_samples(&mut self, samples: &[i16]) -> EmulatorResult<()> {
+        self.inner.push_samples(samples)
+    }
+
+    fn audio(&self) -> Option<&sound::Options> {
+        self.inner.audio()
+    }
+
+    fn realtime(&self) -> bool {
+        self.inner.realtime()
+    }
+}
+
+/// Frontend wrapper replaying keypad events from movie.
+///
+/// Host input is polled only for quit requests, emulation stops at the end
+/// of the movie.
+pub struct MoviePlayer<'a> {
+    /// Wrapped frontend.
+    inner: &'a mut dyn Frontend,
+    /// Replayed movie.
+    movie: Movie,
+    /// Index of next event to apply.
+    next: usize,
+    /// Number of presented frames.
+    frame: u64,
+    /// Number of executed instructions at the end of previous frame.
+    cycles: u64,
+}
+
+impl<'a> MoviePlayer<'a> {
+    /// Construct new `MoviePlayer` object.
+    ///
+    /// # Parameters
+    /// - `inner` - given frontend to wrap.
+    /// - `movie` - given movie to replay.
+    ///
+    /// # Returns
+    /// - New `MoviePlayer` object.
+    pub fn new(inner: &'a mut dyn Frontend, movie: Movie) -> Self {
+        Self {
+            inner,
+            movie,
+            next: 0,
+            frame: 0,
+            cycles: 0,
+        }
+    }
+}
+
+impl Frontend for MoviePlayer<'_> {
+    fn poll_keys(
+        &mut self,
+        keypad: &mut [bool; KEY_COUNT],
+    ) -> EmulatorResult<()> {
+        let mut host = *keypad;
+        self.inner.poll_keys(&mut host)?;
+
+        while let Some(event) = self.movie.events.get(self.next) {
+            if event.frame != self.frame {
+                break;
+            }
+
+            if event.cycle != self.cycles {
+                return Err(format!(
+                    "movie desynchronized at frame {}: expected cycle {}, \
+                     got {}",
+                    self.frame, event.cycle, self.cycles
+                ));
+            }
+
+            keypad[event.key as usize] = event.pressed;
+            self.next += 1;
+        }
+
+        Ok(())
+    }
+
+    fn quit_requested(&self) -> bool {
+        self.inner.quit_requested() || self.frame >= self.movie.frames
+    }
+
+    fn screenshot_requested(&mut self) -> bool {
+        self.inner.screenshot_requested()
+    }
+
+    fn present(&mut self, frame: &Frame) -> EmulatorResult<()> {
+        self.frame = frame.number + 1;
+        self.cycles = frame.cycles;
+        self.inner.present(frame)
+    }
+
+    fn set_tone(&mut self, playing: bool) -> EmulatorResult<()> {
+        self.inner.set_tone(playing)
+    }
+
+    fn push_samples(&mut self, samples: &[i16]) -> EmulatorResult<()> {
+        self.inner.push_samples(samples)
+    }
+
+    fn audio(&self) -> Option<&sound::Options> {
+        self.inner.audio()
+    }
+
+    fn realtime(&self) -> bool {
+        self.inner.realtime()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::emulator::{
+        cpu::tests::{cpu_with_program, program_bytes},
+        frontend::{self, Null},
+        headless::KeyScript,
+        random::Random,
+    };
+
+    /// Program drawing random sprite whenever key 5 is pressed.
+    const PROGRAM: [u16; 7] =
+        [0x6005, 0xE09E, 0x1202, 0xC10F, 0xF129, 0xD125, 0x1202];
+
+    #[test]
+    fn test_movie_format() {
+        let mut movie = Movie::new(
+            &[0x12, 0x00],
+            7,
+            Algorithm::Vip,
+            Quirks::schip(),
+            15,
+            Timing::Vip,
+            0x600,
+        );
+        movie.frames = 40;
+        movie.events.push(MovieEvent {
+            frame: 3,
+            cycle: 45,
+            key: 0xA,
+            pressed: true,
+        });
+
+        let text = movie.to_string();
+        assert!(text.contains("3 45 +A"));
+        assert!(text.contains("timing vip\nload 0x600\n"));
+        assert_eq!(movie, text.parse().unwrap());
+
+        assert!("seed 1".parse::<Movie>().is_err());
+        assert!(format!("{text}3 x +A").parse::<Movie>().is_err());
+        assert!(movie.check_rom(&[0x12, 0x00]).is_ok());
+        assert!(movie.check_rom(&[0x12, 0x02]).is_err());
+    }
+
+    #[test]
+    fn test_record_and_play() {
+        let bytes = program_bytes(&PROGRAM);
+        let machine = || {
+            let mut cpu = cpu_with_program(&PROGRAM, Quirks::default());
+            cpu.set_random(Random::new(Algorithm::SplitMix, 5));
+            cpu
+        };
+
+        let mut cpu = machine();
+        let script = KeyScript::parse("2:+5 4:-5 6:+5").unwrap();
+        let mut null = Null::new(8, script);
+        let header = Movie::new(
+            &bytes,
+            5,
+            Algorithm::SplitMix,
+            Quirks::default(),
+            10,
+            Timing::Instructions,
+            START_ADDR,
+        );
+        let mut recorder = MovieRecorder::new(&mut null, header, *cpu.keypad());
+
+        frontend::run(&mut cpu, &mut recorder, 10).unwrap();
+
+        let movie = recorder.into_movie();
+        assert_eq!(8, movie.frames);
+        assert_eq!(3, movie.events.len());
+
+        let mut replayed = machine();
+        let mut null = Null::new(u64::MAX, KeyScript::default());
+        let mut player = MoviePlayer::new(&mut null, movie.clone());
+
+        frontend::run(&mut replayed, &mut player, 10).unwrap();
+        assert_eq!(cpu.save_state(), replayed.save_state());
+
+        let mut desync = machine();
+        let mut null = Null::new(u64::MAX, KeyScript::default());
+        let mut player = MoviePlayer::new(&mut null, movie);
+
+        assert!(frontend::run(&mut desync, &mut player, 11).is_err());
+    }
+}
@@ -0,0 +1,1410 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Octo assembly language compiler.
+//!
+//! Supports Octo statements, structured control flow, labels with forward
+//! references, constants, aliases, macros and `:calc` expressions.
+
+use crate::emulator::{EmulatorResult, cpu::START_ADDR};
+use std::collections::{HashMap, VecDeque};
+
+/// Maximum number of tokens produced by macro expansions, stops recursive
+/// macros.
+const MAX_EXPANDED_TOKENS: usize = 1 << 18;
+
+/// Unary operators of `:calc` expressions.
+const UNARY_OPERATORS: [&str; 14] = [
+    "-", "~", "!", "sin", "cos", "tan", "exp", "log", "abs", "sqrt", "sign",
+    "ceil", "floor", "@",
+];
+
+/// Source token.
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    /// Token text.
+    text: String,
+    /// Source line number.
+    line: usize,
+}
+
+/// Kind of address reference patched after all labels are known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Fixup {
+    /// 12-bit address in the low nibbles of instruction.
+    Addr,
+    /// 16-bit address in two bytes.
+    Long,
+    /// High address nibble in the low nibble of `:unpack` byte.
+    UnpackHigh,
+    /// Low address byte of `:unpack`.
+    UnpackLow,
+}
+
+/// Open control flow structure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Flow {
+    /// `if ... begin` with address of jump over the body.
+    If(usize),
+    /// `else` with address of jump over the else branch.
+    Else(usize),
+    /// `loop` with start address and addresses of `while` exit jumps.
+    Loop(usize, Vec<usize>),
+}
+
+/// Register condition of `if` and `while` statements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Condition {
+    /// Register equals value or register.
+    Eq(u8, Operand),
+    /// Register differs from value or register.
+    Ne(u8, Operand),
+    /// Register is less than value or register.
+    Lt(u8, Operand),
+    /// Register is greater than value or register.
+    Gt(u8, Operand),
+    /// Register is less than or equal to value or register.
+    Le(u8, Operand),
+    /// Register is greater than or equal to value or register.
+    Ge(u8, Operand),
+    /// Key in register is pressed.
+    Key(u8),
+    /// Key in register is not pressed.
+    NotKey(u8),
+}
+
+impl Condition {
+    /// Get opposite condition.
+    ///
+    /// # Returns
+    /// - Condition holding exactly when this one does not.
+    fn inverse(self) -> Self {
+        match self {
+            Self::Eq(x, y) => Self::Ne(x, y),
+            Self::Ne(x, y) => Self::Eq(x, y),
+            Self::Lt(x, y) => Self::Ge(x, y),
+            Self::Gt(x, y) => Self::Le(x, y),
+            Self::Le(x, y) => Self::Gt(x, y),
+            Self::Ge(x, y) => Self::Lt(x, y),
+            Self::Key(x) => Self::NotKey(x),
+            Self::NotKey(x) => Self::Key(x),
+        }
+    }
+}
+
+/// Right side of register operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operand {
+    /// Register.
+    Register(u8),
+    /// Byte value.
+    Byte(u8),
+}
+
+/// User defined macro.
+#[derive(Debug, Clone)]
+struct Macro {
+    /// Argument names.
+    args: Vec<String>,
+    /// Body tokens.
+    body: Vec<Token>,
+}
+
+/// Octo compiler state.
+struct Assembler {
+    /// Remaining source tokens.
+    tokens: VecDeque<Token>,
+    /// Line of the last taken token.
+    line: usize,
+    /// Compiled program bytes starting at `START_ADDR`.
+    rom: Vec<u8>,
+    /// Current address.
+    here: usize,
+    /// Label addresses.
+    labels: HashMap<String, usize>,
+    /// Constant values.
+    consts: HashMap<String, f64>,
+    /// Register aliases.
+    aliases: HashMap<String, u8>,
+    /// Macros.
+    macros: HashMap<String, Macro>,
+    /// Number of tokens produced by macro expansions.
+    expanded: usize,
+    /// Unresolved label references.
+    fixups: Vec<(usize, String, Fixup, usize)>,
+    /// Open control flow structures.
+    flow: Vec<Flow>,
+}
+
+/// Compile Octo source to program bytes.
+///
+/// # Parameters
+/// - `source` - given Octo source text.
+///
+/// # Returns
+/// - Program data bytes loaded at `START_ADDR` - in case of success.
+/// - `Err`                                    - otherwise.
+pub fn assemble(source: &str) -> EmulatorResult<Vec<u8>> {
+    let mut assembler = Assembler {
+        tokens: tokenize(source),
+        line: 1,
+        rom: Vec::new(),
+        here: START_ADDR,
+        labels: HashMap::new(),
+        consts: HashMap::new(),
+        aliases: HashMap::new(),
+        macros: HashMap::new(),
+        expanded: 0,
+        fixups: Vec::new(),
+        flow: Vec::new(),
+    };
+
+    assembler
+        .compile()
+        .map_err(|error| format!("line {}: {error}", assembler.line))?;
+
+    Ok(assembler.rom)
+}
+
+/// Split source into tokens.
+///
+/// # Parameters
+/// - `source` - given Octo source text.
+///
+/// # Returns
+/// - Whitespace separated tokens without comments.
+fn tokenize(source: &str) -> VecDeque<Token> {
+    let mut tokens = VecDeque::new();
+
+    for (index, line) in source.lines().enumerate() {
+        let mut rest = line.trim_start();
+
+        while !rest.is_empty() && !rest.starts_with('#') {
+            let end = match rest.strip_prefix('"') {
+                Some(string) => string.find('"').map_or(rest.len(), |i| i + 2),
+                None => rest.find(char::is_whitespace).unwrap_or(rest.len()),
+            };
+
+            tokens.push_back(Token {
+                text: rest[..end].to_string(),
+                line: index + 1,
+            });
+            rest = rest[end..].trim_start();
+        }
+    }
+
+    tokens
+}
+
+/// Parse number literal.
+///
+/// # Parameters
+/// - `text` - given decimal, hexadecimal (0x) or binary (0b) literal.
+///
+/// # Returns
+/// - Number value - in case of success.
+/// - `None`       - otherwise.
+fn parse_number(text: &str) -> Option<f64> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text),
+    };
+
+    let value = if let Some(hex) = digits.strip_prefix("0x") {
+        i64::from_str_radix(hex, 16).ok()? as f64
+    } else if let Some(binary) = digits.strip_prefix("0b") {
+        i64::from_str_radix(binary, 2).ok()? as f64
+    } else if digits.starts_with(|c: char| c.is_ascii_digit()) {
+        digits.parse().ok()?
+    } else {
+        return None;
+    };
+
+    Some(if negative { -value } else { value })
+}
+
+impl Assembler {
+    /// Compile all tokens and resolve references.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn compile(&mut self) -> EmulatorResult<()> {
+        // Reserve entry jump to main.
+        self.instruction(0x1000)?;
+
+        while let Some(token) = self.next_token() {
+            self.statement(&token)?;
+        }
+
+        if let Some(flow) = self.flow.last() {
+            let name = match flow {
+                Flow::Loop(..) => "loop",
+                _ => "if",
+            };
+
+            return Err(format!("unterminated '{name}'"));
+        }
+
+        let Some(&main) = self.labels.get("main") else {
+            return Err("program is missing 'main' label".to_string());
+        };
+
+        self.patch_jump(START_ADDR, main);
+
+        for (addr, name, kind, line) in std::mem::take(&mut self.fixups) {
+            self.line = line;
+
+            let Some(&target) = self.labels.get(&name) else {
+                return Err(format!("undefined name '{name}'"));
+            };
+
+            self.patch(addr, target, kind)?;
+        }
+
+        Ok(())
+    }
+
+    /// Compile single statement.
+    ///
+    /// # Parameters
+    /// - `token` - given first statement token.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn statement(&mut self, token: &Token) -> EmulatorResult<()> {
+        if let Some(value) = parse_number(&token.text) {
+            self.byte(value as i64 as u8)?;
+            return Ok(());
+        }
+
+        match token.text.as_str() {
+            ":" => {
+                let name = self.name()?;
+                self.define_label(name, self.here)?;
+            }
+            ":next" => {
+                let name = self.name()?;
+                self.define_label(name, self.here + 1)?;
+            }
+            ":const" => {
+                let name = self.name()?;
+                let value = self.constant()?;
+                self.consts.insert(name, value);
+            }
+            ":alias" => {
+                let name = self.name()?;
+                let register = self.register()?;
+                self.aliases.insert(name, register);
+            }
+            ":unpack" => {
+                let nibble = (self.constant()? as i64 as u16 & 0xF) << 4;
+                let token = self.take()?;
+                let addr = match self.value(&token.text) {
+                    Some(addr) => addr as i64 as u16,
+                    None => {
+                        let fixups = [
+                            (self.here, Fixup::UnpackHigh),
+                            (self.here + 2, Fixup::UnpackLow),
+                        ];
+
+                        for (addr, kind) in fixups {
+                            let name = token.text.clone();
+                            self.fixups.push((addr, name, kind, self.line));
+                        }
+
+                        0
+                    }
+                };
+
+                self.instruction(0x6000 | nibble | addr >> 8 & 0xF)?;
+                self.instruction(0x6100 | addr & 0xFF)?;
+            }
+            ":org" => {
+                let addr = self.constant()? as i64;
+
+                if !(START_ADDR as i64..=0xFFFF).contains(&addr) {
+                    return Err(format!("':org' address {addr:#X} is invalid"));
+                }
+
+                self.here = addr as usize;
+            }
+            ":byte" => {
+                let value = if self.peek("{") {
+                    self.calc()?
+                } else {
+                    self.constant()?
+                };
+                self.byte(value as i64 as u8)?;
+            }
+            ":pointer" => {
+                let addr = self.address(Fixup::Long, 0)?;
+                self.instruction(addr as u16)?;
+            }
+            ":call" => {
+                let addr = self.address(Fixup::Addr, 0)?;
+                self.instruction(0x2000 | addr as u16)?;
+            }
+            ":macro" => self.define_macro()?,
+            ":calc" => {
+                let name = self.name()?;
+                let value = self.calc()?;
+                self.consts.insert(name, value);
+            }
+            ":breakpoint" => {
+                self.take()?;
+            }
+            ":monitor" => {
+                self.take()?;
+                self.take()?;
+            }
+            ":assert" => {
+                let message = match self.tokens.front() {
+                    Some(token) if token.text.starts_with('"') => {
+                        self.take()?.text
+                    }
+                    _ => "assertion failed".to_string(),
+                };
+
+                if self.calc()? == 0.0 {
+                    return Err(message.trim_matches('"').to_string());
+                }
+            }
+            ";" | "return" => self.instruction(0x00EE)?,
+            "clear" => self.instruction(0x00E0)?,
+            "hires" => self.instruction(0x00FF)?,
+            "lores" => self.instruction(0x00FE)?,
+            "exit" => self.instruction(0x00FD)?,
+            "scroll-left" => self.instruction(0x00FC)?,
+            "scroll-right" => self.instruction(0x00FB)?,
+            "scroll-down" => {
+                let count = self.nibble()?;
+                self.instruction(0x00C0 | count)?;
+            }
+            "scroll-up" => {
+                let count = self.nibble()?;
+                self.instruction(0x00D0 | count)?;
+            }
+            "audio" => self.instruction(0xF002)?,
+            "plane" => {
+                let plane = self.nibble()?;
+                self.instruction(0xF001 | plane << 8)?;
+            }
+            "bcd" => self.register_instruction(0xF033)?,
+            "saveflags" => self.register_instruction(0xF075)?,
+            "loadflags" => self.register_instruction(0xF085)?,
+            "save" | "load" => self.memory_instruction(&token.text)?,
+            "sprite" => {
+                let x = self.register()? as u16;
+                let y = self.register()? as u16;
+                let height = self.nibble()?;
+
+                self.instruction(0xD000 | x << 8 | y << 4 | height)?;
+            }
+            "jump" => {
+                let addr = self.address(Fixup::Addr, 0)?;
+                self.instruction(0x1000 | addr as u16)?;
+            }
+            "jump0" => {
+                let addr = self.address(Fixup::Addr, 0)?;
+                self.instruction(0xB000 | addr as u16)?;
+            }
+            "native" => {
+                let addr = self.address(Fixup::Addr, 0)?;
+                self.instruction(addr as u16)?;
+            }
+            "delay" | "buzzer" | "pitch" => {
+                self.expect(":=")?;
+
+                let x = self.register()? as u16;
+                let low = match token.text.as_str() {
+                    "delay" => 0x15,
+                    "buzzer" => 0x18,
+                    _ => 0x3A,
+                };
+
+                self.instruction(0xF000 | x << 8 | low)?;
+            }
+            "i" => self.index_statement()?,
+            "if" => self.if_statement()?,
+            "else" => match self.flow.pop() {
+                Some(Flow::If(jump)) => {
+                    let skip = self.here;
+
+                    self.instruction(0x1000)?;
+                    self.patch_jump(jump, self.here);
+                    self.flow.push(Flow::Else(skip));
+                }
+                _ => return Err("'else' without 'if ... begin'".to_string()),
+            },
+            "end" => match self.flow.pop() {
+                Some(Flow::If(jump) | Flow::Else(jump)) => {
+                    self.patch_jump(jump, self.here)
+                }
+                _ => return Err("'end' without 'if ... begin'".to_string()),
+            },
+            "loop" => self.flow.push(Flow::Loop(self.here, Vec::new())),
+            "while" => {
+                let condition = self.condition()?;
+                self.skip_unless(condition.inverse())?;
+
+                let jump = self.here;
+                self.instruction(0x1000)?;
+
+                match self.flow.iter_mut().rev().find_map(|flow| match flow {
+                    Flow::Loop(_, exits) => Some(exits),
+                    _ => None,
+                }) {
+                    Some(exits) => exits.push(jump),
+                    None => return Err("'while' without 'loop'".to_string()),
+                }
+            }
+            "again" => match self.flow.pop() {
+                Some(Flow::Loop(start, exits)) => {
+                    self.instruction(0x1000 | start as u16)?;
+
+                    for exit in exits {
+                        self.patch_jump(exit, self.here);
+                    }
+                }
+                _ => return Err("'again' without 'loop'".to_string()),
+            },
+            text => {
+                if let Some(register) = self.lookup_register(text) {
+                    return self.register_statement(register);
+                }
+
+                if let Some(body) = self.macros.get(text).cloned() {
+                    return self.expand(body);
+                }
+
+                if text.starts_with(':') || self.consts.contains_key(text) {
+                    return Err(format!("unexpected '{text}'"));
+                }
+
+                // Other names are calls of (possibly forward) labels.
+                self.tokens.push_front(token.clone());
+
+                let addr = self.address(Fixup::Addr, 0)?;
+                self.instruction(0x2000 | addr as u16)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Compile register operation statement.
+    ///
+    /// # Parameters
+    /// - `x` - given target register.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn register_statement(&mut self, x: u8) -> EmulatorResult<()> {
+        let x = x as u16;
+        let operator = self.take()?.text;
+
+        if operator == ":=" {
+            match self.tokens.front().map(|token| token.text.as_str()) {
+                Some("delay") => {
+                    self.take()?;
+                    self.instruction(0xF007 | x << 8)?;
+                    return Ok(());
+                }
+                Some("key") => {
+                    self.take()?;
+                    self.instruction(0xF00A | x << 8)?;
+                    return Ok(());
+                }
+                Some("random") => {
+                    self.take()?;
+                    let mask = self.byte_value()? as u16;
+                    self.instruction(0xC000 | x << 8 | mask)?;
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+
+        let operand = self.operand()?;
+        let opcode = match (operator.as_str(), operand) {
+            (":=", Operand::Byte(byte)) => 0x6000 | byte as u16,
+            ("+=", Operand::Byte(byte)) => 0x7000 | byte as u16,
+            ("-=", Operand::Byte(byte)) => {
+                0x7000 | (byte as u16).wrapping_neg() & 0xFF
+            }
+            (_, Operand::Register(y)) => {
+                let low = match operator.as_str() {
+                    ":=" => 0x0,
+                    "|=" => 0x1,
+                    "&=" => 0x2,
+                    "^=" => 0x3,
+                    "+=" => 0x4,
+                    "-=" => 0x5,
+                    ">>=" => 0x6,
+                    "=-" => 0x7,
+                    "<<=" => 0xE,
+                    _ => return Err(format!("unknown operator '{operator}'")),
+                };
+
+                0x8000 | (y as u16) << 4 | low
+            }
+            _ => {
+                return Err(format!(
+                    "operator '{operator}' requires register operand"
+                ));
+            }
+        };
+
+        self.instruction(opcode | x << 8)?;
+        Ok(())
+    }
+
+    /// Compile `i` register statement.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn index_statement(&mut self) -> EmulatorResult<()> {
+        let operator = self.take()?.text;
+
+        match operator.as_str() {
+            "+=" => self.register_instruction(0xF01E),
+            ":=" => match self.tokens.front().map(|t| t.text.as_str()) {
+                Some("hex") => {
+                    self.take()?;
+                    self.register_instruction(0xF029)
+                }
+                Some("bighex") => {
+                    self.take()?;
+                    self.register_instruction(0xF030)
+                }
+                Some("long") => {
+                    self.take()?;
+                    self.instruction(0xF000)?;
+
+                    let addr = self.address(Fixup::Long, 0)?;
+                    self.instruction(addr as u16)?;
+                    Ok(())
+                }
+                _ => {
+                    let addr = self.address(Fixup::Addr, 0)?;
+                    self.instruction(0xA000 | addr as u16)?;
+                    Ok(())
+                }
+            },
+            _ => Err(format!("unknown operator 'i {operator}'")),
+        }
+    }
+
+    /// Compile `if` statement.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn if_statement(&mut self) -> EmulatorResult<()> {
+        let condition = self.condition()?;
+
+        match self.take()?.text.as_str() {
+            "then" => {
+                self.skip_unless(condition)?;
+            }
+            "begin" => {
+                self.skip_unless(condition.inverse())?;
+                self.flow.push(Flow::If(self.here));
+                self.instruction(0x1000)?;
+            }
+            other => {
+                return Err(format!(
+                    "expected 'then' or 'begin', got '{other}'"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parse register condition.
+    ///
+    /// # Returns
+    /// - Condition - in case of success.
+    /// - `Err`     - otherwise.
+    fn condition(&mut self) -> EmulatorResult<Condition> {
+        let x = self.register()?;
+        let operator = self.take()?.text;
+
+        let condition = match operator.as_str() {
+            "key" => Condition::Key(x),
+            "-key" => Condition::NotKey(x),
+            "==" => Condition::Eq(x, self.operand()?),
+            "!=" => Condition::Ne(x, self.operand()?),
+            "<" => Condition::Lt(x, self.operand()?),
+            ">" => Condition::Gt(x, self.operand()?),
+            "<=" => Condition::Le(x, self.operand()?),
+            ">=" => Condition::Ge(x, self.operand()?),
+            _ => return Err(format!("unknown condition '{operator}'")),
+        };
+
+        Ok(condition)
+    }
+
+    /// Emit instructions skipping next instruction unless condition holds.
+    ///
+    /// Comparisons compute borrow flag in VF first.
+    ///
+    /// # Parameters
+    /// - `condition` - given condition.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - if instructions do not fit in 64K memory.
+    fn skip_unless(&mut self, condition: Condition) -> EmulatorResult<()> {
+        let reg = |x: u8| (x as u16) << 8;
+
+        match condition {
+            Condition::Eq(x, Operand::Byte(byte)) => {
+                self.instruction(0x4000 | reg(x) | byte as u16)?
+            }
+            Condition::Eq(x, Operand::Register(y)) => {
+                self.instruction(0x9000 | reg(x) | (y as u16) << 4)?
+            }
+            Condition::Ne(x, Operand::Byte(byte)) => {
+                self.instruction(0x3000 | reg(x) | byte as u16)?
+            }
+            Condition::Ne(x, Operand::Register(y)) => {
+                self.instruction(0x5000 | reg(x) | (y as u16) << 4)?
+            }
+            Condition::Key(x) => self.instruction(0xE0A1 | reg(x))?,
+            Condition::NotKey(x) => self.instruction(0xE09E | reg(x))?,
+            Condition::Lt(x, y) | Condition::Ge(x, y) => {
+                // VF = 1 if x >= y.
+                match y {
+                    Operand::Register(y) => {
+                        self.instruction(0x8F00 | (x as u16) << 4)?;
+                        self.instruction(0x8F05 | (y as u16) << 4)?;
+                    }
+                    Operand::Byte(byte) => {
+                        self.instruction(0x6F00 | byte as u16)?;
+                        self.instruction(0x8F07 | (x as u16) << 4)?;
+                    }
+                }
+
+                let holds = matches!(condition, Condition::Ge(..));
+                self.instruction(if holds { 0x3F00 } else { 0x4F00 })?;
+            }
+            Condition::Gt(x, y) | Condition::Le(x, y) => {
+                // VF = 1 if y >= x.
+                match y {
+                    Operand::Register(y) => {
+                        self.instruction(0x8F00 | (y as u16) << 4)?;
+                        self.instruction(0x8F05 | (x as u16) << 4)?;
+                    }
+                    Operand::Byte(byte) => {
+                        self.instruction(0x6F00 | byte as u16)?;
+                        self.instruction(0x8F05 | (x as u16) << 4)?;
+                    }
+                }
+
+                let holds = matches!(condition, Condition::Le(..));
+                self.instruction(if holds { 0x3F00 } else { 0x4F00 })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Compile `save` or `load` statement with optional register range.
+    ///
+    /// # Parameters
+    /// - `name` - given statement name.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn memory_instruction(&mut self, name: &str) -> EmulatorResult<()> {
+        let x = self.register()? as u16;
+
+        if self.peek("-") {
+            self.take()?;
+
+            let y = self.register()? as u16;
+            let low = if name == "save" { 2 } else { 3 };
+
+            self.instruction(0x5000 | x << 8 | y << 4 | low)?;
+        } else {
+            let low = if name == "save" { 0x55 } else { 0x65 };
+            self.instruction(0xF000 | x << 8 | low)?;
+        }
+
+        Ok(())
+    }
+
+    /// Compile instruction with single register operand.
+    ///
+    /// # Parameters
+    /// - `opcode` - given opcode with zero register nibble.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn register_instruction(&mut self, opcode: u16) -> EmulatorResult<()> {
+        let x = self.register()? as u16;
+
+        self.instruction(opcode | x << 8)?;
+        Ok(())
+    }
+
+    /// Define macro.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn define_macro(&mut self) -> EmulatorResult<()> {
+        let name = self.name()?;
+        let mut args = Vec::new();
+
+        while !self.peek("{") {
+            args.push(self.name()?);
+        }
+
+        let body = self.block()?;
+        self.macros.insert(name, Macro { args, body });
+
+        Ok(())
+    }
+
+    /// Expand macro invocation.
+    ///
+    /// # Parameters
+    /// - `definition` - given macro definition.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn expand(&mut self, definition: Macro) -> EmulatorResult<()> {
+        self.expanded += definition.body.len().max(1);
+
+        if self.expanded > MAX_EXPANDED_TOKENS {
+            return Err(format!(
+                "macro expansion exceeds {MAX_EXPANDED_TOKENS} tokens"
+            ));
+        }
+
+        let mut values = HashMap::new();
+
+        for arg in &definition.args {
+            values.insert(arg.clone(), self.take()?.text);
+        }
+
+        for token in definition.body.into_iter().rev() {
+            let text = values.get(&token.text).cloned().unwrap_or(token.text);
+
+            self.tokens.push_front(Token {
+                text,
+                line: self.line,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Take tokens enclosed in braces.
+    ///
+    /// # Returns
+    /// - Tokens without outer braces - in case of success.
+    /// - `Err`                       - otherwise.
+    fn block(&mut self) -> EmulatorResult<Vec<Token>> {
+        self.expect("{")?;
+
+        let mut depth = 1;
+        let mut body = Vec::new();
+
+        loop {
+            let token = self.take()?;
+
+            match token.text.as_str() {
+                "{" => depth += 1,
+                "}" => depth -= 1,
+                _ => {}
+            }
+
+            if depth == 0 {
+                return Ok(body);
+            }
+
+            body.push(token);
+        }
+    }
+
+    /// Evaluate `:calc` expression in braces.
+    ///
+    /// # Returns
+    /// - Expression value - in case of success.
+    /// - `Err`            - otherwise.
+    fn calc(&mut self) -> EmulatorResult<f64> {
+        let block = self.block()?;
+        let tokens: Vec<&str> =
+            block.iter().map(|token| token.text.as_str()).collect();
+        let mut position = 0;
+        let value = self.expression(&tokens, &mut position)?;
+
+        match tokens.get(position) {
+            Some(token) => Err(format!("unexpected '{token}' in expression")),
+            None => Ok(value),
+        }
+    }
+
+    /// Evaluate expression right to left without operator precedence.
+    ///
+    /// # Parameters
+    /// - `tokens`   - given expression tokens.
+    /// - `position` - given position of the next token.
+    ///
+    /// # Returns
+    /// - Expression value - in case of success.
+    /// - `Err`            - otherwise.
+    fn expression(
+        &self,
+        tokens: &[&str],
+        position: &mut usize,
+    ) -> EmulatorResult<f64> {
+        let left = self.term(tokens, position)?;
+
+        let Some(&operator) = tokens.get(*position).filter(|&&t| t != ")")
+        else {
+            return Ok(left);
+        };
+
+        *position += 1;
+
+        let right = self.expression(tokens, position)?;
+        let int = |value: f64| value as i64;
+
+        let value = match operator {
+            "+" => left + right,
+            "-" => left - right,
+            "*" => left * right,
+            "/" => left / right,
+            "%" => left % right,
+            "&" => (int(left) & int(right)) as f64,
+            "|" => (int(left) | int(right)) as f64,
+            "^" => (int(left) ^ int(right)) as f64,
+            "<<" => (int(left) << int(right)) as f64,
+            ">>" => (int(left) >> int(right)) as f64,
+            "pow" => left.powf(right),
+            "min" => left.min(right),
+            "max" => left.max(right),
+            "<" => (left < right) as u8 as f64,
+            ">" => (left > right) as u8 as f64,
+            "<=" => (left <= right) as u8 as f64,
+            ">=" => (left >= right) as u8 as f64,
+            "==" => (left == right) as u8 as f64,
+            "!=" => (left != right) as u8 as f64,
+            _ => return Err(format!("unknown operator '{operator}'")),
+        };
+
+        Ok(value)
+    }
+
+    /// Evaluate expression term.
+    ///
+    /// # Parameters
+    /// - `tokens`   - given expression tokens.
+    /// - `position` - given position of the next token.
+    ///
+    /// # Returns
+    /// - Term value - in case of success.
+    /// - `Err`      - otherwise.
+    fn term(
+        &self,
+        tokens: &[&str],
+        position: &mut usize,
+    ) -> EmulatorResult<f64> {
+        let Some(&token) = tokens.get(*position) else {
+            return Err("unexpected end of expression".to_string());
+        };
+
+        *position += 1;
+
+        if token == "(" {
+            let value = self.expression(tokens, position)?;
+
+            if tokens.get(*position) != Some(&")") {
+                return Err("expected ')'".to_string());
+            }
+
+            *position += 1;
+            return Ok(value);
+        }
+
+        if UNARY_OPERATORS.contains(&token) {
+            let value = self.term(tokens, position)?;
+
+            return Ok(match token {
+                "-" => -value,
+                "~" => !(value as i64) as f64,
+                "!" => (value == 0.0) as u8 as f64,
+                "sin" => value.sin(),
+                "cos" => value.cos(),
+                "tan" => value.tan(),
+                "exp" => value.exp(),
+                "log" => value.ln(),
+                "abs" => value.abs(),
+                "sqrt" => value.sqrt(),
+                "sign" => value.signum(),
+                "ceil" => value.ceil(),
+                "floor" => value.floor(),
+                _ => {
+                    // Byte of compiled program at address.
+                    let byte = (value as usize)
+                        .checked_sub(START_ADDR)
+                        .and_then(|offset| self.rom.get(offset));
+
+                    byte.copied().unwrap_or(0) as f64
+                }
+            });
+        }
+
+        self.value(token)
+            .ok_or_else(|| format!("undefined name '{token}' in expression"))
+    }
+
+    /// Get value of known number, constant or label.
+    ///
+    /// # Parameters
+    /// - `text` - given token text.
+    ///
+    /// # Returns
+    /// - Value - if it is known.
+    /// - `None` - otherwise.
+    fn value(&self, text: &str) -> Option<f64> {
+        match text {
+            "HERE" => Some(self.here as f64),
+            "PI" => Some(std::f64::consts::PI),
+            "E" => Some(std::f64::consts::E),
+            _ => parse_number(text)
+                .or_else(|| self.consts.get(text).copied())
+                .or_else(|| self.labels.get(text).map(|&addr| addr as f64)),
+        }
+    }
+
+    /// Take constant value.
+    ///
+    /// # Returns
+    /// - Value - in case of success.
+    /// - `Err` - otherwise.
+    fn constant(&mut self) -> EmulatorResult<f64> {
+        let token = self.take()?;
+
+        self.value(&token.text)
+            .ok_or_else(|| format!("undefined name '{}'", token.text))
+    }
+
+    /// Take byte value.
+    ///
+    /// # Returns
+    /// - Byte - in case of success.
+    /// - `Err` - otherwise.
+    fn byte_value(&mut self) -> EmulatorResult<u8> {
+        let value = self.constant()? as i64;
+
+        if !(-128..=255).contains(&value) {
+            return Err(format!("value {value} does not fit in byte"));
+        }
+
+        Ok(value as u8)
+    }
+
+    /// Take nibble value.
+    ///
+    /// # Returns
+    /// - Nibble - in case of success.
+    /// - `Err`  - otherwise.
+    fn nibble(&mut self) -> EmulatorResult<u16> {
+        let value = self.constant()? as i64;
+
+        if !(0..=0xF).contains(&value) {
+            return Err(format!("value {value} does not fit in nibble"));
+        }
+
+        Ok(value as u16)
+    }
+
+    /// Take register or byte operand.
+    ///
+    /// # Returns
+    /// - Operand - in case of success.
+    /// - `Err`   - otherwise.
+    fn operand(&mut self) -> EmulatorResult<Operand> {
+        let register = self
+            .tokens
+            .front()
+            .and_then(|token| self.lookup_register(&token.text));
+
+        match register {
+            Some(register) => {
+                self.take()?;
+                Ok(Operand::Register(register))
+            }
+            None => Ok(Operand::Byte(self.byte_value()?)),
+        }
+    }
+
+    /// Take address, recording reference to label defined later.
+    ///
+    /// # Parameters
+    /// - `kind`   - given reference kind.
+    /// - `offset` - given offset of patched bytes from current address.
+    ///
+    /// # Returns
+    /// - Address or zero for forward reference - in case of success.
+    /// - `Err`                                 - otherwise.
+    fn address(&mut self, kind: Fixup, offset: usize) -> EmulatorResult<usize> {
+        let token = self.take()?;
+
+        if let Some(value) = self.value(&token.text) {
+            let addr = value as i64 as usize;
+
+            if kind == Fixup::Addr && addr > 0xFFF {
+                return Err(format!("address {addr:#X} is out of range"));
+            }
+
+            return Ok(addr);
+        }
+
+        if parse_number(&token.text).is_none()
+            && self.lookup_register(&token.text).is_none()
+        {
+            let addr = self.here + offset;
+            self.fixups.push((addr, token.text, kind, self.line));
+            return Ok(0);
+        }
+
+        Err(format!("expected address, got '{}'", token.text))
+    }
+
+    /// Take register name.
+    ///
+    /// # Returns
+    /// - Register index - in case of success.
+    /// - `Err`          - otherwise.
+    fn register(&mut self) -> EmulatorResult<u8> {
+        let token = self.take()?;
+
+        self.lookup_register(&token.text)
+            .ok_or_else(|| format!("expected register, got '{}'", token.text))
+    }
+
+    /// Get register by name or alias.
+    ///
+    /// # Parameters
+    /// - `text` - given token text.
+    ///
+    /// # Returns
+    /// - Register index - if token names register.
+    /// - `None`         - otherwise.
+    fn lookup_register(&self, text: &str) -> Option<u8> {
+        if let Some(&register) = self.aliases.get(text) {
+            return Some(register);
+        }
+
+        let digit = text.strip_prefix(['v', 'V'])?;
+
+        match digit.len() {
+            1 => u8::from_str_radix(digit, 16).ok(),
+            _ => None,
+        }
+    }
+
+    /// Take new name.
+    ///
+    /// # Returns
+    /// - Name - in case of success.
+    /// - `Err` - otherwise.
+    fn name(&mut self) -> EmulatorResult<String> {
+        let token = self.take()?;
+
+        if parse_number(&token.text).is_some()
+            || self.lookup_register(&token.text).is_some()
+        {
+            return Err(format!("'{}' is not a valid name", token.text));
+        }
+
+        Ok(token.text)
+    }
+
+    /// Define label.
+    ///
+    /// # Parameters
+    /// - `name` - given label name.
+    /// - `addr` - given label address.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - if label is already defined.
+    fn define_label(
+        &mut self,
+        name: String,
+        addr: usize,
+    ) -> EmulatorResult<()> {
+        if self.labels.contains_key(&name) {
+            return Err(format!("label '{name}' is already defined"));
+        }
+
+        self.labels.insert(name, addr);
+        Ok(())
+    }
+
+    /// Take next token.
+    ///
+    /// # Returns
+    /// - Token - if source is not finished.
+    /// - `None` - otherwise.
+    fn next_token(&mut self) -> Option<Token> {
+        let token = self.tokens.pop_front()?;
+        self.line = token.line;
+
+        Some(token)
+    }
+
+    /// Take next token required by statement.
+    ///
+    /// # Returns
+    /// - Token - in case of success.
+    /// - `Err` - if source is finished.
+    fn take(&mut self) -> EmulatorResult<Token> {
+        self.next_token()
+            .ok_or_else(|| "unexpected end of source".to_string())
+    }
+
+    /// Check whether next token has specific text.
+    ///
+    /// # Parameters
+    /// - `text` - given expected token text.
+    ///
+    /// # Returns
+    /// - `true` - if next token matches.
+    fn peek(&self, text: &str) -> bool {
+        self.tokens.front().is_some_and(|token| token.text == text)
+    }
+
+    /// Take token with specific text.
+    ///
+    /// # Parameters
+    /// - `text` - given expected token text.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn expect(&mut self, text: &str) -> EmulatorResult<()> {
+        let token = self.take()?;
+
+        if token.text != text {
+            return Err(format!("expected '{text}', got '{}'", token.text));
+        }
+
+        Ok(())
+    }
+
+    /// Emit byte at current address.
+    ///
+    /// # Parameters
+    /// - `byte` - given byte.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - if current address is outside of 64K memory.
+    fn byte(&mut self, byte: u8) -> EmulatorResult<()> {
+        if self.here > 0xFFFF {
+            return Err(format!(
+                "address {:#X} is outside of memory",
+                self.here
+            ));
+        }
+
+        let offset = self.here - START_ADDR;
+
+        if offset >= self.rom.len() {
+            self.rom.resize(offset + 1, 0);
+        }
+
+        self.rom[offset] = byte;
+        self.here += 1;
+
+        Ok(())
+    }
+
+    /// Emit two bytes instruction.
+    ///
+    /// # Parameters
+    /// - `opcode` - given instruction opcode.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - if instruction does not fit in 64K memory.
+    fn instruction(&mut self, opcode: u16) -> EmulatorResult<()> {
+        let [high, low] = opcode.to_be_bytes();
+
+        self.byte(high)?;
+        self.byte(low)
+    }
+
+    /// Set target of jump instruction.
+    ///
+    /// # Parameters
+    /// - `addr`   - given jump instruction address.
+    /// - `target` - given jump target address.
+    fn patch_jump(&mut self, addr: usize, target: usize) {
+        let offset = addr - START_ADDR;
+        let [high, low] = (0x1000 | target as u16 & 0xFFF).to_be_bytes();
+
+        self.rom[offset] = high;
+        self.rom[offset + 1] = low;
+    }
+
+    /// Resolve label reference.
+    ///
+    /// # Parameters
+    /// - `addr`   - given address of patched bytes.
+    /// - `target` - given label address.
+    /// - `kind`   - given reference kind.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - if address is out of range.
+    fn patch(
+        &mut self,
+        addr: usize,
+        target: usize,
+        kind: Fixup,
+    ) -> EmulatorResult<()> {
+        let offset = addr - START_ADDR;
+
+        match kind {
+            Fixup::Addr => {
+                if target > 0xFFF {
+                    return Err(format!("address {target:#X} is out of range"));
+                }
+
+                self.rom[offset] |= (target >> 8) as u8;
+                self.rom[offset + 1] = target as u8;
+            }
+            Fixup::Long => {
+                self.rom[offset] = (target >> 8) as u8;
+                self.rom[offset + 1] = target as u8;
+            }
+            Fixup::UnpackHigh => self.rom[offset + 1] |= (target >> 8) as u8,
+            Fixup::UnpackLow => self.rom[offset + 1] = target as u8,
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::emulator::{cpu::Cpu, quirks::Quirks};
+
+    /// Assemble program and run it until it reaches endless loop.
+    ///
+    /// # Parameters
+    /// - `source` - given Octo source text.
+    ///
+    /// # Returns
+    /// - CPU after running the program.
+    fn run(source: &str) -> Cpu {
+        let mut cpu = Cpu::with_quirks(Quirks::default());
+        cpu.load_program(&assemble(source).unwrap()).unwrap();
+
+        for _ in 0..1000 {
+            cpu.step().unwrap();
+        }
+
+        cpu
+    }
+
+    #[test]
+    fn test_assemble() {
+        let source = "
+            :const SIZE 5
+            :alias counter v3
+            : main
+                i := sprite
+                counter := SIZE
+                draw
+                loop again
+            : draw sprite v0 v1 SIZE ;
+            : sprite 0xF0 0b10010000 0x90 0x90 0xF0
+        ";
+
+        assert_eq!(
+            vec![
+                0x12, 0x02, 0xA2, 0x0E, 0x63, 0x05, 0x22, 0x0A, 0x12, 0x08,
+                0xD0, 0x15, 0x00, 0xEE, 0xF0, 0x90, 0x90, 0x90, 0xF0,
+            ],
+            assemble(source).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_directives() {
+        let source = "
+            :macro twice op { op op }
+            :calc DOUBLE { 2 * ( 3 + 4 ) }
+            : main
+                twice clear
+                :unpack 0xA data
+                i := long data
+                :org 0x300
+            : data :byte DOUBLE :byte { DOUBLE - 1 }
+        ";
+        let rom = assemble(source).unwrap();
+
+        assert_eq!([0x00, 0xE0, 0x00, 0xE0], rom[2..6]);
+        assert_eq!([0x60, 0xA3, 0x61, 0x00], rom[6..10]);
+        assert_eq!([0xF0, 0x00, 0x03, 0x00], rom[10..14]);
+        assert_eq!([14, 13], rom[0x100..]);
+    }
+
+    #[test]
+    fn test_control_flow() {
+        // V1 counts loop iterations, V2..V5 record comparison outcomes.
+        let cpu = run("
+            : main
+                loop
+                    v1 += 1
+                    while v1 < 5
+                again
+                if v1 >= 5 then v2 := 1
+                if v1 > 5 begin v3 := 1 else v3 := 2 end
+                v0 := 5
+                if v1 <= v0 then v4 := 1
+                if v1 != 5 then v5 := 1
+                loop again
+        ");
+
+        assert_eq!([5, 1, 2, 1, 0], cpu.registers()[1..6]);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(assemble("clear").unwrap_err().contains("main"));
+        assert!(assemble(": main loop").unwrap_err().contains("loop"));
+        assert!(assemble(": main jump nowhere").is_err());
+        assert!(
+            assemble(": main\n v0 := 256")
+                .unwrap_err()
+                .starts_with("line 2")
+        );
+        assert!(assemble(": main : main").is_err());
+
+        // Recursive macro and code past the end of 64K memory.
+        let recursive = assemble(":macro m { m }\n: main m").unwrap_err();
+        assert!(recursive.contains("macro expansion"));
+        assert!(assemble(": main :org 0xFFFE 1 2 3 4").is_err());
+        assert!(assemble(": main :org 0xFFFE 1 2").is_ok());
+    }
+}
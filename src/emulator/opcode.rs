@@ -6,6 +6,7 @@
 use crate::emulator::disasm::Decodable;
 
 /// CHIP-8 opcode struct.
+#[derive(Debug, Clone, Copy)]
 pub struct OpCode {
     /// Opcode raw bytes.
     pub raw: u16,
@@ -69,7 +70,7 @@ impl OpCode {
         match self.raw {
             0x00E0 => "CLS".to_string(),
             0x00EE => "RET".to_string(),
-            _ => format!("SYS {addr:#03X}"),
+            _ => format!("SYS {addr:03X}"),
         }
     }
 
@@ -81,10 +82,10 @@ impl OpCode {
         let addr = self.addr;
 
         match self.class {
-            0x1 => format!("JP {addr:#03X}"),
-            0x2 => format!("CALL {addr:#03X}"),
-            0xA => format!("LD I, {addr:#03X}"),
-            0xB => format!("JP V0, {addr:#03X}"),
+            0x1 => format!("JP {addr:03X}"),
+            0x2 => format!("CALL {addr:03X}"),
+            0xA => format!("LD I, {addr:03X}"),
+            0xB => format!("JP V0, {addr:03X}"),
             _ => self.unknown(),
         }
     }
@@ -98,11 +99,11 @@ impl OpCode {
         let byte = self.byte;
 
         match self.class {
-            0x3 => format!("SE V{reg_x}, {byte:#02X}"),
-            0x4 => format!("SNE V{reg_x}, {byte:#02X}"),
-            0x6 => format!("LD V{reg_x}, {byte:#02X}"),
-            0x7 => format!("ADD V{reg_x}, {byte:#02X}"),
-            0xC => format!("RND V{reg_x}, {byte:#02X}"),
+            0x3 => format!("SE V{reg_x}, {byte:02X}"),
+            0x4 => format!("SNE V{reg_x}, {byte:02X}"),
+            0x6 => format!("LD V{reg_x}, {byte:02X}"),
+            0x7 => format!("ADD V{reg_x}, {byte:02X}"),
+            0xC => format!("RND V{reg_x}, {byte:02X}"),
             _ => self.unknown(),
         }
     }
@@ -137,7 +138,7 @@ impl OpCode {
                 0x0 => format!("SNE V{reg_x}, V{reg_y}"),
                 _ => self.unknown(),
             },
-            0xD => format!("DRW V{reg_x}, V{reg_y}, {nibble:#02X}"),
+            0xD => format!("DRW V{reg_x}, V{reg_y}, {nibble:02X}"),
             _ => self.unknown(),
         }
     }
@@ -164,6 +165,7 @@ impl OpCode {
         let reg_x = self.reg_x;
 
         match self.byte {
+            0x02 if reg_x == 0 => "LD AUDIO, [I]".to_string(),
             0x07 => format!("LD V{reg_x}, DT"),
             0x0A => format!("LD V{reg_x}, K"),
             0x15 => format!("LD DT, V{reg_x}"),
@@ -171,6 +173,7 @@ impl OpCode {
             0x1E => format!("ADD I, V{reg_x}"),
             0x29 => format!("LD F, V{reg_x}"),
             0x33 => format!("LD B, V{reg_x}"),
+            0x3A => format!("LD PITCH, V{reg_x}"),
             0x55 => format!("LD [I], V{reg_x}"),
             0x65 => format!("LD V{reg_x}, [I]"),
             _ => self.unknown(),
@@ -419,6 +422,12 @@ pub mod tests {
         let disasm_str = OpCode::new(0xF665).decode();
         assert_eq!("LD V6, [I]", disasm_str);
 
+        let disasm_str = OpCode::new(0xF002).decode();
+        assert_eq!("LD AUDIO, [I]", disasm_str);
+
+        let disasm_str = OpCode::new(0xF63A).decode();
+        assert_eq!("LD PITCH, V6", disasm_str);
+
         let disasm_str = OpCode::new(0xF6FF).decode();
         assert_eq!("UNKNOWN: F6FF", disasm_str);
     }
@@ -0,0 +1,269 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Instruction-level profiler.
+
+use crate::emulator::{
+    cpu::{Cpu, Observer, RAM_SIZE},
+    disasm::{self, Decodable},
+    opcode::OpCode,
+};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+};
+
+/// Number of hottest addresses displayed in report.
+const HOTSPOTS_COUNT: usize = 16;
+
+/// Subroutine execution statistics.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Subroutine {
+    /// Number of completed calls.
+    pub calls: u64,
+    /// Number of instructions executed inside subroutine including nested
+    /// calls.
+    pub cycles: u64,
+}
+
+/// Instruction-level profiler struct.
+pub struct Profiler {
+    /// Number of executions per memory address.
+    address_hits: Vec<u64>,
+    /// Number of executions per instruction mnemonic.
+    kind_hits: HashMap<String, u64>,
+    /// Statistics per subroutine address.
+    subroutines: BTreeMap<u16, Subroutine>,
+    /// Active calls as (subroutine address, cycle of call) pairs.
+    calls: Vec<(u16, u64)>,
+    /// Stack pointer before executing current instruction.
+    sp: u8,
+    /// Total number of profiled instructions.
+    total: u64,
+}
+
+impl Profiler {
+    /// Construct new `Profiler` object.
+    ///
+    /// # Returns
+    /// - New `Profiler` object.
+    pub fn new() -> Self {
+        Self {
+            address_hits: vec![0; RAM_SIZE],
+            kind_hits: HashMap::new(),
+            subroutines: BTreeMap::new(),
+            calls: Vec::new(),
+            sp: 0,
+            total: 0,
+        }
+    }
+
+    /// Get number of executions of instruction at specified address.
+    ///
+    /// # Parameters
+    /// - `addr` - given instruction address.
+    ///
+    /// # Returns
+    /// - Number of executions.
+    pub fn hits(&self, addr: usize) -> u64 {
+        self.address_hits.get(addr).copied().unwrap_or(0)
+    }
+
+    /// Get statistics of subroutine at specified address.
+    ///
+    /// # Parameters
+    /// - `addr` - given subroutine address.
+    ///
+    /// # Returns
+    /// - Subroutine statistics - if subroutine returned at least once.
+    /// - `None`                - otherwise.
+    pub fn subroutine(&self, addr: u16) -> Option<Subroutine> {
+        self.subroutines.get(&addr).copied()
+    }
+
+    /// Print sorted profiling report.
+    ///
+    /// # Parameters
+    /// - `cpu` - given profiled CPU.
+    pub fn report(&self, cpu: &Cpu) {
+        let total = self.total.max(1) as f64;
+        let percent = |hits: u64| hits as f64 * 100.0 / total;
+
+        println!("Instructions executed: {}", self.total);
+        println!("\nHotspots:");
+
+        let mut hotspots: Vec<(usize, u64)> = self
+            .address_hits
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, hits)| *hits > 0)
+            .collect();
+
+        hotspots.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        for (addr, hits) in hotspots.into_iter().take(HOTSPOTS_COUNT) {
+            let memory = cpu.memory();
+            let raw = u16::from_be_bytes([memory[addr], memory[addr + 1]]);
+            let opcode = OpCode::new(raw).decode();
+            let share = percent(hits);
+
+            println!("  <{addr:#05X}>  {hits:>10}  {share:>6.2}%  {opcode}");
+        }
+
+        println!("\nInstruction kinds:");
+
+        let mut kinds: Vec<(&String, &u64)> = self.kind_hits.iter().collect();
+        kinds.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+
+        for (kind, &hits) in kinds {
+            println!("  {kind:<6}  {hits:>10}  {:>6.2}%", percent(hits));
+        }
+
+        println!("\nSubroutines:");
+
+        let mut subroutines: Vec<(&u16, &Subroutine)> =
+            self.subroutines.iter().collect();
+        subroutines.sort_by_key(|(_, subroutine)| Reverse(subroutine.cycles));
+
+        for (addr, subroutine) in subroutines {
+            let Subroutine { calls, cycles } = *subroutine;
+            let average = cycles as f64 / calls as f64;
+
+            println!(
+                "  <{addr:#05X}>  calls: {calls:>8}  cycles: {cycles:>10} \
+                 ({:>6.2}%)  avg: {average:.1}",
+                percent(cycles)
+            );
+        }
+    }
+
+    /// Print disassembly listing annotated with hit counts.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    /// - `load_addr`    - given program load address.
+    pub fn annotated_listing(&self, program_data: &[u8], load_addr: usize) {
+        println!("\nAnnotated listing:");
+
+        for (addr, opcode) in disasm::listing(program_data, load_addr) {
+            let bytes = opcode.raw;
+            let hits = self.hits(addr);
+            let opcode = opcode.decode();
+
+            println!("<{addr:#05X}>  |{bytes:04X}|  {hits:>10}  {opcode}");
+        }
+    }
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Observer for Profiler {
+    /// Record instruction which is about to be executed.
+    ///
+    /// # Parameters
+    /// - `cpu` - given CPU before executing instruction.
+    fn before_step(&mut self, cpu: &Cpu) {
+        let pc = cpu.pc() as usize;
+        let memory = cpu.memory();
+
+        if pc + 1 >= RAM_SIZE {
+            return;
+        }
+
+        let opcode =
+            OpCode::new(u16::from_be_bytes([memory[pc], memory[pc + 1]]));
+
+        self.address_hits[pc] += 1;
+        *self.kind_hits.entry(mnemonic(&opcode)).or_default() += 1;
+        self.sp = cpu.sp();
+        self.total += 1;
+    }
+
+    /// Record subroutine calls and returns of executed instruction.
+    ///
+    /// # Parameters
+    /// - `cpu` - given CPU after executing instruction.
+    fn after_step(&mut self, cpu: &Cpu) {
+        if cpu.sp() > self.sp {
+            self.calls.push((cpu.pc(), cpu.cycles() - 1));
+        } else if cpu.sp() < self.sp
+            && let Some((addr, start)) = self.calls.pop()
+        {
+            let subroutine = self.subroutines.entry(addr).or_default();
+
+            subroutine.calls += 1;
+            subroutine.cycles += cpu.cycles() - start;
+        }
+    }
+}
+
+/// Get instruction kind name.
+///
+/// # Parameters
+/// - `opcode` - given opcode.
+///
+/// # Returns
+/// - Instruction mnemonic without operands.
+fn mnemonic(opcode: &OpCode) -> String {
+    let decoded = opcode.decode();
+
+    match decoded.split_whitespace().next() {
+        Some("UNKNOWN:") | None => "UNKNOWN".to_string(),
+        Some(mnemonic) => mnemonic.to_string(),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::emulator::{cpu::tests::cpu_with_program, quirks::Quirks};
+
+    /// Run profiler over given CPU.
+    ///
+    /// # Parameters
+    /// - `profiler` - given profiler.
+    /// - `cpu`      - given CPU to run.
+    /// - `count`    - given number of instructions to execute.
+    fn profile(profiler: &mut Profiler, cpu: &mut Cpu, count: usize) {
+        for _ in 0..count {
+            profiler.before_step(cpu);
+            cpu.step().unwrap();
+            profiler.after_step(cpu);
+        }
+    }
+
+    #[test]
+    fn test_address_hits() {
+        let program = [0x6001, 0x7001, 0x1202];
+        let mut cpu = cpu_with_program(&program, Quirks::default());
+        let mut profiler = Profiler::new();
+
+        profile(&mut profiler, &mut cpu, 7);
+
+        assert_eq!(1, profiler.hits(0x200));
+        assert_eq!(3, profiler.hits(0x202));
+        assert_eq!(3, profiler.hits(0x204));
+        assert_eq!(Some(&1), profiler.kind_hits.get("LD"));
+        assert_eq!(Some(&3), profiler.kind_hits.get("ADD"));
+        assert_eq!(Some(&3), profiler.kind_hits.get("JP"));
+    }
+
+    #[test]
+    fn test_subroutines() {
+        // 0x200: CALL 0x206; JP 0x200 (twice); 0x206: LD V0; RET.
+        let program = [0x2206, 0x1200, 0x0000, 0x6001, 0x00EE];
+        let mut cpu = cpu_with_program(&program, Quirks::default());
+        let mut profiler = Profiler::new();
+
+        profile(&mut profiler, &mut cpu, 8);
+
+        let subroutine = profiler.subroutine(0x206).unwrap();
+        assert_eq!(2, subroutine.calls);
+        assert_eq!(6, subroutine.cycles);
+    }
+}
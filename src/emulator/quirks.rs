@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! CHIP-8 interpreter behavior quirks related declarations.
+
+use crate::emulator::EmulatorResult;
+
+/// Names of available quirks presets.
+pub const PRESETS: [&str; 3] = ["chip8", "schip", "xochip"];
+
+/// Names of individual quirks in flags order.
+pub const NAMES: [&str; 6] = [
+    "vf_reset",
+    "memory",
+    "display_wait",
+    "clipping",
+    "shifting",
+    "jumping",
+];
+
+/// Interpreter behavior differences between CHIP-8 platforms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quirks {
+    /// AND, OR and XOR instructions reset VF register to zero.
+    pub vf_reset: bool,
+    /// Fx55 and Fx65 instructions increment I register.
+    pub memory: bool,
+    /// Sprite drawing waits for the vertical blank interrupt.
+    pub display_wait: bool,
+    /// Sprites are clipped at the screen edges instead of wrapping.
+    pub clipping: bool,
+    /// 8xy6 and 8xyE instructions shift Vx in place ignoring Vy.
+    pub shifting: bool,
+    /// Bnnn instruction jumps to nnn + Vx instead of nnn + V0.
+    pub jumping: bool,
+}
+
+impl Quirks {
+    /// Get original COSMAC VIP CHIP-8 interpreter quirks.
+    ///
+    /// # Returns
+    /// - New `Quirks` object.
+    pub const fn chip8() -> Self {
+        Self {
+            vf_reset: true,
+            memory: true,
+            display_wait: true,
+            clipping: true,
+            shifting: false,
+            jumping: false,
+        }
+    }
+
+    /// Get modern SUPER-CHIP interpreter quirks.
+    ///
+    /// # Returns
+    /// - New `Quirks` object.
+    pub const fn schip() -> Self {
+        Self {
+            vf_reset: false,
+            memory: false,
+            display_wait: false,
+            clipping: true,
+            shifting: true,
+            jumping: true,
+        }
+    }
+
+    /// Get XO-CHIP interpreter quirks.
+    ///
+    /// # Returns
+    /// - New `Quirks` object.
+    pub const fn xochip() -> Self {
+        Self {
+            vf_reset: false,
+            memory: true,
+            display_wait: false,
+            clipping: false,
+            shifting: false,
+            jumping: false,
+        }
+    }
+
+    /// Get quirks by preset name.
+    ///
+    /// # Parameters
+    /// - `name` - given preset name.
+    ///
+    /// # Returns
+    /// - Preset quirks - in case of success.
+    /// - `Err`         - otherwise.
+    pub fn from_preset(name: &str) -> EmulatorResult<Self> {
+        match name {
+            "chip8" | "vip" => Ok(Self::chip8()),
+            "schip" => Ok(Self::schip()),
+            "xochip" => Ok(Self::xochip()),
+            _ => Err(format!(
+                "unknown quirks preset '{name}' (expected one of: {})",
+                PRESETS.join(", ")
+            )),
+        }
+    }
+}
+
+impl Quirks {
+    /// Get quirks as flags in `NAMES` order.
+    ///
+    /// # Returns
+    /// - Whether each quirk is enabled.
+    pub const fn flags(&self) -> [bool; 6] {
+        [
+            self.vf_reset,
+            self.memory,
+            self.display_wait,
+            self.clipping,
+            self.shifting,
+            self.jumping,
+        ]
+    }
+
+    /// Construct quirks from flags in `NAMES` order.
+    ///
+    /// # Parameters
+    /// - `flags` - given whether each quirk is enabled.
+    ///
+    /// # Returns
+    /// - New `Quirks` object.
+    pub const fn from_flags(flags: [bool; 6]) -> Self {
+        let [vf_reset, memory, display_wait, clipping, shifting, jumping] =
+            flags;
+
+        Self {
+            vf_reset,
+            memory,
+            display_wait,
+            clipping,
+            shifting,
+            jumping,
+        }
+    }
+}
+
+impl Default for Quirks {
+    fn default() -> Self {
+        Self::chip8()
+    }
+}
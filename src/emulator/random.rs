@@ -0,0 +1,230 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Deterministic random number generation for RND instruction.
+
+use crate::emulator::EmulatorResult;
+
+/// Memory page holding COSMAC VIP CHIP-8 interpreter code.
+pub const VIP_TABLE_PAGE: usize = 0x100;
+
+/// Random number generation algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// SplitMix64 pseudo random generator.
+    SplitMix,
+    /// COSMAC VIP interpreter generator.
+    ///
+    /// The original interpreter keeps 16-bit seed, increments it on each
+    /// RND and adds byte of its own code page indexed by the low seed byte to
+    /// the high seed byte. The table is read from the same RAM page, which is
+    /// empty on high-level CPU, so the generator is only usable on machine
+    /// with interpreter image loaded there (`--vip` copies it).
+    Vip,
+}
+
+impl Algorithm {
+    /// Get algorithm by name.
+    ///
+    /// # Parameters
+    /// - `name` - given algorithm name.
+    ///
+    /// # Returns
+    /// - Algorithm - in case of success.
+    /// - `Err`     - otherwise.
+    pub fn from_name(name: &str) -> EmulatorResult<Self> {
+        match name {
+            "splitmix" => Ok(Self::SplitMix),
+            "vip" => Ok(Self::Vip),
+            _ => Err(format!(
+                "unknown random algorithm '{name}' (expected splitmix or vip)"
+            )),
+        }
+    }
+
+    /// Get algorithm name.
+    ///
+    /// # Returns
+    /// - Name accepted by `from_name`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SplitMix => "splitmix",
+            Self::Vip => "vip",
+        }
+    }
+}
+
+/// Per-machine random number generator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Random {
+    /// Generation algorithm.
+    algorithm: Algorithm,
+    /// Initial seed.
+    seed: u64,
+    /// Current generator state.
+    state: u64,
+}
+
+impl Random {
+    /// Construct new `Random` object.
+    ///
+    /// # Parameters
+    /// - `algorithm` - given generation algorithm.
+    /// - `seed`      - given initial seed.
+    ///
+    /// # Returns
+    /// - New `Random` object.
+    pub fn new(algorithm: Algorithm, seed: u64) -> Self {
+        let state = match algorithm {
+            Algorithm::SplitMix => seed,
+            Algorithm::Vip => seed & 0xFFFF,
+        };
+
+        Self {
+            algorithm,
+            seed,
+            state,
+        }
+    }
+
+    /// Restore generator from saved state.
+    ///
+    /// # Parameters
+    /// - `algorithm` - given generation algorithm.
+    /// - `seed`      - given initial seed.
+    /// - `state`     - given generator state.
+    ///
+    /// # Returns
+    /// - New `Random` object.
+    pub fn from_state(algorithm: Algorithm, seed: u64, state: u64) -> Self {
+        Self {
+            algorithm,
+            seed,
+            state,
+        }
+    }
+
+    /// Get generation algorithm.
+    ///
+    /// # Returns
+    /// - Generation algorithm.
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Get initial seed.
+    ///
+    /// # Returns
+    /// - Seed the generator was constructed with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Get current generator state.
+    ///
+    /// # Returns
+    /// - Generator state.
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+
+    /// Generate next random byte.
+    ///
+    /// # Parameters
+    /// - `memory` - given machine RAM (used by VIP algorithm).
+    ///
+    /// # Returns
+    /// - Random byte in the full 0-255 range.
+    pub fn next_byte(&mut self, memory: &[u8]) -> u8 {
+        match self.algorithm {
+            Algorithm::SplitMix => self.next_splitmix() as u8,
+            Algorithm::Vip => self.next_vip(memory),
+        }
+    }
+
+    /// Generate next SplitMix64 value.
+    ///
+    /// # Returns
+    /// - Random 64-bit value.
+    fn next_splitmix(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+        value ^ (value >> 31)
+    }
+
+    /// Generate next COSMAC VIP interpreter value.
+    ///
+    /// # Parameters
+    /// - `memory` - given machine RAM.
+    ///
+    /// # Returns
+    /// - Random byte.
+    fn next_vip(&mut self, memory: &[u8]) -> u8 {
+        let seed = (self.state as u16).wrapping_add(1);
+        let [high, low] = seed.to_be_bytes();
+        let table = memory.get(VIP_TABLE_PAGE + low as usize).copied();
+        let high = high.wrapping_add(table.unwrap_or(0));
+
+        self.state = u16::from_be_bytes([high, low]) as u64;
+        high
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_splitmix_deterministic() {
+        let mut first = Random::new(Algorithm::SplitMix, 42);
+        let mut second = Random::new(Algorithm::SplitMix, 42);
+        let mut other = Random::new(Algorithm::SplitMix, 43);
+
+        let a: Vec<u8> = (0..16).map(|_| first.next_byte(&[])).collect();
+        let b: Vec<u8> = (0..16).map(|_| second.next_byte(&[])).collect();
+        let c: Vec<u8> = (0..16).map(|_| other.next_byte(&[])).collect();
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_splitmix_full_range() {
+        let mut random = Random::new(Algorithm::SplitMix, 0);
+        let mut seen = [false; 256];
+
+        for _ in 0..10_000 {
+            seen[random.next_byte(&[]) as usize] = true;
+        }
+
+        assert!(seen.iter().all(|&seen| seen));
+    }
+
+    #[test]
+    fn test_vip() {
+        let mut memory = [0u8; 0x200];
+        memory[0x101] = 0x10;
+        memory[0x102] = 0x20;
+
+        let mut random = Random::new(Algorithm::Vip, 0x0500);
+        assert_eq!(0x15, random.next_byte(&memory));
+        assert_eq!(0x35, random.next_byte(&memory));
+        assert_eq!(0x3502, random.state());
+    }
+
+    #[test]
+    fn test_restore_state() {
+        let mut random = Random::new(Algorithm::SplitMix, 7);
+        random.next_byte(&[]);
+
+        let (algorithm, seed, state) =
+            (random.algorithm(), random.seed(), random.state());
+        let mut restored = Random::from_state(algorithm, seed, state);
+
+        assert_eq!(random.next_byte(&[]), restored.next_byte(&[]));
+    }
+}
@@ -0,0 +1,400 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Program ROM loading and validation.
+//!
+//! Besides raw binaries, programs can be loaded from Octo cartridge GIF
+//! images, plain hexadecimal text dumps and Intel HEX files.
+
+mod cartridge;
+mod hex;
+
+use crate::emulator::{EmulatorResult, cpu::RAM_SIZE, romdb::Entry};
+use std::{fmt, fs};
+
+/// Known file signatures of formats which are not CHIP-8 programs.
+const FOREIGN_SIGNATURES: [(&[u8], &str); 7] = [
+    (b"\x7FELF", "ELF executable"),
+    (b"MZ\x90\x00", "DOS/Windows executable"),
+    (b"\x89PNG", "PNG image"),
+    (b"PK\x03\x04", "ZIP archive"),
+    (b"\x1F\x8B\x08", "gzip archive"),
+    (b"%PDF", "PDF document"),
+    (b"RIFF", "RIFF media file"),
+];
+
+/// Target machine variant.
+///
+/// Variants are ordered by instruction set, so each one supports all
+/// instructions of the previous ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Variant {
+    /// CHIP-8 machines with 4K of memory.
+    Chip8,
+    /// SUPER-CHIP machines with 4K of memory.
+    Schip,
+    /// XO-CHIP machines with 64K of memory.
+    XoChip,
+}
+
+impl Variant {
+    /// Get machine memory size.
+    ///
+    /// # Returns
+    /// - Number of addressable memory bytes.
+    pub const fn memory_size(&self) -> usize {
+        match self {
+            Self::Chip8 | Self::Schip => RAM_SIZE,
+            Self::XoChip => 0x10000,
+        }
+    }
+
+    /// Get machine variant name.
+    ///
+    /// # Returns
+    /// - Human readable variant name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Chip8 => "CHIP-8",
+            Self::Schip => "SUPER-CHIP",
+            Self::XoChip => "XO-CHIP",
+        }
+    }
+}
+
+/// ROM file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Raw program bytes.
+    Binary,
+    /// Octo cartridge GIF image.
+    Cartridge,
+    /// Hexadecimal text dump.
+    HexText,
+    /// Intel HEX file.
+    IntelHex,
+}
+
+impl Format {
+    /// Get format name.
+    ///
+    /// # Returns
+    /// - Human readable format name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Binary => "binary",
+            Self::Cartridge => "Octo cartridge",
+            Self::HexText => "hex text",
+            Self::IntelHex => "Intel HEX",
+        }
+    }
+}
+
+/// Loaded program ROM.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rom {
+    /// Program data bytes.
+    pub data: Vec<u8>,
+    /// Format program was decoded from.
+    pub format: Format,
+    /// Program settings embedded in container.
+    pub settings: Option<Entry>,
+}
+
+/// ROM loading error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RomError {
+    /// File could not be read.
+    Io { filename: String, message: String },
+    /// File contains no program data.
+    Empty,
+    /// Load address is outside of machine memory.
+    Address { addr: usize, variant: Variant },
+    /// Program does not fit in memory after load address.
+    TooLarge {
+        size: usize,
+        capacity: usize,
+        variant: Variant,
+    },
+    /// File is recognized as other known format.
+    Foreign(&'static str),
+    /// Container could not be decoded.
+    Malformed { format: Format, message: String },
+}
+
+impl fmt::Display for RomError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { filename, message } => {
+                write!(f, "Error read '{filename}': {message}")
+            }
+            Self::Empty => write!(f, "ROM file is empty"),
+            Self::Address { addr, variant } => write!(
+                f,
+                "Load address {addr:#05X} is outside of {} memory ({} bytes)",
+                variant.name(),
+                variant.memory_size()
+            ),
+            Self::TooLarge {
+                size,
+                capacity,
+                variant,
+            } => write!(
+                f,
+                "ROM of {size} bytes does not fit in {} memory \
+                 ({capacity} bytes available)",
+                variant.name()
+            ),
+            Self::Foreign(format) => {
+                write!(f, "File looks like {format}, not a CHIP-8 ROM")
+            }
+            Self::Malformed { format, message } => {
+                write!(f, "Malformed {} file: {message}", format.name())
+            }
+        }
+    }
+}
+
+impl From<RomError> for String {
+    fn from(error: RomError) -> Self {
+        error.to_string()
+    }
+}
+
+/// Read and validate program ROM file.
+///
+/// # Parameters
+/// - `filename`  - given ROM file name.
+/// - `variant`   - given target machine variant.
+/// - `load_addr` - given program load address.
+///
+/// # Returns
+/// - Decoded ROM - in case of success.
+/// - `RomError`  - otherwise.
+pub fn load(
+    filename: &str,
+    variant: Variant,
+    load_addr: usize,
+) -> Result<Rom, RomError> {
+    let bytes = fs::read(filename).map_err(|error| RomError::Io {
+        filename: filename.to_string(),
+        message: error.to_string(),
+    })?;
+
+    let rom = decode(bytes)?;
+    validate(&rom.data, variant, load_addr)?;
+
+    Ok(rom)
+}
+
+/// Encode program in container format.
+///
+/// # Parameters
+/// - `data`      - given program data bytes.
+/// - `format`    - given container format.
+/// - `load_addr` - given program load address.
+///
+/// # Returns
+/// - File bytes - in case of success.
+/// - `Err`      - if format can not be written.
+pub fn encode(
+    data: &[u8],
+    format: Format,
+    load_addr: usize,
+) -> EmulatorResult<Vec<u8>> {
+    match format {
+        Format::Binary => Ok(data.to_vec()),
+        Format::HexText => Ok(hex::format_text(data).into_bytes()),
+        Format::IntelHex => {
+            Ok(hex::format_intel_hex(data, load_addr).into_bytes())
+        }
+        Format::Cartridge => {
+            Err("writing Octo cartridges is not supported".to_string())
+        }
+    }
+}
+
+/// Decode program from container format detected by contents.
+///
+/// # Parameters
+/// - `bytes` - given file bytes.
+///
+/// # Returns
+/// - Decoded ROM - in case of success.
+/// - `RomError`  - if container is malformed.
+pub fn decode(bytes: Vec<u8>) -> Result<Rom, RomError> {
+    let malformed =
+        |format| move |message| RomError::Malformed { format, message };
+
+    if bytes.starts_with(b"GIF8") {
+        let cartridge =
+            cartridge::decode(&bytes).map_err(malformed(Format::Cartridge))?;
+
+        return Ok(Rom {
+            data: cartridge.data,
+            format: Format::Cartridge,
+            settings: Some(cartridge.settings),
+        });
+    }
+
+    let text = std::str::from_utf8(&bytes).ok();
+
+    if let Some(text) = text
+        && hex::is_intel_hex(text)
+    {
+        let data =
+            hex::parse_intel_hex(text).map_err(malformed(Format::IntelHex))?;
+
+        return Ok(Rom {
+            data,
+            format: Format::IntelHex,
+            settings: None,
+        });
+    }
+
+    if let Some(data) = text.and_then(hex::parse_text) {
+        return Ok(Rom {
+            data,
+            format: Format::HexText,
+            settings: None,
+        });
+    }
+
+    Ok(Rom {
+        data: bytes,
+        format: Format::Binary,
+        settings: None,
+    })
+}
+
+/// Check that program data looks like CHIP-8 ROM and fits in memory.
+///
+/// # Parameters
+/// - `data`      - given program data bytes.
+/// - `variant`   - given target machine variant.
+/// - `load_addr` - given program load address.
+///
+/// # Returns
+/// - `Ok`       - if program can be loaded.
+/// - `RomError` - otherwise.
+pub fn validate(
+    data: &[u8],
+    variant: Variant,
+    load_addr: usize,
+) -> Result<(), RomError> {
+    if data.is_empty() {
+        return Err(RomError::Empty);
+    }
+
+    if let Some(format) = detect_foreign(data) {
+        return Err(RomError::Foreign(format));
+    }
+
+    if load_addr >= variant.memory_size() {
+        return Err(RomError::Address {
+            addr: load_addr,
+            variant,
+        });
+    }
+
+    let capacity = variant.memory_size() - load_addr;
+
+    if data.len() > capacity {
+        return Err(RomError::TooLarge {
+            size: data.len(),
+            capacity,
+            variant,
+        });
+    }
+
+    Ok(())
+}
+
+/// Detect well-known file format by its signature.
+///
+/// # Parameters
+/// - `data` - given file data bytes.
+///
+/// # Returns
+/// - Format name - if file is not a CHIP-8 program.
+/// - `None`      - otherwise.
+pub fn detect_foreign(data: &[u8]) -> Option<&'static str> {
+    FOREIGN_SIGNATURES
+        .iter()
+        .find(|(signature, _)| data.starts_with(signature))
+        .map(|&(_, format)| format)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        // Odd length ROMs ending with a data byte are valid.
+        assert_eq!(
+            Ok(()),
+            validate(&[0x12, 0x00, 0xFF], Variant::Chip8, 0x200)
+        );
+        assert_eq!(Err(RomError::Empty), validate(&[], Variant::Chip8, 0x200));
+
+        let rom = vec![0; 0x1000];
+        let error = validate(&rom, Variant::Chip8, 0x200).unwrap_err();
+
+        assert_eq!(
+            RomError::TooLarge {
+                size: 0x1000,
+                capacity: 0xE00,
+                variant: Variant::Chip8,
+            },
+            error
+        );
+        assert!(error.to_string().contains("3584 bytes available"));
+        assert_eq!(Ok(()), validate(&rom, Variant::XoChip, 0x200));
+        assert!(matches!(
+            validate(&[0x00, 0xE0], Variant::Chip8, 0x1000),
+            Err(RomError::Address { .. })
+        ));
+    }
+
+    #[test]
+    fn test_decode() {
+        let binary = decode(vec![0x00, 0xE0]).unwrap();
+        assert_eq!(Format::Binary, binary.format);
+
+        let text = decode(b"00 E0 12 00\n".to_vec()).unwrap();
+        assert_eq!(
+            (Format::HexText, vec![0x00, 0xE0, 0x12, 0x00]),
+            (text.format, text.data)
+        );
+
+        let intel = decode(b":0202000000E01C\n:00000001FF\n".to_vec());
+        assert_eq!(vec![0x00, 0xE0], intel.unwrap().data);
+
+        let payload = r#"{"program": ": main clear", "options": {}}"#;
+        let octo = decode(cartridge::tests::cartridge(payload)).unwrap();
+
+        assert_eq!(Format::Cartridge, octo.format);
+        assert_eq!(vec![0x12, 0x02, 0x00, 0xE0], octo.data);
+        assert!(octo.settings.is_some());
+
+        assert!(matches!(
+            decode(b":02020000".to_vec()),
+            Err(RomError::Malformed {
+                format: Format::IntelHex,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_detect_foreign() {
+        assert_eq!(Some("ELF executable"), detect_foreign(b"\x7FELF\x02\x01"));
+        assert_eq!(Some("PNG image"), detect_foreign(b"\x89PNG\r\n"));
+        assert_eq!(None, detect_foreign(&[0x00, 0xE0, 0xA2, 0x2A]));
+        assert_eq!(
+            Err(RomError::Foreign("ZIP archive")),
+            validate(b"PK\x03\x04", Variant::Chip8, 0x200)
+        );
+    }
+}
@@ -0,0 +1,214 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Octo cartridge GIF images.
+//!
+//! Cartridge pixels carry two payload bits in the low bits of their color
+//! indices, four pixels per byte with the most significant bits first,
+//! continuing across animation frames. Payload is a 32-bit big-endian length
+//! followed by UTF-8 JSON with Octo source (`program`) and its `options`.
+
+use crate::emulator::{
+    capture, octo, quirks::Quirks, romdb::Entry, terminal::Color,
+};
+use serde_json::Value;
+
+/// Decoded cartridge contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cartridge {
+    /// Compiled program data bytes.
+    pub data: Vec<u8>,
+    /// Program settings from cartridge options.
+    pub settings: Entry,
+}
+
+/// Decode cartridge and compile its program.
+///
+/// # Parameters
+/// - `image` - given GIF image bytes.
+///
+/// # Returns
+/// - Cartridge contents - in case of success.
+/// - `Err`              - otherwise.
+pub fn decode(image: &[u8]) -> Result<Cartridge, String> {
+    let payload = payload(image)?;
+    let json: Value = serde_json::from_str(&payload)
+        .map_err(|error| format!("malformed payload: {error}"))?;
+
+    let Some(source) = json["program"].as_str() else {
+        return Err("payload has no program".to_string());
+    };
+
+    let data = octo::assemble(source)?;
+    let settings = settings(&json["options"]);
+
+    Ok(Cartridge { data, settings })
+}
+
+/// Extract payload text from cartridge pixels.
+///
+/// # Parameters
+/// - `image` - given GIF image bytes.
+///
+/// # Returns
+/// - Payload text - in case of success.
+/// - `Err`        - otherwise.
+fn payload(image: &[u8]) -> Result<String, String> {
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::Indexed);
+
+    let mut decoder = options
+        .read_info(image)
+        .map_err(|error| format!("malformed GIF: {error}"))?;
+    let mut pixels = Vec::new();
+
+    while let Some(frame) = decoder
+        .read_next_frame()
+        .map_err(|error| format!("malformed GIF: {error}"))?
+    {
+        pixels.extend(frame.buffer.iter().map(|index| index & 3));
+    }
+
+    let bytes: Vec<u8> = pixels
+        .chunks_exact(4)
+        .map(|bits| bits[0] << 6 | bits[1] << 4 | bits[2] << 2 | bits[3])
+        .collect();
+
+    let Some((length, rest)) = bytes.split_first_chunk::<4>() else {
+        return Err("image is too small".to_string());
+    };
+
+    let Some(payload) = rest.get(..u32::from_be_bytes(*length) as usize) else {
+        return Err("payload is truncated".to_string());
+    };
+
+    String::from_utf8(payload.to_vec())
+        .map_err(|_| "payload is not UTF-8 text".to_string())
+}
+
+/// Convert Octo options to program settings.
+///
+/// # Parameters
+/// - `options` - given Octo options object.
+///
+/// # Returns
+/// - Program settings.
+fn settings(options: &Value) -> Entry {
+    // Octo defaults to all quirks disabled.
+    let mut quirks = Quirks::xochip();
+    let flag = |name: &str| options[name].as_bool();
+
+    if let Some(value) = flag("shiftQuirks") {
+        quirks.shifting = value;
+    }
+
+    if let Some(value) = flag("loadStoreQuirks") {
+        quirks.memory = !value;
+    }
+
+    if let Some(value) = flag("clipQuirks") {
+        quirks.clipping = value;
+    }
+
+    if let Some(value) = flag("vBlankQuirks") {
+        quirks.display_wait = value;
+    }
+
+    if let Some(value) = flag("jumpQuirks") {
+        quirks.jumping = value;
+    }
+
+    if let Some(value) = flag("logicQuirks") {
+        quirks.vf_reset = value;
+    }
+
+    let color = |name: &str| -> Option<Color> {
+        let text = options[name].as_str()?;
+        capture::parse_palette(&format!("{text},{text}"))
+            .ok()
+            .map(|(color, _)| color)
+    };
+
+    Entry {
+        title: "Octo cartridge".to_string(),
+        authors: Vec::new(),
+        platform: None,
+        quirks: Some(quirks),
+        speed: options["tickrate"].as_u64().map(|speed| speed as usize),
+        keys: Vec::new(),
+        colors: color("fillColor").zip(color("backgroundColor")),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// Build cartridge image with payload in color indices low bits.
+    ///
+    /// # Parameters
+    /// - `payload` - given payload text.
+    ///
+    /// # Returns
+    /// - GIF image bytes.
+    pub fn cartridge(payload: &str) -> Vec<u8> {
+        let mut bytes = (payload.len() as u32).to_be_bytes().to_vec();
+        bytes.extend(payload.as_bytes());
+
+        let (width, height) = (32u16, 32u16);
+        let mut pixels: Vec<u8> = bytes
+            .iter()
+            .flat_map(|byte| {
+                [byte >> 6, byte >> 4 & 3, byte >> 2 & 3, byte & 3]
+            })
+            .map(|bits| 4 | bits)
+            .collect();
+
+        let frame_size = width as usize * height as usize;
+        pixels.resize(pixels.len().div_ceil(frame_size) * frame_size, 4);
+
+        let palette = [0u8; 8 * 3];
+        let mut image = Vec::new();
+        let mut encoder =
+            gif::Encoder::new(&mut image, width, height, &palette).unwrap();
+
+        for chunk in pixels.chunks(frame_size) {
+            let frame =
+                gif::Frame::from_indexed_pixels(width, height, chunk, None);
+            encoder.write_frame(&frame).unwrap();
+        }
+
+        drop(encoder);
+        image
+    }
+
+    #[test]
+    fn test_decode() {
+        let payload = r##"{
+            "program": ": main\n  v0 := 1\n  loop again",
+            "options": {
+                "tickrate": 20,
+                "shiftQuirks": true,
+                "fillColor": "#FFFFFF",
+                "backgroundColor": "#000000"
+            }
+        }"##;
+
+        let decoded = decode(&cartridge(payload)).unwrap();
+
+        assert_eq!(vec![0x12, 0x02, 0x60, 0x01, 0x12, 0x04], decoded.data);
+        assert_eq!(Some(20), decoded.settings.speed);
+        assert!(decoded.settings.quirks.unwrap().shifting);
+        assert_eq!(
+            Some(((0xFF, 0xFF, 0xFF), (0, 0, 0))),
+            decoded.settings.colors
+        );
+
+        // Payload spanning several frames.
+        let long = format!(r#"{{"program": ": main {}"}}"#, "0 ".repeat(300));
+        assert_eq!(302, decode(&cartridge(&long)).unwrap().data.len());
+
+        assert!(decode(&cartridge(r#"{"options": {}}"#)).is_err());
+        assert!(decode(b"GIF89a").is_err());
+    }
+}
@@ -0,0 +1,284 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Hexadecimal text and Intel HEX program dumps.
+
+use std::collections::BTreeMap;
+
+/// Intel HEX data record type.
+const DATA: u8 = 0x00;
+
+/// Intel HEX end of file record type.
+const END_OF_FILE: u8 = 0x01;
+
+/// Intel HEX extended segment address record type.
+const SEGMENT_ADDRESS: u8 = 0x02;
+
+/// Intel HEX extended linear address record type.
+const LINEAR_ADDRESS: u8 = 0x04;
+
+/// Parse plain hexadecimal text dump.
+///
+/// Bytes are separated by whitespace or commas and may have `0x` or `$`
+/// prefix, multiple bytes may be written together (`00E0`). Comments start
+/// with `#`, `;` or `//`.
+///
+/// # Parameters
+/// - `text` - given dump text.
+///
+/// # Returns
+/// - Program data bytes - if text is hexadecimal dump.
+/// - `None`             - otherwise.
+pub fn parse_text(text: &str) -> Option<Vec<u8>> {
+    let mut data = Vec::new();
+
+    for line in text.lines() {
+        let line = line
+            .split(['#', ';'])
+            .next()
+            .and_then(|line| line.split("//").next())
+            .unwrap_or_default();
+
+        for token in line.split(|c: char| c.is_whitespace() || c == ',') {
+            if token.is_empty() {
+                continue;
+            }
+
+            let digits = token
+                .strip_prefix("0x")
+                .or_else(|| token.strip_prefix('$'))
+                .unwrap_or(token);
+
+            if digits.is_empty() || digits.len() % 2 != 0 {
+                return None;
+            }
+
+            for pair in digits.as_bytes().chunks_exact(2) {
+                let pair = std::str::from_utf8(pair).ok()?;
+                data.push(u8::from_str_radix(pair, 16).ok()?);
+            }
+        }
+    }
+
+    (!data.is_empty()).then_some(data)
+}
+
+/// Check whether text looks like Intel HEX file.
+///
+/// # Parameters
+/// - `text` - given file text.
+///
+/// # Returns
+/// - `true` - if first non-empty line is a record.
+pub fn is_intel_hex(text: &str) -> bool {
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .is_some_and(|line| line.starts_with(':'))
+}
+
+/// Parse Intel HEX file.
+///
+/// Program data starts at the lowest record address, gaps are filled with
+/// zeros.
+///
+/// # Parameters
+/// - `text` - given Intel HEX text.
+///
+/// # Returns
+/// - Program data bytes - in case of success.
+/// - `Err`              - otherwise.
+pub fn parse_intel_hex(text: &str) -> Result<Vec<u8>, String> {
+    let mut memory = BTreeMap::new();
+    let mut base = 0usize;
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        let error = |message: &str| format!("line {}: {message}", index + 1);
+        let record = parse_record(line).map_err(|message| error(&message))?;
+        let (kind, addr, data) = record;
+
+        match kind {
+            DATA => {
+                for (offset, &byte) in data.iter().enumerate() {
+                    memory.insert(base + addr as usize + offset, byte);
+                }
+            }
+            END_OF_FILE => break,
+            SEGMENT_ADDRESS | LINEAR_ADDRESS => {
+                let [high, low] = data[..] else {
+                    return Err(error("address record should have 2 bytes"));
+                };
+                let value = u16::from_be_bytes([high, low]) as usize;
+
+                base = match kind {
+                    SEGMENT_ADDRESS => value << 4,
+                    _ => value << 16,
+                };
+            }
+            // Start address records do not affect program data.
+            _ => {}
+        }
+    }
+
+    let (Some((&first, _)), Some((&last, _))) =
+        (memory.first_key_value(), memory.last_key_value())
+    else {
+        return Err("no data records".to_string());
+    };
+
+    let mut data = vec![0; last - first + 1];
+
+    for (addr, byte) in memory {
+        data[addr - first] = byte;
+    }
+
+    Ok(data)
+}
+
+/// Format program as hexadecimal text dump.
+///
+/// # Parameters
+/// - `data` - given program data bytes.
+///
+/// # Returns
+/// - Dump text with 16 bytes per line.
+pub fn format_text(data: &[u8]) -> String {
+    data.chunks(16)
+        .map(|chunk| {
+            let bytes: Vec<String> =
+                chunk.iter().map(|byte| format!("{byte:02X}")).collect();
+            bytes.join(" ") + "\n"
+        })
+        .collect()
+}
+
+/// Format program as Intel HEX file.
+///
+/// # Parameters
+/// - `data`      - given program data bytes.
+/// - `load_addr` - given program load address.
+///
+/// # Returns
+/// - Intel HEX text with 16 bytes per data record.
+pub fn format_intel_hex(data: &[u8], load_addr: usize) -> String {
+    let mut text = String::new();
+
+    for (index, chunk) in data.chunks(16).enumerate() {
+        let addr = (load_addr + index * 16) as u16;
+        text += &format_record(DATA, addr, chunk);
+    }
+
+    text + &format_record(END_OF_FILE, 0, &[])
+}
+
+/// Format single Intel HEX record.
+///
+/// # Parameters
+/// - `kind` - given record type.
+/// - `addr` - given record address.
+/// - `data` - given record data bytes.
+///
+/// # Returns
+/// - Record line.
+fn format_record(kind: u8, addr: u16, data: &[u8]) -> String {
+    let [high, low] = addr.to_be_bytes();
+    let mut bytes = vec![data.len() as u8, high, low, kind];
+    bytes.extend_from_slice(data);
+
+    let sum = bytes.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte));
+    bytes.push(sum.wrapping_neg());
+
+    let digits: String =
+        bytes.iter().map(|byte| format!("{byte:02X}")).collect();
+    format!(":{digits}\n")
+}
+
+/// Parse single Intel HEX record.
+///
+/// # Parameters
+/// - `line` - given record line.
+///
+/// # Returns
+/// - Record type, address and data - in case of success.
+/// - `Err`                         - otherwise.
+fn parse_record(line: &str) -> Result<(u8, u16, Vec<u8>), String> {
+    let Some(digits) = line.strip_prefix(':') else {
+        return Err("record should start with ':'".to_string());
+    };
+
+    if !digits.is_ascii() || digits.len() % 2 != 0 || digits.len() < 10 {
+        return Err("record is truncated".to_string());
+    }
+
+    let bytes = (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|_| "record has invalid hex digits".to_string())?;
+
+    let length = bytes[0] as usize;
+
+    if bytes.len() != length + 5 {
+        return Err("record length mismatch".to_string());
+    }
+
+    let checksum = bytes.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte));
+
+    if checksum != 0 {
+        return Err("record checksum mismatch".to_string());
+    }
+
+    let addr = u16::from_be_bytes([bytes[1], bytes[2]]);
+
+    Ok((bytes[3], addr, bytes[4..4 + length].to_vec()))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_text() {
+        let text = "# clear and jump\n00E0 0x12,0x00 ; loop\n$FF // data";
+
+        assert_eq!(Some(vec![0x00, 0xE0, 0x12, 0x00, 0xFF]), parse_text(text));
+        assert_eq!(None, parse_text("00E"));
+        assert_eq!(None, parse_text("hello world"));
+        assert_eq!(None, parse_text("# only comment"));
+    }
+
+    #[test]
+    fn test_parse_intel_hex() {
+        let text = "\
+:0402000000E0120008
+:01020600FFF8
+:00000001FF
+";
+
+        assert!(is_intel_hex(text));
+        assert_eq!(
+            Ok(vec![0x00, 0xE0, 0x12, 0x00, 0x00, 0x00, 0xFF]),
+            parse_intel_hex(text)
+        );
+        assert!(parse_intel_hex(":0402000000E0120009").is_err());
+        assert!(parse_intel_hex(":00000001FF").is_err());
+        assert!(!is_intel_hex("00E0"));
+    }
+
+    #[test]
+    fn test_format() {
+        let data: Vec<u8> = (0..20).collect();
+
+        assert_eq!(Some(data.clone()), parse_text(&format_text(&data)));
+
+        let text = format_intel_hex(&data, 0x200);
+        assert!(text.ends_with(":00000001FF\n"));
+        assert_eq!(Ok(data), parse_intel_hex(&text));
+    }
+}
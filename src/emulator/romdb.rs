@@ -0,0 +1,348 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! ROM database lookup by SHA-1 hash.
+//!
+//! Database follows `programs.json` schema of the community CHIP-8 database:
+//! array of programs with title, authors and ROMs keyed by SHA-1 hash. No
+//! database is bundled, the community file has to be given with `--romdb`
+//! or `[paths] romdb` configuration key.
+
+mod sha1;
+
+use crate::emulator::{
+    EmulatorResult, Settings, capture, config_file, quirks::Quirks, terminal,
+    terminal::Color,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+
+pub use sha1::hex_digest;
+
+/// Host keys of database logical buttons in default keymap layout.
+const BUTTON_KEYS: [(&str, u8); 6] = [
+    ("up", b'w'),
+    ("down", b's'),
+    ("left", b'a'),
+    ("right", b'd'),
+    ("a", b'e'),
+    ("b", b'q'),
+];
+
+/// Database entry of single ROM.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Program title.
+    pub title: String,
+    /// Program authors.
+    pub authors: Vec<String>,
+    /// First supported platform name.
+    pub platform: Option<String>,
+    /// Platform quirks with ROM specific adjustments.
+    pub quirks: Option<Quirks>,
+    /// Recommended number of instructions per frame.
+    pub speed: Option<usize>,
+    /// Logical buttons and keypad keys pairs.
+    pub keys: Vec<(String, u8)>,
+    /// Lit and unlit pixel colors.
+    pub colors: Option<(Color, Color)>,
+}
+
+impl Entry {
+    /// Parse ROM entry.
+    ///
+    /// # Parameters
+    /// - `program` - given program object.
+    /// - `rom`     - given ROM object of the program.
+    ///
+    /// # Returns
+    /// - New `Entry` object.
+    fn parse(program: &Value, rom: &Value) -> Self {
+        let strings = |value: &Value| -> Vec<String> {
+            value
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|item| item.as_str().map(str::to_string))
+                .collect()
+        };
+
+        let platform = strings(&rom["platforms"])
+            .into_iter()
+            .find(|name| platform_quirks(name).is_some());
+
+        let quirks = platform.as_deref().and_then(|name| {
+            let mut quirks = platform_quirks(name)?;
+            apply_quirks(&mut quirks, &rom["quirkyPlatforms"][name]);
+            Some(quirks)
+        });
+
+        let keys = rom["keys"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter_map(|(button, key)| {
+                let key = key.as_u64().filter(|&key| key < 16)?;
+                Some((button.clone(), key as u8))
+            })
+            .collect();
+
+        let pixels = strings(&rom["colors"]["pixels"]);
+        let colors = match pixels.as_slice() {
+            [unlit, lit, ..] => {
+                capture::parse_palette(&format!("{lit},{unlit}")).ok()
+            }
+            _ => None,
+        };
+
+        Self {
+            title: program["title"].as_str().unwrap_or("Unknown").to_string(),
+            authors: strings(&program["authors"]),
+            platform,
+            quirks,
+            speed: rom["tickrate"].as_u64().map(|speed| speed as usize),
+            keys,
+            colors,
+        }
+    }
+
+    /// Apply entry to runtime settings not set on the command line.
+    ///
+    /// # Parameters
+    /// - `settings` - given runtime settings.
+    pub fn apply(&self, settings: &mut Settings) {
+        settings.quirks = settings.quirks.or(self.quirks);
+        settings.speed = settings.speed.or(self.speed);
+
+        if !self.keys.is_empty() {
+            let keymap = settings.terminal.keymap.take();
+            settings.terminal.keymap =
+                keymap.or_else(|| Some(remap_keys(&self.keys)));
+        }
+
+        if let Some(palette) = settings.capture.palette.or(self.colors) {
+            config_file::set_palette(settings, palette);
+        }
+    }
+}
+
+/// ROM database indexed by SHA-1 hash.
+#[derive(Debug, Clone, Default)]
+pub struct Database {
+    /// ROM entries by lowercase hexadecimal SHA-1 hash.
+    entries: HashMap<String, Entry>,
+}
+
+impl Database {
+    /// Parse database in community CHIP-8 database `programs.json` format.
+    ///
+    /// # Parameters
+    /// - `text` - given database JSON text.
+    ///
+    /// # Returns
+    /// - New `Database` object - in case of success.
+    /// - `Err`                 - otherwise.
+    pub fn parse(text: &str) -> EmulatorResult<Self> {
+        let programs: Value = serde_json::from_str(text)
+            .map_err(|error| format!("malformed ROM database: {error}"))?;
+        let Some(programs) = programs.as_array() else {
+            return Err("ROM database should be array of programs".to_string());
+        };
+
+        let mut entries = HashMap::new();
+
+        for program in programs {
+            for (hash, rom) in program["roms"].as_object().into_iter().flatten()
+            {
+                let entry = Entry::parse(program, rom);
+                entries.insert(hash.to_ascii_lowercase(), entry);
+            }
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Find ROM entry.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    ///
+    /// # Returns
+    /// - ROM entry - if program is known.
+    /// - `None`    - otherwise.
+    pub fn lookup(&self, program_data: &[u8]) -> Option<&Entry> {
+        self.entries.get(&hex_digest(program_data))
+    }
+
+    /// Get number of known ROMs.
+    ///
+    /// # Returns
+    /// - Number of database entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check whether database has no entries.
+    ///
+    /// # Returns
+    /// - `true` - if database is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Get base quirks of database platform.
+///
+/// # Parameters
+/// - `name` - given database platform identifier.
+///
+/// # Returns
+/// - Platform quirks - if platform is supported.
+/// - `None`          - otherwise.
+fn platform_quirks(name: &str) -> Option<Quirks> {
+    match name {
+        "originalChip8" | "hybridVIP" => Some(Quirks::chip8()),
+        "modernChip8" => Some(Quirks {
+            vf_reset: false,
+            display_wait: false,
+            ..Quirks::chip8()
+        }),
+        "chip48" | "superchip1" | "superchip" => Some(Quirks::schip()),
+        "xochip" => Some(Quirks::xochip()),
+        _ => None,
+    }
+}
+
+/// Apply database quirk flags.
+///
+/// # Parameters
+/// - `quirks` - given quirks to adjust.
+/// - `flags`  - given database quirks object.
+fn apply_quirks(quirks: &mut Quirks, flags: &Value) {
+    for (name, value) in flags.as_object().into_iter().flatten() {
+        let Some(value) = value.as_bool() else {
+            continue;
+        };
+
+        match name.as_str() {
+            "shift" => quirks.shifting = value,
+            "memoryLeaveIUnchanged" => quirks.memory = !value,
+            "wrap" => quirks.clipping = !value,
+            "jump" => quirks.jumping = value,
+            "vblank" => quirks.display_wait = value,
+            "logic" => quirks.vf_reset = value,
+            _ => {}
+        }
+    }
+}
+
+/// Build keymap placing logical buttons on their default host keys.
+///
+/// Host key of each button is swapped with the key currently mapped to
+/// the button's keypad key, so the keymap stays complete.
+///
+/// # Parameters
+/// - `keys` - given logical buttons and keypad keys pairs.
+///
+/// # Returns
+/// - Keyboard characters in keypad keys order (0-F).
+fn remap_keys(keys: &[(String, u8)]) -> String {
+    let mut keymap = terminal::DEFAULT_KEYMAP.as_bytes().to_vec();
+
+    for (button, key) in keys {
+        let Some(&(_, host)) =
+            BUTTON_KEYS.iter().find(|(name, _)| name == button)
+        else {
+            continue;
+        };
+
+        if let Some(position) = keymap.iter().position(|&byte| byte == host) {
+            keymap.swap(position, *key as usize);
+        }
+    }
+
+    String::from_utf8(keymap).expect("keymap should be ASCII")
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// Test program data.
+    const PROGRAM: [u8; 4] = [0x00, 0xE0, 0x12, 0x00];
+
+    /// Get test database with single program.
+    fn database() -> Database {
+        let text = format!(
+            r##"[{{
+                "title": "Test",
+                "authors": ["Someone"],
+                "roms": {{
+                    "{}": {{
+                        "platforms": ["megachip8", "superchip"],
+                        "quirkyPlatforms": {{
+                            "superchip": {{ "wrap": true }}
+                        }},
+                        "tickrate": 30,
+                        "keys": {{ "up": 3, "a": 10 }},
+                        "colors": {{ "pixels": ["#000000", "#ffffff"] }}
+                    }}
+                }}
+            }}]"##,
+            hex_digest(&PROGRAM).to_ascii_uppercase()
+        );
+
+        Database::parse(&text).unwrap()
+    }
+
+    #[test]
+    fn test_lookup() {
+        let database = database();
+        let entry = database.lookup(&PROGRAM).unwrap();
+
+        assert_eq!("Test", entry.title);
+        assert_eq!(Some("superchip"), entry.platform.as_deref());
+        assert!(!entry.quirks.unwrap().clipping);
+        assert_eq!(Some(30), entry.speed);
+        assert!(database.lookup(&PROGRAM[..2]).is_none());
+        assert!(Database::parse("{}").is_err());
+    }
+
+    #[test]
+    fn test_apply() {
+        let database = database();
+        let entry = database.lookup(&PROGRAM).unwrap();
+
+        let mut settings = Settings::default();
+        entry.apply(&mut settings);
+
+        assert_eq!(Some(30), settings.speed);
+        assert_eq!((0xFF, 0xFF, 0xFF), settings.capture.palette.unwrap().0);
+
+        // Up moves to keypad 3 and fire to keypad A, replaced keys take
+        // over their previous host keys.
+        let keymap = settings.terminal.keymap.as_deref().unwrap().as_bytes();
+        assert_eq!(b'w', keymap[3]);
+        assert_eq!(b'3', keymap[5]);
+        assert_eq!(b'e', keymap[0xA]);
+        assert_eq!(b'z', keymap[6]);
+
+        // Command line settings take precedence, even if equal to defaults.
+        let mut settings = Settings {
+            quirks: Some(Quirks::chip8()),
+            speed: Some(10),
+            ..Settings::default()
+        };
+        settings.terminal.keymap = Some(terminal::DEFAULT_KEYMAP.to_string());
+        config_file::set_palette(&mut settings, terminal::DEFAULT_PALETTE);
+        entry.apply(&mut settings);
+
+        assert_eq!(Some(Quirks::chip8()), settings.quirks);
+        assert_eq!(Some(10), settings.speed);
+        assert_eq!(
+            Some(terminal::DEFAULT_KEYMAP),
+            settings.terminal.keymap.as_deref()
+        );
+        assert_eq!(Some(terminal::DEFAULT_PALETTE), settings.capture.palette);
+    }
+}
@@ -0,0 +1,132 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! SHA-1 message digest (FIPS 180-4) used to identify ROMs.
+
+/// Size of message block in bytes.
+const BLOCK_SIZE: usize = 64;
+
+/// Initial hash value.
+const INITIAL: [u32; 5] = [
+    0x6745_2301,
+    0xEFCD_AB89,
+    0x98BA_DCFE,
+    0x1032_5476,
+    0xC3D2_E1F0,
+];
+
+/// Calculate SHA-1 digest.
+///
+/// # Parameters
+/// - `data` - given message bytes.
+///
+/// # Returns
+/// - 20 bytes digest.
+pub fn digest(data: &[u8]) -> [u8; 20] {
+    let mut state = INITIAL;
+    let bit_length = (data.len() as u64).wrapping_mul(8);
+
+    // Message is padded with 0x80, zeros and big-endian bit length.
+    let mut message = data.to_vec();
+    message.push(0x80);
+
+    while message.len() % BLOCK_SIZE != BLOCK_SIZE - 8 {
+        message.push(0);
+    }
+
+    message.extend(bit_length.to_be_bytes());
+
+    for block in message.chunks_exact(BLOCK_SIZE) {
+        compress(&mut state, block);
+    }
+
+    let mut result = [0u8; 20];
+
+    for (bytes, word) in result.chunks_exact_mut(4).zip(state) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+
+    result
+}
+
+/// Calculate SHA-1 digest in lowercase hexadecimal.
+///
+/// # Parameters
+/// - `data` - given message bytes.
+///
+/// # Returns
+/// - 40 characters digest string representation.
+pub fn hex_digest(data: &[u8]) -> String {
+    digest(data)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Process single message block.
+///
+/// # Parameters
+/// - `state` - given intermediate hash value.
+/// - `block` - given 64 bytes message block.
+fn compress(state: &mut [u32; 5], block: &[u8]) {
+    let mut words = [0u32; 80];
+
+    for (word, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+
+    for i in 16..80 {
+        words[i] =
+            (words[i - 3] ^ words[i - 8] ^ words[i - 14] ^ words[i - 16])
+                .rotate_left(1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = *state;
+
+    for (i, word) in words.iter().enumerate() {
+        let (f, k) = match i {
+            0..20 => ((b & c) | (!b & d), 0x5A82_7999),
+            20..40 => (b ^ c ^ d, 0x6ED9_EBA1),
+            40..60 => ((b & c) | (b & d) | (c & d), 0x8F1B_BCDC),
+            _ => (b ^ c ^ d, 0xCA62_C1D6),
+        };
+
+        let temp = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(*word);
+
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+
+    for (value, added) in state.iter_mut().zip([a, b, c, d, e]) {
+        *value = value.wrapping_add(added);
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digest() {
+        assert_eq!("da39a3ee5e6b4b0d3255bfef95601890afd80709", hex_digest(b""));
+        assert_eq!(
+            "a9993e364706816aba3e25717850c26c9cd0d89d",
+            hex_digest(b"abc")
+        );
+        // Two blocks message.
+        assert_eq!(
+            "84983e441c3bd26ebaae4aa1f95129e5e54670f1",
+            hex_digest(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            )
+        );
+    }
+}
@@ -0,0 +1,362 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Beeper synthesis to PCM samples and WAV recording.
+
+use crate::emulator::{
+    EmulatorResult,
+    cpu::{AUDIO_PATTERN_SIZE, DEFAULT_PITCH, KEY_COUNT},
+    frontend::{Frame, Frontend},
+};
+use std::fs;
+
+/// Default PCM sample rate in Hz.
+pub const DEFAULT_SAMPLE_RATE: u32 = 44_100;
+
+/// Emulated frames per second.
+const FRAME_RATE: u64 = 60;
+
+/// Default beeper square wave frequency in Hz.
+pub const DEFAULT_FREQUENCY: f64 = 440.0;
+
+/// Default beeper volume in percent.
+pub const DEFAULT_VOLUME: u8 = 25;
+
+/// XO-CHIP audio pattern playback rate at default pitch in Hz.
+const PATTERN_RATE: f64 = 4000.0;
+
+/// Number of one-bit samples in XO-CHIP audio pattern.
+const PATTERN_BITS: usize = AUDIO_PATTERN_SIZE * 8;
+
+/// Square wave amplitude at full volume.
+const MAX_AMPLITUDE: i32 = 32_000;
+
+/// Sound output options.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// PCM sample rate in Hz.
+    pub sample_rate: u32,
+    /// Beeper square wave frequency in Hz.
+    pub frequency: f64,
+    /// Beeper volume in percent.
+    pub volume: u8,
+    /// Output WAV file name for session audio.
+    pub wav: Option<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            sample_rate: DEFAULT_SAMPLE_RATE,
+            frequency: DEFAULT_FREQUENCY,
+            volume: DEFAULT_VOLUME,
+            wav: None,
+        }
+    }
+}
+
+/// Beeper synthesizer producing samples frame by frame.
+#[derive(Debug, Clone)]
+pub struct Synth {
+    /// PCM sample rate in Hz.
+    sample_rate: u32,
+    /// Beeper square wave frequency in Hz.
+    frequency: f64,
+    /// Square wave amplitude.
+    amplitude: i16,
+    /// Number of synthesized frames.
+    frame: u64,
+    /// Waveform position in periods (tone) or pattern bits (XO-CHIP).
+    phase: f64,
+}
+
+impl Synth {
+    /// Construct new `Synth` object.
+    ///
+    /// # Parameters
+    /// - `options` - given sound output options.
+    ///
+    /// # Returns
+    /// - New `Synth` object.
+    pub fn new(options: &Options) -> Self {
+        let volume = options.volume.min(100) as i32;
+
+        Self {
+            sample_rate: options.sample_rate,
+            frequency: options.frequency,
+            amplitude: (MAX_AMPLITUDE * volume / 100) as i16,
+            frame: 0,
+            phase: 0.0,
+        }
+    }
+
+    /// Synthesize samples of single frame.
+    ///
+    /// Frame lengths alternate so that 60 frames always produce exactly
+    /// one second of samples.
+    ///
+    /// # Parameters
+    /// - `playing` - given whether sound timer was active during frame.
+    /// - `pattern` - given XO-CHIP audio pattern (square tone if not set).
+    /// - `pitch`   - given XO-CHIP audio pattern playback pitch.
+    ///
+    /// # Returns
+    /// - Signed 16-bit mono PCM samples.
+    pub fn frame_samples(
+        &mut self,
+        playing: bool,
+        pattern: Option<&[u8; AUDIO_PATTERN_SIZE]>,
+        pitch: u8,
+    ) -> Vec<i16> {
+        let rate = self.sample_rate as u64;
+        let start = self.frame * rate / FRAME_RATE;
+        let end = (self.frame + 1) * rate / FRAME_RATE;
+        let count = (end - start) as usize;
+
+        self.frame += 1;
+
+        if !playing {
+            self.phase = 0.0;
+            return vec![0; count];
+        }
+
+        let step = match pattern {
+            Some(_) => {
+                let octaves = (pitch as f64 - DEFAULT_PITCH as f64) / 48.0;
+                PATTERN_RATE * octaves.exp2() / self.sample_rate as f64
+            }
+            None => self.frequency / self.sample_rate as f64,
+        };
+
+        (0..count)
+            .map(|_| {
+                let high = match pattern {
+                    Some(pattern) => {
+                        let bit = self.phase as usize % PATTERN_BITS;
+                        pattern[bit / 8] & (0x80 >> (bit % 8)) != 0
+                    }
+                    None => self.phase.fract() < 0.5,
+                };
+
+                self.phase += step;
+
+                if high {
+                    self.amplitude
+                } else {
+                    -self.amplitude
+                }
+            })
+            .collect()
+    }
+}
+
+/// Encode samples as WAV file.
+///
+/// # Parameters
+/// - `samples`     - given signed 16-bit mono PCM samples.
+/// - `sample_rate` - given PCM sample rate in Hz.
+///
+/// # Returns
+/// - WAV file bytes.
+pub fn wav(samples: &[i16], sample_rate: u32) -> Vec<u8> {
+    let data_size = (samples.len() * 2) as u32;
+    let mut bytes = Vec::with_capacity(44 + data_size as usize);
+
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_size).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_size.to_le_bytes());
+
+    for sample in samples {
+        bytes.extend_from_slice(&sample.to_le_bytes());
+    }
+
+    bytes
+}
+
+/// Frontend wrapper recording session audio.
+///
+/// Display, input and tone are forwarded to the wrapped frontend, generated
+/// samples are kept for writing to WAV file.
+pub struct WavRecorder<'a> {
+    /// Wrapped frontend.
+    inner: &'a mut dyn Frontend,
+    /// Sound output options.
+    options: Options,
+    /// Recorded samples.
+    samples: Vec<i16>,
+}
+
+impl<'a> WavRecorder<'a> {
+    /// Construct new `WavRecorder` object.
+    ///
+    /// # Parameters
+    /// - `inner`   - given frontend to wrap.
+    /// - `options` - given sound output options.
+    ///
+    /// # Returns
+    /// - New `WavRecorder` object.
+    pub fn new(inner: &'a mut dyn Frontend, options: &Options) -> Self {
+        Self {
+            inner,
+            options: options.clone(),
+            samples: Vec::new(),
+        }
+    }
+
+    /// Get recorded samples.
+    ///
+    /// # Returns
+    /// - Signed 16-bit mono PCM samples.
+    pub fn samples(&self) -> &[i16] {
+        &self.samples
+    }
+
+    /// Write recorded samples to WAV file.
+    ///
+    /// # Parameters
+    /// - `filename` - given output file name.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    pub fn save(&self, filename: &str) -> EmulatorResult<()> {
+        fs::write(filename, wav(&self.samples, self.options.sample_rate))
+            .map_err(|error| format!("Error write '{filename}': {error}"))
+    }
+}
+
+impl Frontend for WavRecorder<'_> {
+    fn poll_keys(
+        &mut self,
+        keypad: &mut [bool; KEY_COUNT],
+    ) -> EmulatorResult<()> {
+        self.inner.poll_keys(keypad)
+    }
+
+    fn quit_requested(&self) -> bool {
+        self.inner.quit_requested()
+    }
+
+    fn screenshot_requested(&mut self) -> bool {
+        self.inner.screenshot_requested()
+    }
+
+    fn present(&mut self, frame: &Frame) -> EmulatorResult<()> {
+        self.inner.present(frame)
+    }
+
+    fn set_tone(&mut self, playing: bool) -> EmulatorResult<()> {
+        self.inner.set_tone(playing)
+    }
+
+    fn push_samples(&mut self, samples: &[i16]) -> EmulatorResult<()> {
+        self.samples.extend_from_slice(samples);
+        Ok(())
+    }
+
+    fn audio(&self) -> Option<&Options> {
+        Some(&self.options)
+    }
+
+    fn realtime(&self) -> bool {
+        self.inner.realtime()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::emulator::{
+        cpu::tests::cpu_with_program,
+        frontend::{self, Null},
+        headless::KeyScript,
+        quirks::Quirks,
+    };
+
+    #[test]
+    fn test_synth() {
+        let options = Options {
+            sample_rate: 48_000,
+            ..Options::default()
+        };
+        let mut synth = Synth::new(&options);
+
+        assert_eq!(vec![0; 800], synth.frame_samples(false, None, 0));
+
+        let tone = synth.frame_samples(true, None, 0);
+        let period = (48_000.0 / DEFAULT_FREQUENCY) as usize;
+        assert_eq!(800, tone.len());
+        assert!(tone[..period / 2].iter().all(|&s| s == 8_000));
+        assert_eq!(-8_000, tone[period / 2 + 1]);
+
+        let total: usize = (0..60)
+            .map(|_| synth.frame_samples(true, None, 0).len())
+            .sum();
+        assert_eq!(48_000, total);
+
+        let options = Options {
+            sample_rate: 8_000,
+            volume: 50,
+            ..Options::default()
+        };
+        let mut synth = Synth::new(&options);
+        let mut pattern = [0u8; AUDIO_PATTERN_SIZE];
+        pattern[0] = 0xF0;
+
+        let samples = synth.frame_samples(true, Some(&pattern), DEFAULT_PITCH);
+        assert!(samples[..8].iter().all(|&s| s == 16_000));
+        assert!(samples[8..16].iter().all(|&s| s == -16_000));
+
+        // Custom beeper frequency.
+        let options = Options {
+            sample_rate: 8_000,
+            frequency: 1_000.0,
+            ..Options::default()
+        };
+        let tone = Synth::new(&options).frame_samples(true, None, 0);
+        assert!(tone[..4].iter().all(|&s| s == 8_000));
+        assert!(tone[4..8].iter().all(|&s| s == -8_000));
+    }
+
+    #[test]
+    fn test_sound_timing() {
+        // Set sound timer to 6 during the first frame.
+        let program = [0x6006, 0xF018, 0x1204];
+        let mut cpu = cpu_with_program(&program, Quirks::default());
+        let mut null = Null::new(10, KeyScript::default());
+        let options = Options {
+            sample_rate: 6_000,
+            ..Options::default()
+        };
+        let mut recorder = WavRecorder::new(&mut null, &options);
+
+        frontend::run(&mut cpu, &mut recorder, 3).unwrap();
+
+        let samples = recorder.samples();
+        let audible: Vec<bool> = samples
+            .chunks(100)
+            .map(|frame| frame.iter().any(|&sample| sample != 0))
+            .collect();
+
+        assert_eq!(1000, samples.len());
+        assert_eq!(
+            vec![
+                true, true, true, true, true, true, false, false, false, false
+            ],
+            audible
+        );
+
+        let bytes = wav(samples, 6_000);
+        assert_eq!(b"RIFF", &bytes[..4]);
+        assert_eq!(44 + 2000, bytes.len());
+    }
+}
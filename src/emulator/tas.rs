@@ -0,0 +1,645 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Tool-assisted frame advance and input re-recording.
+
+use crate::emulator::{
+    EmulatorResult,
+    cpu::{Cpu, KEY_COUNT},
+    headless,
+    movie::{Movie, MovieEvent},
+};
+use std::collections::BTreeMap;
+
+/// Number of frames between automatic checkpoints.
+const CHECKPOINT_INTERVAL: u64 = 60;
+
+/// Maximum number of input log edits kept for undo.
+const HISTORY_LIMIT: usize = 256;
+
+/// Keypad keys held during single frame, one bit per key.
+pub type Keys = u16;
+
+/// Parse held keys, e.g. `5A` for keys 5 and A or `-` for none.
+///
+/// # Parameters
+/// - `text` - given hexadecimal key digits.
+///
+/// # Returns
+/// - Held keys - in case of success.
+/// - `Err`     - otherwise.
+pub fn parse_keys(text: &str) -> EmulatorResult<Keys> {
+    if text == "-" {
+        return Ok(0);
+    }
+
+    text.chars().try_fold(0, |keys, digit| {
+        let key = digit
+            .to_digit(16)
+            .ok_or_else(|| format!("invalid key '{digit}'"))?;
+
+        Ok(keys | 1 << key)
+    })
+}
+
+/// Format held keys as hexadecimal key digits.
+///
+/// # Parameters
+/// - `keys` - given held keys.
+///
+/// # Returns
+/// - Key digits or `-` if no keys are held.
+pub fn format_keys(keys: Keys) -> String {
+    if keys == 0 {
+        return "-".to_string();
+    }
+
+    (0..KEY_COUNT)
+        .filter(|key| keys & (1 << key) != 0)
+        .map(|key| format!("{key:X}"))
+        .collect()
+}
+
+/// Named input branch.
+#[derive(Debug, Clone)]
+struct Branch {
+    /// Frame the branch was saved at.
+    frame: u64,
+    /// Input log of the branch.
+    log: Vec<Keys>,
+}
+
+/// Frame advance session with editable input log.
+///
+/// Input log holds keys for every frame. Editing a frame keeps the rest of
+/// the log intact and only invalidates machine states after the edit, so
+/// seeking forward replays the existing input.
+pub struct Session {
+    /// Current machine.
+    cpu: Cpu,
+    /// Number of instructions executed per frame.
+    speed: usize,
+    /// Number of the next frame to execute.
+    frame: u64,
+    /// Held keys per frame.
+    log: Vec<Keys>,
+    /// Machine save states at the start of frames.
+    checkpoints: BTreeMap<u64, Vec<u8>>,
+    /// Named input branches.
+    branches: BTreeMap<String, Branch>,
+    /// Previous input logs for undo.
+    history: Vec<Vec<Keys>>,
+}
+
+impl Session {
+    /// Construct new `Session` object.
+    ///
+    /// # Parameters
+    /// - `cpu`   - given machine with loaded program.
+    /// - `speed` - given number of instructions per frame.
+    ///
+    /// # Returns
+    /// - New `Session` object.
+    pub fn new(cpu: Cpu, speed: usize) -> Self {
+        let mut checkpoints = BTreeMap::new();
+        checkpoints.insert(0, cpu.save_state());
+
+        Self {
+            cpu,
+            speed,
+            frame: 0,
+            log: Vec::new(),
+            checkpoints,
+            branches: BTreeMap::new(),
+            history: Vec::new(),
+        }
+    }
+
+    /// Get current machine.
+    ///
+    /// # Returns
+    /// - Machine state before the next frame.
+    pub fn cpu(&self) -> &Cpu {
+        &self.cpu
+    }
+
+    /// Get number of the next frame to execute.
+    ///
+    /// # Returns
+    /// - Current frame number.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Get input log.
+    ///
+    /// # Returns
+    /// - Held keys per frame.
+    pub fn log(&self) -> &[Keys] {
+        &self.log
+    }
+
+    /// Get keys held during specified frame.
+    ///
+    /// # Parameters
+    /// - `frame` - given frame number.
+    ///
+    /// # Returns
+    /// - Logged keys (none past the end of the log).
+    pub fn keys(&self, frame: u64) -> Keys {
+        self.log.get(frame as usize).copied().unwrap_or(0)
+    }
+
+    /// Set keys held during specified frame.
+    ///
+    /// # Parameters
+    /// - `frame` - given frame number.
+    /// - `keys`  - given held keys.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - if re-simulating current frame failed.
+    pub fn set_keys(&mut self, frame: u64, keys: Keys) -> EmulatorResult<()> {
+        if self.keys(frame) == keys && (frame as usize) < self.log.len() {
+            return Ok(());
+        }
+
+        let mut log = self.log.clone();
+
+        if log.len() <= frame as usize {
+            log.resize(frame as usize + 1, 0);
+        }
+
+        log[frame as usize] = keys;
+        self.replace_log(log)
+    }
+
+    /// Execute next frame with keys from the input log.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    pub fn advance(&mut self) -> EmulatorResult<()> {
+        let keys = self.keys(self.frame);
+
+        for key in 0..KEY_COUNT {
+            self.cpu.set_key(key as u8, keys & (1 << key) != 0);
+        }
+
+        self.cpu.frame(self.speed)?;
+        self.frame += 1;
+
+        if self.frame.is_multiple_of(CHECKPOINT_INTERVAL) {
+            self.checkpoints.insert(self.frame, self.cpu.save_state());
+        }
+
+        Ok(())
+    }
+
+    /// Execute next frame holding specified keys, re-recording the log.
+    ///
+    /// # Parameters
+    /// - `keys` - given held keys.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    pub fn record(&mut self, keys: Keys) -> EmulatorResult<()> {
+        self.set_keys(self.frame, keys)?;
+        self.advance()
+    }
+
+    /// Move to the start of specified frame.
+    ///
+    /// # Parameters
+    /// - `frame` - given frame number.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    pub fn seek(&mut self, frame: u64) -> EmulatorResult<()> {
+        if frame < self.frame {
+            self.rewind(frame)?;
+        }
+
+        while self.frame < frame {
+            self.advance()?;
+        }
+
+        Ok(())
+    }
+
+    /// Save named branch of current frame and input log.
+    ///
+    /// # Parameters
+    /// - `name` - given branch name.
+    pub fn save_branch(&mut self, name: &str) {
+        let branch = Branch {
+            frame: self.frame,
+            log: self.log.clone(),
+        };
+
+        self.branches.insert(name.to_string(), branch);
+    }
+
+    /// Restore named branch input log and move to its frame.
+    ///
+    /// # Parameters
+    /// - `name` - given branch name.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    pub fn load_branch(&mut self, name: &str) -> EmulatorResult<()> {
+        let branch = self
+            .branches
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("unknown branch '{name}'"))?;
+
+        self.replace_log(branch.log)?;
+        self.seek(branch.frame)
+    }
+
+    /// Get saved branch names.
+    ///
+    /// # Returns
+    /// - Branch names with frames.
+    pub fn branches(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.branches
+            .iter()
+            .map(|(name, branch)| (name.as_str(), branch.frame))
+    }
+
+    /// Revert last input log edit.
+    ///
+    /// # Returns
+    /// - `Ok(true)`  - if edit was reverted.
+    /// - `Ok(false)` - if there is nothing to undo.
+    /// - `Err`       - if re-simulating current frame failed.
+    pub fn undo(&mut self) -> EmulatorResult<bool> {
+        let Some(log) = self.history.pop() else {
+            return Ok(false);
+        };
+
+        self.apply_log(log)?;
+        Ok(true)
+    }
+
+    /// Convert input log up to the current frame to input movie.
+    ///
+    /// # Parameters
+    /// - `header` - given empty movie with machine configuration.
+    ///
+    /// # Returns
+    /// - Movie - in case of success.
+    /// - `Err` - otherwise.
+    pub fn to_movie(&self, mut header: Movie) -> EmulatorResult<Movie> {
+        let mut cpu = self.cpu.clone();
+        let mut previous = 0;
+
+        cpu.load_state(&self.checkpoints[&0])?;
+
+        for frame in 0..self.frame {
+            let keys = self.keys(frame);
+
+            for key in 0..KEY_COUNT {
+                let pressed = keys & (1 << key) != 0;
+
+                if pressed != (previous & (1 << key) != 0) {
+                    header.events.push(MovieEvent {
+                        frame,
+                        cycle: cpu.cycles(),
+                        key: key as u8,
+                        pressed,
+                    });
+                }
+
+                cpu.set_key(key as u8, pressed);
+            }
+
+            cpu.frame(self.speed)?;
+            previous = keys;
+        }
+
+        header.frames = self.frame;
+        Ok(header)
+    }
+
+    /// Replace input log keeping previous one for undo.
+    ///
+    /// # Parameters
+    /// - `log` - given new input log.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - if re-simulating current frame failed.
+    fn replace_log(&mut self, log: Vec<Keys>) -> EmulatorResult<()> {
+        if self.history.len() >= HISTORY_LIMIT {
+            self.history.remove(0);
+        }
+
+        self.history.push(self.log.clone());
+        self.apply_log(log)
+    }
+
+    /// Switch to input log, invalidating states after first difference.
+    ///
+    /// # Parameters
+    /// - `log` - given new input log.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - if re-simulating current frame failed.
+    fn apply_log(&mut self, log: Vec<Keys>) -> EmulatorResult<()> {
+        let length = self.log.len().max(log.len());
+        let changed = (0..length)
+            .find(|&i| log.get(i).copied().unwrap_or(0) != self.keys(i as u64))
+            .map(|i| i as u64);
+
+        self.log = log;
+
+        let Some(changed) = changed else {
+            return Ok(());
+        };
+
+        self.checkpoints.retain(|&frame, _| frame <= changed);
+
+        if changed < self.frame {
+            let frame = self.frame;
+
+            self.rewind(changed)?;
+            self.seek(frame)?;
+        }
+
+        Ok(())
+    }
+
+    /// Restore the nearest checkpoint at or before specified frame.
+    ///
+    /// # Parameters
+    /// - `frame` - given frame number.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn rewind(&mut self, frame: u64) -> EmulatorResult<()> {
+        let (&start, state) = self
+            .checkpoints
+            .range(..=frame)
+            .next_back()
+            .ok_or("no checkpoint before frame")?;
+
+        self.cpu.load_state(state)?;
+        self.frame = start;
+
+        Ok(())
+    }
+}
+
+/// Console commands help.
+pub const HELP: &str = "\
+advance [n] [keys]  execute n frames holding keys (re-records input)
+play [n]            execute n frames with recorded input
+seek <frame>        move to the start of frame
+edit <frame> <keys> set keys held during frame
+undo                revert last input edit
+branch <name>       save branch of current frame and input
+load <name>         restore branch
+branches            list saved branches
+log [from] [to]     print recorded input
+show                print the screen
+export <file>       write input up to current frame as movie
+quit                exit
+keys are hexadecimal digits (e.g. 5A) or '-' for none";
+
+/// Execute single console command.
+///
+/// # Parameters
+/// - `session` - given frame advance session.
+/// - `line`    - given command line.
+/// - `header`  - given empty movie with machine configuration for export.
+///
+/// # Returns
+/// - Command output - in case of success.
+/// - `None`         - if user requested to quit.
+/// - `Err`          - otherwise.
+pub fn execute(
+    session: &mut Session,
+    line: &str,
+    header: &Movie,
+) -> EmulatorResult<Option<String>> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let number = |index: usize, default: u64| -> EmulatorResult<u64> {
+        match tokens.get(index) {
+            Some(token) => token
+                .parse()
+                .map_err(|_| format!("invalid number '{token}'")),
+            None => Ok(default),
+        }
+    };
+    let argument = |index: usize| {
+        tokens
+            .get(index)
+            .copied()
+            .ok_or_else(|| format!("missing argument of '{}'", tokens[0]))
+    };
+
+    match tokens.first().copied() {
+        None => {}
+        Some("advance" | "a") => {
+            let keys = match tokens.get(2) {
+                Some(keys) => parse_keys(keys)?,
+                None => session.keys(session.frame()),
+            };
+
+            for _ in 0..number(1, 1)? {
+                session.record(keys)?;
+            }
+        }
+        Some("play" | "p") => {
+            let frame = session.frame() + number(1, 1)?;
+            session.seek(frame)?;
+        }
+        Some("seek" | "s") => session.seek(number(1, 0)?)?,
+        Some("edit" | "e") => {
+            session.set_keys(number(1, 0)?, parse_keys(argument(2)?)?)?
+        }
+        Some("undo" | "u") => {
+            let output = match session.undo()? {
+                true => status(session),
+                false => "nothing to undo".to_string(),
+            };
+
+            return Ok(Some(output));
+        }
+        Some("branch" | "b") => session.save_branch(argument(1)?),
+        Some("load" | "l") => session.load_branch(argument(1)?)?,
+        Some("branches") => {
+            let branches: Vec<String> = session
+                .branches()
+                .map(|(name, frame)| format!("{name} @ {frame}"))
+                .collect();
+
+            return Ok(Some(branches.join("\n")));
+        }
+        Some("log") => {
+            let from = number(1, 0)?;
+            let to = number(2, session.log().len() as u64)?;
+            let lines: Vec<String> = (from..to)
+                .map(|frame| {
+                    format!("{frame:>8} {}", format_keys(session.keys(frame)))
+                })
+                .collect();
+
+            return Ok(Some(lines.join("\n")));
+        }
+        Some("show") => {
+            let screen = headless::ascii(session.cpu().display());
+            return Ok(Some(format!("{screen}{}", status(session))));
+        }
+        Some("export") => {
+            let filename = argument(1)?;
+            session.to_movie(header.clone())?.save(filename)?;
+        }
+        Some("help" | "h" | "?") => return Ok(Some(HELP.to_string())),
+        Some("quit" | "q") => return Ok(None),
+        Some(command) => return Err(format!("unknown command '{command}'")),
+    }
+
+    Ok(Some(status(session)))
+}
+
+/// Format session status line.
+///
+/// # Parameters
+/// - `session` - given frame advance session.
+///
+/// # Returns
+/// - Current frame, program counter and logged keys.
+fn status(session: &Session) -> String {
+    let frame = session.frame();
+
+    format!(
+        "frame {frame}  pc {:03X}  cycles {}  keys {}",
+        session.cpu().pc(),
+        session.cpu().cycles(),
+        format_keys(session.keys(frame))
+    )
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::emulator::{
+        cpu::{START_ADDR, Timing, tests::cpu_with_program},
+        quirks::Quirks,
+        random::Algorithm,
+    };
+
+    /// Program counting frames with key 5 held in V1.
+    const PROGRAM: [u16; 5] = [0x6005, 0xE0A1, 0x7101, 0xD001, 0x1202];
+
+    /// Construct session running test program.
+    fn session() -> Session {
+        Session::new(cpu_with_program(&PROGRAM, Quirks::default()), 10)
+    }
+
+    #[test]
+    fn test_keys_format() {
+        assert_eq!(0x0421, parse_keys("05A").unwrap());
+        assert_eq!(0, parse_keys("-").unwrap());
+        assert!(parse_keys("G").is_err());
+        assert_eq!("05A", format_keys(0x0421));
+        assert_eq!("-", format_keys(0));
+    }
+
+    #[test]
+    fn test_record_and_rerecord() {
+        let mut session = session();
+        let held = 1 << 5;
+
+        session.record(held).unwrap();
+        session.record(0).unwrap();
+        session.record(held).unwrap();
+        assert_eq!(2, session.cpu().registers()[1]);
+        assert_eq!(vec![held, 0, held], session.log());
+
+        // Re-record frame 1 keeping input of frame 2.
+        session.seek(1).unwrap();
+        session.record(held).unwrap();
+        assert_eq!(vec![held, held, held], session.log());
+        session.seek(3).unwrap();
+        assert_eq!(3, session.cpu().registers()[1]);
+
+        // Edit past frame while staying at current frame.
+        session.set_keys(0, 0).unwrap();
+        assert_eq!(3, session.frame());
+        assert_eq!(2, session.cpu().registers()[1]);
+
+        assert!(session.undo().unwrap());
+        assert_eq!(3, session.cpu().registers()[1]);
+    }
+
+    #[test]
+    fn test_branches_and_movie() {
+        let mut session = session();
+        let held = 1 << 5;
+
+        for _ in 0..100 {
+            session.record(held).unwrap();
+        }
+
+        session.save_branch("held");
+        session.seek(50).unwrap();
+
+        for _ in 50..100 {
+            session.record(0).unwrap();
+        }
+
+        assert_eq!(50, session.cpu().registers()[1]);
+
+        session.load_branch("held").unwrap();
+        assert_eq!(100, session.frame());
+        assert_eq!(100, session.cpu().registers()[1]);
+
+        let header = Movie::new(
+            &[],
+            0,
+            Algorithm::SplitMix,
+            Quirks::default(),
+            10,
+            Timing::Instructions,
+            START_ADDR,
+        );
+        let movie = session.to_movie(header).unwrap();
+
+        assert_eq!(100, movie.frames);
+        assert_eq!(1, movie.events.len());
+        assert!(session.load_branch("missing").is_err());
+    }
+
+    #[test]
+    fn test_console() {
+        let mut session = session();
+        let header = Movie::new(
+            &[],
+            0,
+            Algorithm::SplitMix,
+            Quirks::default(),
+            10,
+            Timing::Instructions,
+            START_ADDR,
+        );
+        let mut run = |line: &str| execute(&mut session, line, &header);
+
+        assert!(run("advance 3 5").unwrap().unwrap().starts_with("frame 3"));
+        assert!(run("seek 1").is_ok());
+        assert!(run("edit 2 -").is_ok());
+        assert_eq!("       1 5\n       2 -", run("log 1 3").unwrap().unwrap());
+        assert!(run("play 2").unwrap().unwrap().starts_with("frame 3"));
+        assert!(run("load x").is_err());
+        assert!(run("jump").is_err());
+        assert!(run("quit").unwrap().is_none());
+    }
+}
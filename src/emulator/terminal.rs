@@ -0,0 +1,404 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Terminal frontend rendering display with Unicode block characters.
+
+use crate::emulator::{
+    EmulatorResult,
+    cpu::{DISPLAY_HEIGHT, DISPLAY_WIDTH, KEY_COUNT},
+    frontend::{Frame, Frontend},
+};
+use std::{
+    fmt::Write as _,
+    io::{self, Read, Write},
+    mem::MaybeUninit,
+};
+
+/// Default keymap in keypad keys order (0-F) for QWERTY keyboards.
+///
+/// ```text
+/// 1 2 3 C        1 2 3 4
+/// 4 5 6 D   ->   q w e r
+/// 7 8 9 E        a s d f
+/// A 0 B F        z x c v
+/// ```
+pub const DEFAULT_KEYMAP: &str = "x123qweasdzc4rfv";
+
+/// Number of frames key is considered held after terminal reports it.
+///
+/// Terminals report only key presses (repeated while held), so the release
+/// is emulated by timeout.
+const KEY_HOLD_FRAMES: u8 = 12;
+
+/// Escape key code.
+const KEY_ESCAPE: u8 = 0x1B;
+
+/// Ctrl-C key code.
+const KEY_INTERRUPT: u8 = 0x03;
+
+/// Tab key code.
+const KEY_SCREENSHOT: u8 = b'\t';
+
+/// Characters used to render display pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Glyphs {
+    /// Upper and lower half blocks, two pixels per character.
+    HalfBlock,
+    /// Braille patterns, eight pixels per character.
+    Braille,
+}
+
+/// RGB color.
+pub type Color = (u8, u8, u8);
+
+/// Default lit and unlit pixel colors.
+pub const DEFAULT_PALETTE: (Color, Color) =
+    ((0xFF, 0xCC, 0x00), (0x99, 0x66, 0x00));
+
+/// Terminal frontend options.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// Keyboard characters in keypad keys order (0-F) (looked up in ROM
+    /// database if not set).
+    pub keymap: Option<String>,
+    /// Characters used to render display pixels.
+    pub glyphs: Glyphs,
+    /// Lit and unlit pixel colors (looked up in ROM database if not set).
+    pub palette: Option<(Color, Color)>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            keymap: None,
+            glyphs: Glyphs::HalfBlock,
+            palette: None,
+        }
+    }
+}
+
+/// Parse keymap string.
+///
+/// # Parameters
+/// - `keymap` - given keyboard characters in keypad keys order (0-F).
+///
+/// # Returns
+/// - Keypad key per keyboard byte - in case of success.
+/// - `Err`                         - otherwise.
+pub fn parse_keymap(keymap: &str) -> EmulatorResult<[Option<u8>; 128]> {
+    let mut result = [None; 128];
+
+    if keymap.len() != KEY_COUNT || !keymap.is_ascii() {
+        return Err(format!(
+            "keymap must consist of {KEY_COUNT} ASCII characters"
+        ));
+    }
+
+    for (key, byte) in keymap.bytes().enumerate() {
+        let byte = byte.to_ascii_lowercase() as usize;
+
+        if result[byte].is_some() {
+            return Err(format!(
+                "keymap has duplicate key '{}'",
+                byte as u8 as char
+            ));
+        }
+
+        result[byte] = Some(key as u8);
+    }
+
+    Ok(result)
+}
+
+/// Render display as colored text.
+///
+/// # Parameters
+/// - `display` - given display pixels.
+/// - `options` - given rendering options.
+///
+/// # Returns
+/// - Display text with ANSI colors, one line per character row.
+pub fn render(display: &[bool], options: &Options) -> String {
+    let pixel = |x: usize, y: usize| {
+        x < DISPLAY_WIDTH
+            && y < DISPLAY_HEIGHT
+            && display[y * DISPLAY_WIDTH + x]
+    };
+    let ((fr, fg, fb), (br, bg, bb)) =
+        options.palette.unwrap_or(DEFAULT_PALETTE);
+    let mut result = String::new();
+
+    let _ =
+        write!(result, "\x1b[38;2;{fr};{fg};{fb}m\x1b[48;2;{br};{bg};{bb}m");
+
+    match options.glyphs {
+        Glyphs::HalfBlock => {
+            for y in (0..DISPLAY_HEIGHT).step_by(2) {
+                for x in 0..DISPLAY_WIDTH {
+                    result.push(match (pixel(x, y), pixel(x, y + 1)) {
+                        (false, false) => ' ',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (true, true) => '█',
+                    });
+                }
+
+                result.push_str("\r\n");
+            }
+        }
+        Glyphs::Braille => {
+            // Braille dots bit positions of 2x4 character cell.
+            const DOTS: [[u32; 2]; 4] =
+                [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+            for y in (0..DISPLAY_HEIGHT).step_by(4) {
+                for x in (0..DISPLAY_WIDTH).step_by(2) {
+                    let mut code = 0x2800;
+
+                    for (dy, row) in DOTS.iter().enumerate() {
+                        for (dx, dot) in row.iter().enumerate() {
+                            if pixel(x + dx, y + dy) {
+                                code |= dot;
+                            }
+                        }
+                    }
+
+                    result.push(char::from_u32(code).unwrap_or(' '));
+                }
+
+                result.push_str("\r\n");
+            }
+        }
+    }
+
+    result.push_str("\x1b[0m");
+    result
+}
+
+/// Terminal frontend struct.
+///
+/// Switches terminal to raw mode and alternate screen on construction and
+/// restores it when dropped.
+pub struct Terminal {
+    /// Rendering options.
+    options: Options,
+    /// Keypad key per keyboard byte.
+    keymap: [Option<u8>; 128],
+    /// Remaining frames each keypad key is held.
+    held: [u8; KEY_COUNT],
+    /// Original terminal attributes.
+    original: libc::termios,
+    /// Last rendered display text.
+    last_frame: String,
+    /// Whether user requested to quit.
+    quit: bool,
+    /// Whether user requested screenshot.
+    screenshot: bool,
+    /// Whether buzzer tone is playing.
+    tone: bool,
+}
+
+impl Terminal {
+    /// Construct new `Terminal` object.
+    ///
+    /// # Parameters
+    /// - `options` - given terminal frontend options.
+    ///
+    /// # Returns
+    /// - New `Terminal` object - in case of success.
+    /// - `Err`                 - otherwise.
+    pub fn new(options: &Options) -> EmulatorResult<Self> {
+        let keymap =
+            parse_keymap(options.keymap.as_deref().unwrap_or(DEFAULT_KEYMAP))?;
+        let mut termios = MaybeUninit::<libc::termios>::uninit();
+
+        // SAFETY: tcgetattr fully initializes termios on success.
+        let original = unsafe {
+            if libc::tcgetattr(libc::STDIN_FILENO, termios.as_mut_ptr()) != 0 {
+                return Err("standard input is not a terminal".to_string());
+            }
+
+            termios.assume_init()
+        };
+
+        let mut raw = original;
+
+        // SAFETY: raw is a valid termios structure.
+        unsafe { libc::cfmakeraw(&mut raw) };
+
+        raw.c_oflag |= libc::OPOST;
+        raw.c_cc[libc::VMIN] = 0;
+        raw.c_cc[libc::VTIME] = 0;
+
+        // SAFETY: raw is a valid termios structure.
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) }
+            != 0
+        {
+            return Err("failed to switch terminal to raw mode".to_string());
+        }
+
+        let terminal = Self {
+            options: options.clone(),
+            keymap,
+            held: [0; KEY_COUNT],
+            original,
+            last_frame: String::new(),
+            quit: false,
+            screenshot: false,
+            tone: false,
+        };
+
+        terminal.write("\x1b[?1049h\x1b[?25l\x1b[2J")?;
+
+        Ok(terminal)
+    }
+
+    /// Write text to terminal.
+    ///
+    /// # Parameters
+    /// - `text` - given text to write.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn write(&self, text: &str) -> EmulatorResult<()> {
+        let mut stdout = io::stdout().lock();
+
+        stdout
+            .write_all(text.as_bytes())
+            .and_then(|_| stdout.flush())
+            .map_err(|error| format!("Error write terminal: {error}"))
+    }
+}
+
+impl Frontend for Terminal {
+    fn poll_keys(
+        &mut self,
+        keypad: &mut [bool; KEY_COUNT],
+    ) -> EmulatorResult<()> {
+        let mut buffer = [0u8; 64];
+        let count = io::stdin()
+            .lock()
+            .read(&mut buffer)
+            .map_err(|error| format!("Error read keyboard: {error}"))?;
+
+        for held in self.held.iter_mut() {
+            *held = held.saturating_sub(1);
+        }
+
+        let input = &buffer[..count];
+
+        // Lone escape quits, escape sequences (e.g. arrow keys) are ignored.
+        if input == [KEY_ESCAPE] || input.contains(&KEY_INTERRUPT) {
+            self.quit = true;
+        }
+
+        if input.contains(&KEY_SCREENSHOT) {
+            self.screenshot = true;
+        }
+
+        for &byte in input {
+            let byte = byte.to_ascii_lowercase() as usize;
+
+            if let Some(Some(key)) = self.keymap.get(byte) {
+                self.held[*key as usize] = KEY_HOLD_FRAMES;
+            }
+        }
+
+        for (pressed, held) in keypad.iter_mut().zip(self.held) {
+            *pressed = held > 0;
+        }
+
+        Ok(())
+    }
+
+    fn quit_requested(&self) -> bool {
+        self.quit
+    }
+
+    fn screenshot_requested(&mut self) -> bool {
+        std::mem::take(&mut self.screenshot)
+    }
+
+    fn present(&mut self, frame: &Frame) -> EmulatorResult<()> {
+        let display = render(frame.display, &self.options);
+        let mut output = String::from("\x1b[H");
+
+        if display != self.last_frame {
+            output.push_str(&display);
+            self.last_frame = display;
+        } else {
+            let rows = self.last_frame.matches("\r\n").count();
+            let _ = write!(output, "\x1b[{}H", rows + 1);
+        }
+
+        let _ = write!(
+            output,
+            "\x1b[KPC: {:03X}  IPS: {:>6}  DT: {:02X}  ST: {:02X}  \
+             [Tab] screenshot  [Esc] quit",
+            frame.pc, frame.ips, frame.dt, frame.st
+        );
+
+        self.write(&output)
+    }
+
+    fn set_tone(&mut self, playing: bool) -> EmulatorResult<()> {
+        // Terminal bell is the only sound available, ring it once per tone.
+        if playing && !self.tone {
+            self.write("\x07")?;
+        }
+
+        self.tone = playing;
+        Ok(())
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = self.write("\x1b[0m\x1b[?25h\x1b[?1049l");
+
+        // SAFETY: original is a valid termios structure.
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original);
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_keymap() {
+        let keymap = parse_keymap(DEFAULT_KEYMAP).unwrap();
+
+        assert_eq!(Some(0x0), keymap[b'x' as usize]);
+        assert_eq!(Some(0xC), keymap[b'4' as usize]);
+        assert_eq!(Some(0xF), keymap[b'v' as usize]);
+        assert_eq!(None, keymap[b'p' as usize]);
+
+        assert!(parse_keymap("x123").is_err());
+        assert!(parse_keymap("xx23qweasdzcr4fv").is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let mut display = [false; DISPLAY_WIDTH * DISPLAY_HEIGHT];
+        display[0] = true;
+        display[DISPLAY_WIDTH + 1] = true;
+
+        let mut options = Options::default();
+        let text = render(&display, &options);
+        let rows: Vec<&str> = text.split("\r\n").collect();
+
+        assert_eq!(DISPLAY_HEIGHT / 2 + 1, rows.len());
+        assert!(rows[0].contains("m▀▄ "));
+
+        options.glyphs = Glyphs::Braille;
+        let text = render(&display, &options);
+        let rows: Vec<&str> = text.split("\r\n").collect();
+
+        assert_eq!(DISPLAY_HEIGHT / 4 + 1, rows.len());
+        assert!(rows[0].contains("m\u{2811}\u{2800}"));
+    }
+}
@@ -0,0 +1,435 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! Execution traces recording and comparison.
+
+use crate::emulator::{
+    EmulatorResult,
+    cpu::{Cpu, REGISTER_COUNT},
+    disasm::Decodable,
+    opcode::OpCode,
+};
+use std::{collections::VecDeque, fmt, str::FromStr};
+
+/// Number of instructions displayed before divergence point.
+pub const CONTEXT_SIZE: usize = 8;
+
+/// CPU state snapshot taken before executing single instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceEntry {
+    /// Number of instructions executed before this one.
+    pub cycle: u64,
+    /// Address of the instruction.
+    pub pc: u16,
+    /// Instruction raw bytes.
+    pub opcode: u16,
+    /// General purpose registers.
+    pub registers: [u8; REGISTER_COUNT],
+    /// I register.
+    pub register_i: u16,
+    /// Stack pointer.
+    pub sp: u8,
+    /// Delay timer register.
+    pub dt: u8,
+    /// Sound timer register.
+    pub st: u8,
+}
+
+impl TraceEntry {
+    /// Capture state of the CPU before executing next instruction.
+    ///
+    /// # Parameters
+    /// - `cpu` - given CPU to capture.
+    ///
+    /// # Returns
+    /// - New `TraceEntry` object.
+    pub fn capture(cpu: &Cpu) -> Self {
+        let pc = cpu.pc();
+        let memory = cpu.memory();
+        let pos = pc as usize;
+        let opcode = match (memory.get(pos), memory.get(pos + 1)) {
+            (Some(&high), Some(&low)) => u16::from_be_bytes([high, low]),
+            _ => 0,
+        };
+
+        Self {
+            cycle: cpu.cycles(),
+            pc,
+            opcode,
+            registers: *cpu.registers(),
+            register_i: cpu.register_i(),
+            sp: cpu.sp(),
+            dt: cpu.dt(),
+            st: cpu.st(),
+        }
+    }
+
+    /// Get list of differences with other entry.
+    ///
+    /// # Parameters
+    /// - `other` - given entry to compare with.
+    ///
+    /// # Returns
+    /// - List of human readable differences.
+    pub fn differences(&self, other: &Self) -> Vec<String> {
+        let mut result = Vec::new();
+
+        if self.pc != other.pc {
+            result.push(format!("PC: {:03X} != {:03X}", self.pc, other.pc));
+        }
+
+        if self.opcode != other.opcode {
+            let (a, b) = (self.opcode, other.opcode);
+            result.push(format!("OP: {a:04X} != {b:04X}"));
+        }
+
+        for (i, (a, b)) in
+            self.registers.iter().zip(other.registers).enumerate()
+        {
+            if *a != b {
+                result.push(format!("V{i:X}: {a:02X} != {b:02X}"));
+            }
+        }
+
+        if self.register_i != other.register_i {
+            let (a, b) = (self.register_i, other.register_i);
+            result.push(format!("I: {a:03X} != {b:03X}"));
+        }
+
+        if self.sp != other.sp {
+            result.push(format!("SP: {} != {}", self.sp, other.sp));
+        }
+
+        if self.dt != other.dt {
+            result.push(format!("DT: {:02X} != {:02X}", self.dt, other.dt));
+        }
+
+        if self.st != other.st {
+            result.push(format!("ST: {:02X} != {:02X}", self.st, other.st));
+        }
+
+        result
+    }
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:08} {:03X} {:04X}", self.cycle, self.pc, self.opcode)?;
+
+        write!(f, " V=")?;
+        for register in self.registers {
+            write!(f, "{register:02X}")?;
+        }
+
+        write!(
+            f,
+            " I={:03X} SP={:X} DT={:02X} ST={:02X}",
+            self.register_i, self.sp, self.dt, self.st
+        )
+    }
+}
+
+impl FromStr for TraceEntry {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let error = || format!("malformed trace line '{line}'");
+        let fields: Vec<&str> = line.split_whitespace().collect();
+
+        let [cycle, pc, opcode, registers, register_i, sp, dt, st] = fields[..]
+        else {
+            return Err(error());
+        };
+
+        let field = |value: &str, prefix: &str| -> Result<u16, String> {
+            let value = value.strip_prefix(prefix).ok_or_else(error)?;
+            u16::from_str_radix(value, 16).map_err(|_| error())
+        };
+
+        let registers = registers.strip_prefix("V=").ok_or_else(error)?;
+
+        if registers.len() != REGISTER_COUNT * 2 {
+            return Err(error());
+        }
+
+        let mut values = [0u8; REGISTER_COUNT];
+
+        for (i, value) in values.iter_mut().enumerate() {
+            let digits = &registers[i * 2..i * 2 + 2];
+            *value = u8::from_str_radix(digits, 16).map_err(|_| error())?;
+        }
+
+        Ok(Self {
+            cycle: cycle.parse().map_err(|_| error())?,
+            pc: field(pc, "")?,
+            opcode: field(opcode, "")?,
+            registers: values,
+            register_i: field(register_i, "I=")?,
+            sp: field(sp, "SP=")? as u8,
+            dt: field(dt, "DT=")? as u8,
+            st: field(st, "ST=")? as u8,
+        })
+    }
+}
+
+/// First point where two executions diverge.
+#[derive(Debug)]
+pub struct Divergence {
+    /// Instructions executed before divergence (identical in both traces).
+    pub context: Vec<TraceEntry>,
+    /// First execution state at divergence point.
+    pub first: Option<TraceEntry>,
+    /// Second execution state at divergence point.
+    pub second: Option<TraceEntry>,
+    /// Memory differences as (address, first value, second value).
+    pub memory: Vec<(usize, u8, u8)>,
+}
+
+impl Divergence {
+    /// Print divergence report.
+    pub fn report(&self) {
+        println!("Context:");
+
+        for entry in &self.context {
+            println!("  {}", describe(entry));
+        }
+
+        println!("Divergence:");
+
+        for (label, entry) in [("A", &self.first), ("B", &self.second)] {
+            match entry {
+                Some(entry) => println!("  {label}: {}", describe(entry)),
+                None => println!("  {label}: <end of trace>"),
+            }
+        }
+
+        if let (Some(first), Some(second)) = (&self.first, &self.second) {
+            for difference in first.differences(second) {
+                println!("  {difference}");
+            }
+        }
+
+        for (addr, first, second) in &self.memory {
+            println!("  [{addr:03X}]: {first:02X} != {second:02X}");
+        }
+    }
+}
+
+/// Get trace entry description with instruction mnemonic.
+///
+/// # Parameters
+/// - `entry` - given trace entry.
+///
+/// # Returns
+/// - Trace entry description.
+fn describe(entry: &TraceEntry) -> String {
+    format!("{entry}  {}", OpCode::new(entry.opcode).decode())
+}
+
+/// Parse execution trace text.
+///
+/// # Parameters
+/// - `text` - given trace text with one entry per line.
+///
+/// # Returns
+/// - Trace entries - in case of success.
+/// - `Err`         - otherwise.
+pub fn parse(text: &str) -> EmulatorResult<Vec<TraceEntry>> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(TraceEntry::from_str)
+        .collect()
+}
+
+/// Find first divergence between two recorded traces.
+///
+/// # Parameters
+/// - `first`  - given first trace.
+/// - `second` - given second trace.
+///
+/// # Returns
+/// - First divergence - if traces differ.
+/// - `None`           - otherwise.
+pub fn diff_traces(
+    first: &[TraceEntry],
+    second: &[TraceEntry],
+) -> Option<Divergence> {
+    let count = first.len().max(second.len());
+
+    (0..count)
+        .find(|&i| first.get(i) != second.get(i))
+        .map(|i| Divergence {
+            context: first[i.saturating_sub(CONTEXT_SIZE)..i].to_vec(),
+            first: first.get(i).copied(),
+            second: second.get(i).copied(),
+            memory: Vec::new(),
+        })
+}
+
+/// Run two machines in lockstep and find first divergence.
+///
+/// # Parameters
+/// - `first`  - given first machine.
+/// - `second` - given second machine.
+/// - `speed`  - given number of instructions per frame.
+/// - `limit`  - given maximum number of instructions to execute.
+///
+/// # Returns
+/// - First divergence - if executions differ.
+/// - `None`           - otherwise.
+/// - `Err`            - if both machines failed at the same point.
+pub fn diff_machines(
+    first: &mut Cpu,
+    second: &mut Cpu,
+    speed: usize,
+    limit: u64,
+) -> EmulatorResult<Option<Divergence>> {
+    let mut context = VecDeque::with_capacity(CONTEXT_SIZE);
+
+    for _ in 0..limit {
+        let entry_a = TraceEntry::capture(first);
+        let entry_b = TraceEntry::capture(second);
+
+        if entry_a != entry_b {
+            return Ok(Some(divergence(&context, first, second, None)));
+        }
+
+        let result_a = first.cycle(speed);
+        let result_b = second.cycle(speed);
+
+        match (result_a, result_b) {
+            (Ok(_), Ok(_)) => {}
+            (Err(error), Err(_)) => return Err(error),
+            _ => {
+                let entries = Some((entry_a, entry_b));
+                return Ok(Some(divergence(&context, first, second, entries)));
+            }
+        }
+
+        if first.memory() != second.memory() {
+            let entries = Some((entry_a, entry_b));
+            return Ok(Some(divergence(&context, first, second, entries)));
+        }
+
+        if context.len() == CONTEXT_SIZE {
+            context.pop_front();
+        }
+
+        context.push_back(entry_a);
+    }
+
+    Ok(None)
+}
+
+/// Construct divergence report of two machines.
+///
+/// # Parameters
+/// - `context` - given instructions executed before divergence.
+/// - `first`   - given first machine.
+/// - `second`  - given second machine.
+/// - `entries` - given diverged instruction states, captured if `None`.
+///
+/// # Returns
+/// - New `Divergence` object.
+fn divergence(
+    context: &VecDeque<TraceEntry>,
+    first: &Cpu,
+    second: &Cpu,
+    entries: Option<(TraceEntry, TraceEntry)>,
+) -> Divergence {
+    let (entry_a, entry_b) = entries.unwrap_or_else(|| {
+        (TraceEntry::capture(first), TraceEntry::capture(second))
+    });
+
+    let memory = first
+        .memory()
+        .iter()
+        .zip(second.memory())
+        .enumerate()
+        .filter(|(_, (a, b))| a != b)
+        .map(|(addr, (a, b))| (addr, *a, *b))
+        .collect();
+
+    Divergence {
+        context: context.iter().copied().collect(),
+        first: Some(entry_a),
+        second: Some(entry_b),
+        memory,
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::emulator::{cpu::tests::cpu_with_program, quirks::Quirks};
+
+    #[test]
+    fn test_entry_roundtrip() {
+        let mut cpu = cpu_with_program(&[0x6A42, 0xA123], Quirks::default());
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+
+        let entry = TraceEntry::capture(&cpu);
+        let parsed: TraceEntry = entry.to_string().parse().unwrap();
+
+        assert_eq!(entry, parsed);
+        assert_eq!(0x42, parsed.registers[0xA]);
+        assert_eq!(0x123, parsed.register_i);
+    }
+
+    #[test]
+    fn test_diff_traces() {
+        let cpu = cpu_with_program(&[0x6001], Quirks::default());
+        let entry = TraceEntry::capture(&cpu);
+        let mut other = entry;
+        other.registers[3] = 0x10;
+
+        assert!(diff_traces(&[entry, entry], &[entry, entry]).is_none());
+
+        let divergence = diff_traces(&[entry, entry], &[entry, other]).unwrap();
+        assert_eq!(1, divergence.context.len());
+        let differences = divergence.first.unwrap().differences(&other);
+        assert_eq!(vec!["V3: 00 != 10"], differences);
+
+        let divergence = diff_traces(&[entry], &[entry, entry]).unwrap();
+        assert!(divergence.first.is_none());
+    }
+
+    #[test]
+    fn test_diff_machines_quirks() {
+        let program = [0x6081, 0x6104, 0x8016, 0x1206];
+        let mut first = cpu_with_program(&program, Quirks::chip8());
+        let mut second = cpu_with_program(&program, Quirks::schip());
+
+        let divergence = diff_machines(&mut first, &mut second, 10, 100)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(3, divergence.context.len());
+        assert_eq!(0x8016, divergence.context[2].opcode);
+        assert_eq!(0x1206, divergence.first.unwrap().opcode);
+    }
+
+    #[test]
+    fn test_diff_machines_memory() {
+        let mut first = cpu_with_program(&[0x1200, 0x0000], Quirks::chip8());
+        let mut second = cpu_with_program(&[0x1200, 0x00FF], Quirks::chip8());
+
+        let divergence = diff_machines(&mut first, &mut second, 10, 100)
+            .unwrap()
+            .unwrap();
+
+        assert!(divergence.context.is_empty());
+        assert_eq!(vec![(0x203, 0x00, 0xFF)], divergence.memory);
+    }
+
+    #[test]
+    fn test_diff_machines_equal() {
+        let program = [0x6001, 0x1202];
+        let mut first = cpu_with_program(&program, Quirks::chip8());
+        let mut second = cpu_with_program(&program, Quirks::schip());
+
+        let result = diff_machines(&mut first, &mut second, 10, 100).unwrap();
+        assert!(result.is_none());
+    }
+}
@@ -0,0 +1,686 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! COSMAC VIP hardware emulation running the original CHIP-8 interpreter.
+//!
+//! The interpreter image is loaded at 0x000 and the program at `START_ADDR`
+//! of 4 KB RAM. The optional monitor ROM is mapped at 0x8000 and provides
+//! interrupt routine refreshing the display and decrementing timers.
+
+mod cdp1802;
+mod cdp1861;
+
+use crate::emulator::{
+    EmulatorResult,
+    cpu::{
+        Cpu, DISPLAY_HEIGHT, DISPLAY_WIDTH, KEY_COUNT, RAM_SIZE,
+        REGISTER_COUNT, START_ADDR,
+    },
+    random::VIP_TABLE_PAGE,
+    trace::{self, Divergence, TraceEntry},
+};
+use cdp1802::{Bus, Cdp1802};
+use cdp1861::{Cdp1861, DMA_BYTES, FRAME_CYCLES};
+use std::collections::VecDeque;
+
+/// Maximum size of the CHIP-8 interpreter image.
+pub const INTERPRETER_SIZE: usize = 512;
+
+/// Monitor ROM start address.
+pub const MONITOR_ADDR: u16 = 0x8000;
+
+/// Monitor ROM size.
+pub const MONITOR_SIZE: usize = 512;
+
+/// Interpreter work area start: stack, variables and display buffer.
+pub const WORK_AREA: usize = 0xEA0;
+
+/// Initial interpreter stack pointer (R2).
+const STACK_TOP: u16 = 0xECF;
+
+/// Address of V0-VF registers.
+const VARIABLES_ADDR: usize = 0xEF0;
+
+/// Address of display buffer.
+const DISPLAY_ADDR: usize = 0xF00;
+
+/// Register holding CHIP-8 program counter.
+const PC_REGISTER: usize = 0x5;
+
+/// Register holding CHIP-8 I register.
+const I_REGISTER: usize = 0xA;
+
+/// Register holding delay (high byte) and sound (low byte) timers.
+const TIMER_REGISTER: usize = 0x8;
+
+/// Register holding last RAM page, set up by the monitor before the
+/// interpreter is started.
+const RAM_PAGE_REGISTER: usize = 0x1;
+
+/// Instruction fetching CHIP-8 opcode byte (LDA R5).
+const FETCH_OPCODE: u8 = 0x40 | PC_REGISTER as u8;
+
+/// Maximum number of frames spent in single CHIP-8 instruction.
+const STEP_FRAMES: u64 = 10;
+
+/// COSMAC VIP run options.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// CHIP-8 interpreter image file name.
+    pub interpreter: String,
+    /// Monitor ROM image file name.
+    pub monitor: Option<String>,
+}
+
+/// COSMAC VIP memory map and I/O devices.
+#[derive(Clone)]
+struct Hardware {
+    /// RAM mirrored below the monitor ROM.
+    ram: [u8; RAM_SIZE],
+    /// Monitor ROM mirrored above `MONITOR_ADDR`.
+    monitor: Option<[u8; MONITOR_SIZE]>,
+    /// Video display controller.
+    video: Cdp1861,
+    /// Hexadecimal keypad keys state.
+    keypad: [bool; KEY_COUNT],
+    /// Key selected by OUT 2 and reported on EF3.
+    key_latch: u8,
+}
+
+impl Hardware {
+    /// Read memory byte without side effects.
+    ///
+    /// # Parameters
+    /// - `addr` - given memory address.
+    ///
+    /// # Returns
+    /// - Memory byte, zero for missing monitor ROM.
+    fn peek(&self, addr: u16) -> u8 {
+        if addr < MONITOR_ADDR {
+            return self.ram[addr as usize % RAM_SIZE];
+        }
+
+        self.monitor
+            .as_ref()
+            .map_or(0, |rom| rom[addr as usize % MONITOR_SIZE])
+    }
+}
+
+impl Bus for Hardware {
+    fn read(&mut self, addr: u16) -> u8 {
+        self.peek(addr)
+    }
+
+    fn write(&mut self, addr: u16, value: u8) {
+        if addr < MONITOR_ADDR {
+            self.ram[addr as usize % RAM_SIZE] = value;
+        }
+    }
+
+    fn output(&mut self, port: u8, value: u8) {
+        match port {
+            1 => self.video.set_enabled(false),
+            2 => self.key_latch = value & 0xF,
+            _ => {}
+        }
+    }
+
+    fn input(&mut self, port: u8) -> u8 {
+        if port == 1 {
+            self.video.set_enabled(true);
+        }
+
+        0
+    }
+
+    fn flag(&self, flag: u8) -> bool {
+        match flag {
+            1 => self.video.ef1(),
+            3 => self.keypad[self.key_latch as usize],
+            _ => false,
+        }
+    }
+}
+
+/// Emulated COSMAC VIP.
+#[derive(Clone)]
+pub struct Vip {
+    /// CDP1802 CPU.
+    cpu: Cdp1802,
+    /// Memory and I/O devices.
+    hardware: Hardware,
+    /// CHIP-8 interpreter image.
+    interpreter: Vec<u8>,
+    /// Number of elapsed machine cycles.
+    cycles: u64,
+    /// Number of executed CHIP-8 opcode byte fetches.
+    fetches: u64,
+    /// Number of executed CHIP-8 instructions.
+    instructions: u64,
+}
+
+impl Vip {
+    /// Construct new `Vip` object.
+    ///
+    /// # Parameters
+    /// - `interpreter` - given CHIP-8 interpreter image.
+    /// - `monitor`     - given monitor ROM image.
+    ///
+    /// # Returns
+    /// - New `Vip` object - in case of success.
+    /// - `Err`            - if images have invalid size.
+    pub fn new(
+        interpreter: &[u8],
+        monitor: Option<&[u8]>,
+    ) -> EmulatorResult<Self> {
+        if interpreter.is_empty() || interpreter.len() > INTERPRETER_SIZE {
+            return Err(format!(
+                "Interpreter image must be 1-{INTERPRETER_SIZE} bytes, got {}",
+                interpreter.len()
+            ));
+        }
+
+        let monitor = match monitor {
+            Some(rom) => Some(rom.try_into().map_err(|_| {
+                format!(
+                    "Monitor ROM must be {MONITOR_SIZE} bytes, got {}",
+                    rom.len()
+                )
+            })?),
+            None => None,
+        };
+
+        let mut vip = Self {
+            cpu: Cdp1802::new(),
+            hardware: Hardware {
+                ram: [0; RAM_SIZE],
+                monitor,
+                video: Cdp1861::new(),
+                keypad: [false; KEY_COUNT],
+                key_latch: 0,
+            },
+            interpreter: interpreter.to_vec(),
+            cycles: 0,
+            fetches: 0,
+            instructions: 0,
+        };
+
+        vip.reset();
+        Ok(vip)
+    }
+
+    /// Reset machine and load the interpreter.
+    fn reset(&mut self) {
+        self.cpu = Cdp1802::new();
+        self.cpu
+            .set_register(RAM_PAGE_REGISTER, (RAM_SIZE as u16 - 1) & 0xFF00);
+        self.hardware.ram = [0; RAM_SIZE];
+        self.hardware.ram[..self.interpreter.len()]
+            .copy_from_slice(&self.interpreter);
+        self.hardware.video = Cdp1861::new();
+        self.hardware.key_latch = 0;
+        self.cycles = 0;
+        self.fetches = 0;
+        self.instructions = 0;
+    }
+
+    /// Reset machine, load program and run interpreter initialization.
+    ///
+    /// # Parameters
+    /// - `program_data` - given program data bytes.
+    ///
+    /// # Returns
+    /// - `Ok`  - if interpreter is ready to fetch the first instruction.
+    /// - `Err` - if program overlaps interpreter work area or interpreter
+    ///   never fetches.
+    pub fn load_program(&mut self, program_data: &[u8]) -> EmulatorResult<()> {
+        if program_data.len() > WORK_AREA - START_ADDR {
+            return Err(format!(
+                "Program of {} bytes overlaps interpreter work area at {:03X}",
+                program_data.len(),
+                WORK_AREA
+            ));
+        }
+
+        self.reset();
+
+        let end = START_ADDR + program_data.len();
+        self.hardware.ram[START_ADDR..end].copy_from_slice(program_data);
+
+        self.run_until(|vip| vip.fetch_ready())
+    }
+
+    /// Set keypad key state.
+    ///
+    /// # Parameters
+    /// - `key`     - given key (0x0-0xF).
+    /// - `pressed` - given key state.
+    pub fn set_key(&mut self, key: u8, pressed: bool) {
+        self.hardware.keypad[(key & 0xF) as usize] = pressed;
+    }
+
+    /// Execute single CPU instruction, DMA transfer or interrupt.
+    ///
+    /// # Returns
+    /// - Number of spent machine cycles.
+    pub fn tick(&mut self) -> u32 {
+        let video = &self.hardware.video;
+
+        let cycles = if video.dma_requested() {
+            let mut bytes = [0; DMA_BYTES];
+
+            for byte in bytes.iter_mut() {
+                *byte = self.cpu.dma_out(&mut self.hardware);
+            }
+
+            self.hardware.video.dma(bytes);
+            DMA_BYTES as u32 * cdp1802::CYCLE
+        } else if video.interrupt() && self.cpu.interrupt() {
+            cdp1802::CYCLE
+        } else {
+            let cycles = self.cpu.step(&mut self.hardware);
+
+            if cycles != cdp1802::CYCLE && self.cpu.opcode() == FETCH_OPCODE {
+                self.fetches += 1;
+            }
+
+            cycles
+        };
+
+        self.hardware.video.advance(cycles);
+        self.cycles += cycles as u64;
+
+        cycles
+    }
+
+    /// Execute single 60 Hz frame.
+    pub fn frame(&mut self) {
+        let frames = self.hardware.video.frames();
+
+        while self.hardware.video.frames() == frames {
+            self.tick();
+        }
+    }
+
+    /// Execute single CHIP-8 instruction.
+    ///
+    /// Runs until the interpreter is about to fetch the next instruction.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - if the instruction does not finish in `STEP_FRAMES` frames.
+    pub fn step(&mut self) -> EmulatorResult<()> {
+        let fetches = self.fetches;
+
+        self.run_until(|vip| vip.fetches > fetches && vip.fetch_ready())?;
+        self.instructions += 1;
+
+        Ok(())
+    }
+
+    /// Execute machine until condition is met.
+    ///
+    /// # Parameters
+    /// - `done` - given stop condition.
+    ///
+    /// # Returns
+    /// - `Ok`  - if condition was met.
+    /// - `Err` - if it was not met in `STEP_FRAMES` frames.
+    fn run_until(
+        &mut self,
+        done: impl Fn(&Self) -> bool,
+    ) -> EmulatorResult<()> {
+        let limit = self.cycles + STEP_FRAMES * FRAME_CYCLES as u64;
+
+        while !done(self) {
+            if self.cycles >= limit {
+                return Err(format!(
+                    "interpreter did not fetch next instruction in \
+                     {STEP_FRAMES} frames (1802 at {:04X})",
+                    self.cpu.pc()
+                ));
+            }
+
+            self.tick();
+        }
+
+        Ok(())
+    }
+
+    /// Check whether interpreter is about to fetch CHIP-8 instruction.
+    ///
+    /// # Returns
+    /// - `true` - if next CPU instruction fetches first opcode byte.
+    fn fetch_ready(&self) -> bool {
+        let video = &self.hardware.video;
+        let interrupted = video.interrupt() && self.cpu.ie();
+
+        self.fetches.is_multiple_of(2)
+            && !video.dma_requested()
+            && !interrupted
+            && self.hardware.peek(self.cpu.pc()) == FETCH_OPCODE
+    }
+
+    /// Get CHIP-8 program counter.
+    ///
+    /// # Returns
+    /// - Address of the next CHIP-8 instruction.
+    pub fn pc(&self) -> u16 {
+        self.cpu.register(PC_REGISTER)
+    }
+
+    /// Get CHIP-8 I register.
+    ///
+    /// # Returns
+    /// - Value of the I register.
+    pub fn register_i(&self) -> u16 {
+        self.cpu.register(I_REGISTER)
+    }
+
+    /// Copy interpreter code page read by VIP random generator to high-level
+    /// machine.
+    ///
+    /// Program counter is moved to the page, so the program must be loaded
+    /// afterwards.
+    ///
+    /// # Parameters
+    /// - `cpu` - given high-level machine to update.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - if page does not fit in machine memory.
+    pub fn copy_random_table(&self, cpu: &mut Cpu) -> EmulatorResult<()> {
+        let table = self.interpreter.get(VIP_TABLE_PAGE..).unwrap_or_default();
+        cpu.load_program_at(table, VIP_TABLE_PAGE)
+    }
+
+    /// Get CHIP-8 general purpose registers.
+    ///
+    /// # Returns
+    /// - Values of V0-VF registers.
+    pub fn registers(&self) -> [u8; REGISTER_COUNT] {
+        let end = VARIABLES_ADDR + REGISTER_COUNT;
+        let mut registers = [0; REGISTER_COUNT];
+
+        registers.copy_from_slice(&self.hardware.ram[VARIABLES_ADDR..end]);
+        registers
+    }
+
+    /// Get CHIP-8 display.
+    ///
+    /// # Returns
+    /// - Display buffer pixels in row-major order.
+    pub fn display(&self) -> [bool; DISPLAY_WIDTH * DISPLAY_HEIGHT] {
+        let buffer = &self.hardware.ram[DISPLAY_ADDR..];
+
+        std::array::from_fn(|i| buffer[i / 8] & (0x80 >> (i % 8)) != 0)
+    }
+
+    /// Get RAM.
+    ///
+    /// # Returns
+    /// - RAM bytes.
+    pub fn memory(&self) -> &[u8; RAM_SIZE] {
+        &self.hardware.ram
+    }
+
+    /// Get CPU state.
+    ///
+    /// # Returns
+    /// - CDP1802 state.
+    pub fn cpu(&self) -> &Cdp1802 {
+        &self.cpu
+    }
+
+    /// Get bytes transferred to the display by DMA during the last frame.
+    ///
+    /// # Returns
+    /// - Eight bytes of each of 128 display lines.
+    pub fn video_output(&self) -> &[u8] {
+        self.hardware.video.output()
+    }
+
+    /// Check whether speaker is on.
+    ///
+    /// # Returns
+    /// - Q output state.
+    pub fn tone(&self) -> bool {
+        self.cpu.q()
+    }
+
+    /// Get number of elapsed machine cycles.
+    ///
+    /// # Returns
+    /// - Machine cycles count.
+    pub fn cycles(&self) -> u64 {
+        self.cycles
+    }
+
+    /// Capture CHIP-8 state before executing next instruction.
+    ///
+    /// # Returns
+    /// - Trace entry comparable with high-level CPU one.
+    pub fn trace_entry(&self) -> TraceEntry {
+        let pc = self.pc();
+        let memory = &self.hardware.ram;
+        let opcode = u16::from_be_bytes([
+            memory[pc as usize % RAM_SIZE],
+            memory[(pc as usize + 1) % RAM_SIZE],
+        ]);
+        let timers = self.cpu.register(TIMER_REGISTER);
+        let depth = STACK_TOP.wrapping_sub(self.cpu.register(2)) / 2;
+
+        TraceEntry {
+            cycle: self.instructions,
+            pc,
+            opcode,
+            registers: self.registers(),
+            register_i: self.register_i(),
+            sp: depth as u8,
+            dt: (timers >> 8) as u8,
+            st: timers as u8,
+        }
+    }
+}
+
+/// Run high-level CPU and COSMAC VIP in lockstep and find first divergence.
+///
+/// Program memory below the interpreter work area and display buffer are
+/// compared after every instruction.
+///
+/// # Parameters
+/// - `cpu`   - given high-level CPU with loaded program.
+/// - `vip`   - given COSMAC VIP with loaded program.
+/// - `speed` - given number of high-level instructions per frame.
+/// - `limit` - given maximum number of instructions to execute.
+///
+/// # Returns
+/// - First divergence - if executions differ.
+/// - `None`           - otherwise.
+/// - `Err`            - if both machines failed at the same point.
+pub fn diff(
+    cpu: &mut Cpu,
+    vip: &mut Vip,
+    speed: usize,
+    limit: u64,
+) -> EmulatorResult<Option<Divergence>> {
+    let mut context = VecDeque::with_capacity(trace::CONTEXT_SIZE);
+
+    for _ in 0..limit {
+        let entry_a = TraceEntry::capture(cpu);
+        let entry_b = vip.trace_entry();
+
+        if entry_a != entry_b {
+            return Ok(Some(divergence(&context, cpu, vip, entry_a, entry_b)));
+        }
+
+        match (cpu.cycle(speed), vip.step()) {
+            (Ok(_), Ok(_)) => {}
+            (Err(error), Err(_)) => return Err(error),
+            _ => {
+                let divergence =
+                    divergence(&context, cpu, vip, entry_a, entry_b);
+                return Ok(Some(divergence));
+            }
+        }
+
+        let divergence = divergence(&context, cpu, vip, entry_a, entry_b);
+
+        if !divergence.memory.is_empty() {
+            return Ok(Some(divergence));
+        }
+
+        if context.len() == trace::CONTEXT_SIZE {
+            context.pop_front();
+        }
+
+        context.push_back(entry_a);
+    }
+
+    Ok(None)
+}
+
+/// Construct divergence report of high-level CPU and COSMAC VIP.
+///
+/// Display differences are reported at VIP display buffer addresses.
+///
+/// # Parameters
+/// - `context` - given instructions executed before divergence.
+/// - `cpu`     - given high-level CPU.
+/// - `vip`     - given COSMAC VIP.
+/// - `first`   - given high-level CPU state.
+/// - `second`  - given COSMAC VIP state.
+///
+/// # Returns
+/// - New `Divergence` object.
+fn divergence(
+    context: &VecDeque<TraceEntry>,
+    cpu: &Cpu,
+    vip: &Vip,
+    first: TraceEntry,
+    second: TraceEntry,
+) -> Divergence {
+    let program = START_ADDR..WORK_AREA;
+    let mut memory: Vec<_> = program
+        .clone()
+        .zip(&cpu.memory()[program.clone()])
+        .zip(&vip.memory()[program])
+        .filter(|((_, a), b)| a != b)
+        .map(|((addr, a), b)| (addr, *a, *b))
+        .collect();
+
+    let display = cpu.display().chunks(8).map(|pixels| {
+        pixels.iter().fold(0u8, |byte, &on| (byte << 1) | on as u8)
+    });
+
+    memory.extend(
+        display
+            .zip(&vip.memory()[DISPLAY_ADDR..])
+            .enumerate()
+            .filter(|(_, (a, b))| a != *b)
+            .map(|(i, (a, b))| (DISPLAY_ADDR + i, a, *b)),
+    );
+
+    Divergence {
+        context: context.iter().copied().collect(),
+        first: Some(first),
+        second: Some(second),
+        memory,
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::emulator::{
+        quirks::Quirks,
+        random::{Algorithm, Random},
+    };
+
+    /// Minimal interpreter fetching both bytes of every CHIP-8 instruction
+    /// with LDA R5 and storing the low byte to V0.
+    const INTERPRETER: [u8; 19] = [
+        0xF8, 0x02, 0xB5, // LDI 02; PHI 5
+        0xF8, 0x0E, 0xB2, 0xB6, // LDI 0E; PHI 2; PHI 6
+        0xF8, 0xCF, 0xA2, // LDI CF; PLO 2
+        0xF8, 0xF0, 0xA6, // LDI F0; PLO 6
+        0x45, 0xAF, 0x45, // LDA 5; PLO F; LDA 5
+        0x56, // STR 6
+        0x30, 0x0D, // BR 0D
+    ];
+
+    #[test]
+    fn test_vip_steps() {
+        let mut vip = Vip::new(&INTERPRETER, None).unwrap();
+
+        vip.load_program(&[0x60, 0x12, 0x60, 0x34]).unwrap();
+        assert_eq!(0x200, vip.pc());
+        assert_eq!(0, vip.trace_entry().cycle);
+
+        vip.step().unwrap();
+        assert_eq!(0x202, vip.pc());
+        assert_eq!(0x12, vip.registers()[0]);
+        assert_eq!(0x6034, vip.trace_entry().opcode);
+
+        vip.step().unwrap();
+        assert_eq!(0x34, vip.registers()[0]);
+        assert_eq!(2, vip.trace_entry().cycle);
+
+        let cycles = vip.cycles();
+        vip.frame();
+        assert!(vip.cycles() - cycles <= FRAME_CYCLES as u64);
+    }
+
+    #[test]
+    fn test_vip_images() {
+        assert!(Vip::new(&[], None).is_err());
+        assert!(Vip::new(&[0; INTERPRETER_SIZE + 1], None).is_err());
+        assert!(Vip::new(&INTERPRETER, Some(&[0; 16])).is_err());
+
+        let mut vip = Vip::new(&INTERPRETER, Some(&[0; MONITOR_SIZE])).unwrap();
+        assert!(vip.load_program(&[0; WORK_AREA]).is_err());
+
+        // Interpreter without fetch loop never becomes ready.
+        let mut idle = Vip::new(&[0x00], None).unwrap();
+        assert!(idle.load_program(&[0x00, 0xE0]).is_err());
+    }
+
+    #[test]
+    fn test_copy_random_table() {
+        let mut interpreter = [0; INTERPRETER_SIZE];
+        interpreter[VIP_TABLE_PAGE + 1] = 0x42;
+
+        let vip = Vip::new(&interpreter, None).unwrap();
+        let mut cpu = Cpu::with_quirks(Quirks::chip8());
+        cpu.set_random(Random::new(Algorithm::Vip, 0));
+
+        // RND V0, 0xFF adds table byte of seed 0x0001 to the high byte.
+        vip.copy_random_table(&mut cpu).unwrap();
+        cpu.load_program(&[0xC0, 0xFF]).unwrap();
+        cpu.step().unwrap();
+
+        assert_eq!(0x42, cpu.registers()[0]);
+        assert_eq!(START_ADDR as u16 + 2, cpu.pc());
+    }
+
+    #[test]
+    fn test_diff_detects_divergence() {
+        // The minimal interpreter stores every low opcode byte to V0.
+        let program = [0x60, 0x01, 0x61, 0x05, 0x12, 0x04];
+        let mut vip = Vip::new(&INTERPRETER, None).unwrap();
+        vip.load_program(&program).unwrap();
+
+        let mut cpu = Cpu::new();
+        cpu.load_program(&program).unwrap();
+
+        let divergence = diff(&mut cpu, &mut vip, 10, 10).unwrap().unwrap();
+        let first = divergence.first.unwrap();
+
+        assert_eq!(2, divergence.context.len());
+        assert_eq!(0x204, first.pc);
+        assert_eq!(
+            vec!["V0: 01 != 05", "V1: 05 != 00"],
+            first.differences(&divergence.second.unwrap())
+        );
+    }
+}
@@ -0,0 +1,669 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! RCA CDP1802 CPU core.
+
+/// Machine cycles of regular instruction (fetch and execute).
+pub const INSTRUCTION_CYCLES: u32 = 2;
+
+/// Machine cycles of long branch and long skip instructions.
+pub const LONG_CYCLES: u32 = 3;
+
+/// Machine cycles of interrupt acknowledge, idle or single DMA transfer.
+pub const CYCLE: u32 = 1;
+
+/// System bus connecting CPU with memory and I/O devices.
+pub trait Bus {
+    /// Read memory byte.
+    ///
+    /// # Parameters
+    /// - `addr` - given memory address.
+    ///
+    /// # Returns
+    /// - Memory byte.
+    fn read(&mut self, addr: u16) -> u8;
+
+    /// Write memory byte.
+    ///
+    /// # Parameters
+    /// - `addr`  - given memory address.
+    /// - `value` - given byte to write.
+    fn write(&mut self, addr: u16, value: u8);
+
+    /// Handle OUT instruction.
+    ///
+    /// # Parameters
+    /// - `port`  - given output port (1-7).
+    /// - `value` - given output byte.
+    fn output(&mut self, port: u8, value: u8);
+
+    /// Handle INP instruction.
+    ///
+    /// # Parameters
+    /// - `port` - given input port (1-7).
+    ///
+    /// # Returns
+    /// - Input byte.
+    fn input(&mut self, port: u8) -> u8;
+
+    /// Get external flag line state.
+    ///
+    /// # Parameters
+    /// - `flag` - given flag number (1-4).
+    ///
+    /// # Returns
+    /// - `true` - if flag line is asserted.
+    fn flag(&self, flag: u8) -> bool;
+}
+
+/// RCA CDP1802 CPU state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cdp1802 {
+    /// Scratchpad registers R0-RF.
+    registers: [u16; 16],
+    /// Data register (accumulator).
+    d: u8,
+    /// Data flag (carry, NOT borrow).
+    df: bool,
+    /// Program counter register designator.
+    p: u8,
+    /// Data pointer register designator.
+    x: u8,
+    /// Saved X and P after interrupt.
+    t: u8,
+    /// Interrupt enable flip-flop.
+    ie: bool,
+    /// Q output flip-flop.
+    q: bool,
+    /// Whether CPU waits for interrupt or DMA after IDL.
+    idle: bool,
+    /// Last executed instruction.
+    opcode: u8,
+}
+
+impl Cdp1802 {
+    /// Construct new `Cdp1802` object in reset state.
+    ///
+    /// # Returns
+    /// - New `Cdp1802` object executing from address 0 with R0.
+    pub fn new() -> Self {
+        Self {
+            registers: [0; 16],
+            d: 0,
+            df: false,
+            p: 0,
+            x: 0,
+            t: 0,
+            ie: true,
+            q: false,
+            idle: false,
+            opcode: 0,
+        }
+    }
+
+    /// Get scratchpad register.
+    ///
+    /// # Parameters
+    /// - `index` - given register number.
+    ///
+    /// # Returns
+    /// - Register value.
+    pub fn register(&self, index: usize) -> u16 {
+        self.registers[index & 0xF]
+    }
+
+    /// Set scratchpad register.
+    ///
+    /// # Parameters
+    /// - `index` - given register number.
+    /// - `value` - given register value.
+    pub fn set_register(&mut self, index: usize, value: u16) {
+        self.registers[index & 0xF] = value;
+    }
+
+    /// Get data register.
+    ///
+    /// # Returns
+    /// - D register value.
+    pub fn d(&self) -> u8 {
+        self.d
+    }
+
+    /// Get data flag.
+    ///
+    /// # Returns
+    /// - DF flag value.
+    pub fn df(&self) -> bool {
+        self.df
+    }
+
+    /// Get program counter register designator.
+    ///
+    /// # Returns
+    /// - P register value.
+    pub fn p(&self) -> u8 {
+        self.p
+    }
+
+    /// Get data pointer register designator.
+    ///
+    /// # Returns
+    /// - X register value.
+    pub fn x(&self) -> u8 {
+        self.x
+    }
+
+    /// Get Q output.
+    ///
+    /// # Returns
+    /// - `true` - if Q is set.
+    pub fn q(&self) -> bool {
+        self.q
+    }
+
+    /// Check whether interrupts are enabled.
+    ///
+    /// # Returns
+    /// - `true` - if IE is set.
+    pub fn ie(&self) -> bool {
+        self.ie
+    }
+
+    /// Get last executed instruction.
+    ///
+    /// # Returns
+    /// - Instruction byte.
+    pub fn opcode(&self) -> u8 {
+        self.opcode
+    }
+
+    /// Get address of the next instruction.
+    ///
+    /// # Returns
+    /// - Value of register designated by P.
+    pub fn pc(&self) -> u16 {
+        self.registers[self.p as usize]
+    }
+
+    /// Acknowledge interrupt request if interrupts are enabled.
+    ///
+    /// # Returns
+    /// - `true` - if interrupt was taken.
+    pub fn interrupt(&mut self) -> bool {
+        if !self.ie {
+            return false;
+        }
+
+        self.t = (self.x << 4) | self.p;
+        self.p = 1;
+        self.x = 2;
+        self.ie = false;
+        self.idle = false;
+
+        true
+    }
+
+    /// Perform single DMA output cycle.
+    ///
+    /// # Parameters
+    /// - `bus` - given system bus.
+    ///
+    /// # Returns
+    /// - Byte read from memory at R0.
+    pub fn dma_out(&mut self, bus: &mut impl Bus) -> u8 {
+        let value = bus.read(self.registers[0]);
+
+        self.registers[0] = self.registers[0].wrapping_add(1);
+        self.idle = false;
+
+        value
+    }
+
+    /// Execute single instruction.
+    ///
+    /// # Parameters
+    /// - `bus` - given system bus.
+    ///
+    /// # Returns
+    /// - Number of machine cycles spent.
+    pub fn step(&mut self, bus: &mut impl Bus) -> u32 {
+        if self.idle {
+            return CYCLE;
+        }
+
+        let opcode = self.immediate(bus);
+        let n = opcode & 0xF;
+
+        self.opcode = opcode;
+
+        match opcode >> 4 {
+            0x0 if n == 0 => self.idle = true,
+            0x0 => self.d = bus.read(self.reg(n)),
+            0x1 => self.add_reg(n, 1),
+            0x2 => self.add_reg(n, u16::MAX),
+            0x3 => {
+                let taken = self.condition(n & 0x7, bus) != (n & 0x8 != 0);
+                self.short_branch(taken, bus);
+            }
+            0x4 => {
+                self.d = bus.read(self.reg(n));
+                self.add_reg(n, 1);
+            }
+            0x5 => bus.write(self.reg(n), self.d),
+            0x6 => self.io(n, bus),
+            0x7 => self.control(n, bus),
+            0x8 => self.d = self.reg(n) as u8,
+            0x9 => self.d = (self.reg(n) >> 8) as u8,
+            0xA => {
+                let value = (self.reg(n) & 0xFF00) | self.d as u16;
+                self.set_reg(n, value);
+            }
+            0xB => {
+                let value = (self.reg(n) & 0x00FF) | (self.d as u16) << 8;
+                self.set_reg(n, value);
+            }
+            0xC => {
+                self.long(n, bus);
+                return LONG_CYCLES;
+            }
+            0xD => self.p = n,
+            0xE => self.x = n,
+            _ => {
+                let operand = match n {
+                    0x6 | 0xE => 0,
+                    0x0..=0x7 => bus.read(self.reg(self.x)),
+                    _ => self.immediate(bus),
+                };
+
+                self.alu(n & 0x7, n & 0x8 != 0, operand, None);
+            }
+        }
+
+        INSTRUCTION_CYCLES
+    }
+
+    /// Get scratchpad register.
+    ///
+    /// # Parameters
+    /// - `n` - given register number.
+    ///
+    /// # Returns
+    /// - Register value.
+    fn reg(&self, n: u8) -> u16 {
+        self.registers[n as usize]
+    }
+
+    /// Set scratchpad register.
+    ///
+    /// # Parameters
+    /// - `n`     - given register number.
+    /// - `value` - given register value.
+    fn set_reg(&mut self, n: u8, value: u16) {
+        self.registers[n as usize] = value;
+    }
+
+    /// Add value to scratchpad register with wrapping.
+    ///
+    /// # Parameters
+    /// - `n`     - given register number.
+    /// - `value` - given value to add.
+    fn add_reg(&mut self, n: u8, value: u16) {
+        self.set_reg(n, self.reg(n).wrapping_add(value));
+    }
+
+    /// Read byte at program counter and advance it.
+    ///
+    /// # Parameters
+    /// - `bus` - given system bus.
+    ///
+    /// # Returns
+    /// - Read byte.
+    fn immediate(&mut self, bus: &mut impl Bus) -> u8 {
+        let value = bus.read(self.reg(self.p));
+
+        self.add_reg(self.p, 1);
+        value
+    }
+
+    /// Evaluate short branch condition.
+    ///
+    /// # Parameters
+    /// - `condition` - given condition number (always, Q, Z, DF, EF1-EF4).
+    /// - `bus`       - given system bus.
+    ///
+    /// # Returns
+    /// - `true` - if condition is met.
+    fn condition(&self, condition: u8, bus: &impl Bus) -> bool {
+        match condition {
+            0 => true,
+            1 => self.q,
+            2 => self.d == 0,
+            3 => self.df,
+            flag => bus.flag(flag - 3),
+        }
+    }
+
+    /// Execute short branch replacing low byte of program counter.
+    ///
+    /// # Parameters
+    /// - `taken` - given whether branch is taken.
+    /// - `bus`   - given system bus.
+    fn short_branch(&mut self, taken: bool, bus: &mut impl Bus) {
+        let pc = self.reg(self.p);
+
+        if taken {
+            let target = bus.read(pc) as u16;
+            self.set_reg(self.p, (pc & 0xFF00) | target);
+        } else {
+            self.set_reg(self.p, pc.wrapping_add(1));
+        }
+    }
+
+    /// Execute long branch, long skip or NOP instruction.
+    ///
+    /// # Parameters
+    /// - `n`   - given low instruction nibble.
+    /// - `bus` - given system bus.
+    fn long(&mut self, n: u8, bus: &mut impl Bus) {
+        let (branch, taken) = match n {
+            0x0 => (true, true),
+            0x1 => (true, self.q),
+            0x2 => (true, self.d == 0),
+            0x3 => (true, self.df),
+            0x4 => (false, false),
+            0x5 => (false, !self.q),
+            0x6 => (false, self.d != 0),
+            0x7 => (false, !self.df),
+            0x8 => (false, true),
+            0x9 => (true, !self.q),
+            0xA => (true, self.d != 0),
+            0xB => (true, !self.df),
+            0xC => (false, self.ie),
+            0xD => (false, self.q),
+            0xE => (false, self.d == 0),
+            _ => (false, self.df),
+        };
+
+        let pc = self.reg(self.p);
+
+        let target = match (branch, taken) {
+            (true, true) => {
+                let high = bus.read(pc);
+                let low = bus.read(pc.wrapping_add(1));
+                u16::from_be_bytes([high, low])
+            }
+            (false, false) => pc,
+            _ => pc.wrapping_add(2),
+        };
+
+        self.set_reg(self.p, target);
+    }
+
+    /// Execute input/output instruction.
+    ///
+    /// # Parameters
+    /// - `n`   - given low instruction nibble.
+    /// - `bus` - given system bus.
+    fn io(&mut self, n: u8, bus: &mut impl Bus) {
+        let addr = self.reg(self.x);
+
+        match n {
+            0x0 => self.add_reg(self.x, 1),
+            0x1..=0x7 => {
+                let value = bus.read(addr);
+
+                bus.output(n, value);
+                self.add_reg(self.x, 1);
+            }
+            // 68 is not defined on the 1802.
+            0x8 => {}
+            _ => {
+                self.d = bus.input(n - 8);
+                bus.write(addr, self.d);
+            }
+        }
+    }
+
+    /// Execute control, stack and carry arithmetic instruction.
+    ///
+    /// # Parameters
+    /// - `n`   - given low instruction nibble.
+    /// - `bus` - given system bus.
+    fn control(&mut self, n: u8, bus: &mut impl Bus) {
+        let addr = self.reg(self.x);
+
+        match n {
+            0x0 | 0x1 => {
+                let value = bus.read(addr);
+
+                self.add_reg(self.x, 1);
+                self.x = value >> 4;
+                self.p = value & 0xF;
+                self.ie = n == 0x0;
+            }
+            0x2 => {
+                self.d = bus.read(addr);
+                self.add_reg(self.x, 1);
+            }
+            0x3 => {
+                bus.write(addr, self.d);
+                self.add_reg(self.x, u16::MAX);
+            }
+            0x8 => bus.write(addr, self.t),
+            0x9 => {
+                self.t = (self.x << 4) | self.p;
+                bus.write(self.reg(2), self.t);
+                self.x = self.p;
+                self.add_reg(2, u16::MAX);
+            }
+            0xA => self.q = false,
+            0xB => self.q = true,
+            _ => {
+                let operand = match n {
+                    0x6 | 0xE => 0,
+                    0x4..=0x7 => bus.read(addr),
+                    _ => self.immediate(bus),
+                };
+
+                self.alu(n & 0x7, n & 0x8 != 0, operand, Some(self.df));
+            }
+        }
+    }
+
+    /// Execute arithmetic or logic operation on D register.
+    ///
+    /// # Parameters
+    /// - `operation` - given operation (load, OR, AND, XOR, add, subtract D,
+    ///   shift, subtract memory).
+    /// - `left`      - given whether shift is to the left.
+    /// - `operand`   - given memory or immediate operand.
+    /// - `carry`     - given DF for carry variants, `None` otherwise.
+    fn alu(
+        &mut self,
+        operation: u8,
+        left: bool,
+        operand: u8,
+        carry: Option<bool>,
+    ) {
+        // Subtraction adds complement with carry in set unless borrowing.
+        let carry_in = carry.unwrap_or(matches!(operation, 0x5 | 0x7));
+        let sum = |a: u8, b: u8| a as u16 + b as u16 + carry_in as u16;
+
+        let result = match operation {
+            0x0 => operand as u16,
+            0x1 => (self.d | operand) as u16,
+            0x2 => (self.d & operand) as u16,
+            0x3 => (self.d ^ operand) as u16,
+            0x4 => sum(self.d, operand),
+            0x5 => sum(operand, !self.d),
+            0x6 => {
+                let shift_in = carry.unwrap_or(false);
+                let (shifted, shifted_out) = match left {
+                    true => (self.d << 1 | shift_in as u8, self.d >> 7),
+                    false => (self.d >> 1 | (shift_in as u8) << 7, self.d & 1),
+                };
+
+                self.df = shifted_out != 0;
+                self.d = shifted;
+                return;
+            }
+            _ => sum(self.d, !operand),
+        };
+
+        if operation >= 0x4 {
+            self.df = result > 0xFF;
+        }
+
+        self.d = result as u8;
+    }
+}
+
+impl Default for Cdp1802 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// Flat 64 KB memory bus with recorded output.
+    pub struct Memory {
+        /// Memory bytes.
+        pub bytes: Vec<u8>,
+        /// Output port writes.
+        pub output: Vec<(u8, u8)>,
+        /// External flags EF1-EF4.
+        pub flags: [bool; 4],
+    }
+
+    impl Memory {
+        /// Construct memory with program at address 0.
+        ///
+        /// # Parameters
+        /// - `program` - given machine code.
+        ///
+        /// # Returns
+        /// - New `Memory` object.
+        pub fn new(program: &[u8]) -> Self {
+            let mut bytes = vec![0; 0x10000];
+            bytes[..program.len()].copy_from_slice(program);
+
+            Self {
+                bytes,
+                output: Vec::new(),
+                flags: [false; 4],
+            }
+        }
+    }
+
+    impl Bus for Memory {
+        fn read(&mut self, addr: u16) -> u8 {
+            self.bytes[addr as usize]
+        }
+
+        fn write(&mut self, addr: u16, value: u8) {
+            self.bytes[addr as usize] = value;
+        }
+
+        fn output(&mut self, port: u8, value: u8) {
+            self.output.push((port, value));
+        }
+
+        fn input(&mut self, port: u8) -> u8 {
+            port * 0x11
+        }
+
+        fn flag(&self, flag: u8) -> bool {
+            self.flags[flag as usize - 1]
+        }
+    }
+
+    /// Execute instructions until IDL.
+    ///
+    /// # Parameters
+    /// - `program` - given machine code.
+    ///
+    /// # Returns
+    /// - CPU, memory and spent machine cycles.
+    fn run(program: &[u8]) -> (Cdp1802, Memory, u32) {
+        let mut cpu = Cdp1802::new();
+        let mut memory = Memory::new(program);
+        let mut cycles = 0;
+
+        while !cpu.idle {
+            cycles += cpu.step(&mut memory);
+        }
+
+        (cpu, memory, cycles)
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        // LDI 5; PLO 3; LDI 0x80; SHL; GLO 3; ADCI 0xFF; PHI 4; SMI 6;
+        // PLO 4; IDL
+        let (cpu, ..) = run(&[
+            0xF8, 0x05, 0xA3, 0xF8, 0x80, 0xFE, 0x83, 0x7C, 0xFF, 0xB4, 0xFF,
+            0x06, 0xA4, 0x00,
+        ]);
+
+        // 5 + 0xFF + carry = 0x105, then 5 - 6 borrows.
+        assert_eq!(0x05FF, cpu.register(4));
+        assert!(!cpu.df());
+    }
+
+    #[test]
+    fn test_branches_and_io() {
+        let mut program = vec![0; 0x20];
+
+        // SEX 2; LDI 40; PLO 2; LBR 0010; IDL
+        program[..8]
+            .copy_from_slice(&[0xE2, 0xF8, 0x40, 0xA2, 0xC0, 0x00, 0x10, 0x00]);
+        // SEQ; BQ 14; IDL
+        program[0x10..0x14].copy_from_slice(&[0x7B, 0x31, 0x14, 0x00]);
+        // LDI 42; STXD; INP 3; OUT 4; LSZ; IDL
+        program[0x14..0x1B]
+            .copy_from_slice(&[0xF8, 0x42, 0x73, 0x6B, 0x64, 0xCE, 0x00]);
+
+        let (cpu, memory, cycles) = run(&program);
+
+        assert!(cpu.q());
+        assert_eq!(0x33, cpu.d());
+        assert_eq!(0x42, memory.bytes[0x40]);
+        assert_eq!(0x33, memory.bytes[0x3F]);
+        assert_eq!(vec![(4, 0x33)], memory.output);
+        assert_eq!(0x40, cpu.register(2));
+        assert_eq!(0x1B, cpu.pc());
+        assert_eq!(10 * INSTRUCTION_CYCLES + 2 * LONG_CYCLES, cycles);
+    }
+
+    #[test]
+    fn test_interrupt() {
+        // SEX 3; LDI 30; PLO 1; NOP
+        let mut program = vec![0; 0x40];
+        program[..5].copy_from_slice(&[0xE3, 0xF8, 0x30, 0xA1, 0xC4]);
+        // Handler: SAV; RET
+        program[0x30..0x32].copy_from_slice(&[0x78, 0x70]);
+
+        let mut cpu = Cdp1802::new();
+        let mut memory = Memory::new(&program);
+        cpu.set_register(2, 0x20);
+
+        for _ in 0..3 {
+            cpu.step(&mut memory);
+        }
+
+        assert!(cpu.interrupt());
+        assert!(!cpu.interrupt());
+        assert_eq!(1, cpu.p());
+
+        cpu.step(&mut memory);
+        cpu.step(&mut memory);
+
+        assert_eq!(0x30, memory.bytes[0x20]);
+        assert!(cpu.ie());
+        assert_eq!((3, 0), (cpu.x(), cpu.p()));
+        assert_eq!(4, cpu.pc());
+    }
+}
@@ -0,0 +1,196 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! RCA CDP1861 video display controller timing.
+
+/// Machine cycles per display line.
+pub const LINE_CYCLES: u32 = 14;
+
+/// Display lines per frame.
+pub const FRAME_LINES: u32 = 262;
+
+/// Machine cycles per frame.
+pub const FRAME_CYCLES: u32 = LINE_CYCLES * FRAME_LINES;
+
+/// Number of bytes transferred by DMA on each visible line.
+pub const DMA_BYTES: usize = 8;
+
+/// Number of visible display lines.
+pub const VISIBLE_LINES: usize = 128;
+
+/// First visible display line.
+const FIRST_LINE: u32 = 64;
+
+/// Display line asserting interrupt request (two lines before display).
+const INTERRUPT_LINE: u32 = 62;
+
+/// Number of lines EF1 is asserted before display start and end.
+const EF1_LINES: u32 = 4;
+
+/// CDP1861 video display controller state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cdp1861 {
+    /// Whether display and interrupts are enabled.
+    enabled: bool,
+    /// Machine cycle within the current frame.
+    cycle: u32,
+    /// Last visible line served by DMA in the current frame.
+    dma_line: Option<u32>,
+    /// Bytes received by DMA during the current frame.
+    output: [u8; DMA_BYTES * VISIBLE_LINES],
+    /// Number of finished frames.
+    frames: u64,
+}
+
+impl Cdp1861 {
+    /// Construct new `Cdp1861` object with display disabled.
+    ///
+    /// # Returns
+    /// - New `Cdp1861` object at the start of frame.
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            cycle: 0,
+            dma_line: None,
+            output: [0; DMA_BYTES * VISIBLE_LINES],
+            frames: 0,
+        }
+    }
+
+    /// Enable or disable display (INP 1 and OUT 1 on the COSMAC VIP).
+    ///
+    /// # Parameters
+    /// - `enabled` - given display state.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Get current display line.
+    ///
+    /// # Returns
+    /// - Line number within frame.
+    pub fn line(&self) -> u32 {
+        self.cycle / LINE_CYCLES
+    }
+
+    /// Get number of finished frames.
+    ///
+    /// # Returns
+    /// - Frame counter.
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Check whether interrupt request line is asserted.
+    ///
+    /// # Returns
+    /// - `true` - during two lines before display if display is enabled.
+    pub fn interrupt(&self) -> bool {
+        self.enabled && (INTERRUPT_LINE..FIRST_LINE).contains(&self.line())
+    }
+
+    /// Check whether EF1 line is asserted.
+    ///
+    /// # Returns
+    /// - `true` - during four lines before display start and end.
+    pub fn ef1(&self) -> bool {
+        let line = self.line();
+        let end = FIRST_LINE + VISIBLE_LINES as u32;
+
+        self.enabled
+            && ((FIRST_LINE - EF1_LINES..FIRST_LINE).contains(&line)
+                || (end - EF1_LINES..end).contains(&line))
+    }
+
+    /// Check whether DMA transfer of visible line is requested.
+    ///
+    /// # Returns
+    /// - `true` - once per visible line if display is enabled.
+    pub fn dma_requested(&self) -> bool {
+        let line = self.line();
+        let visible = FIRST_LINE..FIRST_LINE + VISIBLE_LINES as u32;
+
+        self.enabled && visible.contains(&line) && self.dma_line != Some(line)
+    }
+
+    /// Receive bytes of visible line transferred by DMA.
+    ///
+    /// # Parameters
+    /// - `bytes` - given display line bytes.
+    pub fn dma(&mut self, bytes: [u8; DMA_BYTES]) {
+        let line = self.line();
+        let start = (line - FIRST_LINE) as usize * DMA_BYTES;
+
+        self.output[start..start + DMA_BYTES].copy_from_slice(&bytes);
+        self.dma_line = Some(line);
+    }
+
+    /// Advance display timing.
+    ///
+    /// # Parameters
+    /// - `cycles` - given number of elapsed machine cycles.
+    ///
+    /// # Returns
+    /// - `true` - if frame was finished.
+    pub fn advance(&mut self, cycles: u32) -> bool {
+        self.cycle += cycles;
+
+        if self.cycle < FRAME_CYCLES {
+            return false;
+        }
+
+        self.cycle -= FRAME_CYCLES;
+        self.dma_line = None;
+        self.frames += 1;
+
+        true
+    }
+
+    /// Get bytes received by DMA.
+    ///
+    /// # Returns
+    /// - Eight bytes of each of 128 visible lines.
+    pub fn output(&self) -> &[u8; DMA_BYTES * VISIBLE_LINES] {
+        &self.output
+    }
+}
+
+impl Default for Cdp1861 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_timing() {
+        let mut video = Cdp1861::new();
+
+        assert!(!video.advance(INTERRUPT_LINE * LINE_CYCLES));
+        assert!(!video.interrupt());
+
+        video.set_enabled(true);
+        assert!(video.interrupt());
+        assert!(video.ef1());
+        assert!(!video.dma_requested());
+
+        video.advance(2 * LINE_CYCLES);
+        assert!(!video.interrupt());
+        assert!(video.dma_requested());
+
+        video.dma([0xAA; DMA_BYTES]);
+        assert!(!video.dma_requested());
+        assert_eq!(0xAA, video.output()[0]);
+
+        video.advance(LINE_CYCLES);
+        assert!(video.dma_requested());
+
+        let remaining = FRAME_CYCLES - (FIRST_LINE + 1) * LINE_CYCLES;
+        assert!(video.advance(remaining));
+        assert_eq!(1, video.frames());
+        assert_eq!(0, video.line());
+    }
+}
@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2025-present chip8 emulator project and contributors
+
+//! CHIP-8 emulator library.
+//!
+//! Exposes the machine (`emulator::cpu`), its quirks and random number
+//! generators, and the reinforcement-learning environment and batch APIs
+//! built on top of it. The `chip8` executable is a frontend to this library.
+
+pub mod emulator;
@@ -3,23 +3,42 @@
 
 //! Emulator entry point.
 
-// Allow unused code.
-#![allow(dead_code)]
-
 mod args;
 mod config;
-mod emulator;
 
-use crate::{config::Config, emulator::Emulator};
+use crate::{args::Command, config::Config};
+use chip8::emulator::{Emulator, config_file};
+use std::{env, process};
 
 fn main() {
-    let (mode, filename) = args::handle_args();
-    let mut emulator = Emulator::new();
+    let arguments: Vec<String> = env::args().skip(1).collect();
+    let default_config = config_file::default_path();
+    let name = Config::name();
+
+    let options = match args::parse(&arguments, default_config.as_deref()) {
+        Ok(options) => options,
+        Err(error) => {
+            println!("{name}: {error}");
+            println!("{name}: Use '{name} help' for usage.");
+            process::exit(1);
+        }
+    };
 
-    if let Err(error) = emulator.run(mode, filename) {
-        // TODO: add custom error macro.
-        let name = Config::name();
+    match options.command {
+        Command::Help(command) => print!("{}", args::help(command)),
+        Command::Version => print!("{}", args::version()),
+        Command::Completions(shell) => print!("{}", args::completions(shell)),
+        Command::Config => {
+            print!("{}", config_file::render(&options.settings, &options.paths))
+        }
+        Command::Emulate(mode, filename) => {
+            let mut emulator = Emulator::new(options.settings);
 
-        println!("{name}: {error}");
+            if let Err(error) = emulator.run(mode, filename) {
+                // TODO: add custom error macro.
+                println!("{name}: {error}");
+                process::exit(1);
+            }
+        }
     }
 }